@@ -0,0 +1,117 @@
+use std::fmt;
+
+use crate::parser::{Parser, ParserError};
+use crate::token::{Span, Token, TokenKind};
+
+/// An optional type annotation, as in `let x: int = 1;` or `fn(a: int) -> bool { ... }`.
+///
+/// Annotations are only read by the type checker; evaluation ignores them.
+#[derive(Debug, Clone)]
+pub enum Annotation {
+    /// A type name like `int`, or a type variable like `a`.
+    Named(Token),
+    /// `fn(int, int) -> int`.
+    Function {
+        token: Token,
+        parameters: Vec<Annotation>,
+        result: Box<Annotation>,
+    },
+}
+
+impl Annotation {
+    pub fn parse(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
+        if parser
+            .token()
+            .is_none_or(|token| token.kind() != TokenKind::Function)
+        {
+            return parser
+                .expect_token_with_kind(TokenKind::Identifier)
+                .map(Self::Named);
+        }
+
+        parser.nested(Self::parse_function)
+    }
+
+    fn parse_function(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::Function)?;
+        _ = parser.expect_token_with_kind(TokenKind::LParenthesis)?;
+
+        let mut parameters = Vec::new();
+
+        if parser
+            .token()
+            .is_some_and(|token| token.kind() != TokenKind::RParenthesis)
+        {
+            parameters.push(Self::parse(parser)?);
+
+            while let Some(TokenKind::Comma) = parser.token().map(Token::kind) {
+                _ = parser.expect_token_with_kind(TokenKind::Comma)?;
+                parameters.push(Self::parse(parser)?);
+            }
+        }
+
+        _ = parser.expect_token_with_kind(TokenKind::RParenthesis)?;
+        _ = parser.expect_token_with_kind(TokenKind::Arrow)?;
+        let result = Box::new(Self::parse(parser)?);
+
+        Ok(Self::Function {
+            token,
+            parameters,
+            result,
+        })
+    }
+
+    /// Parse `introducer annotation` (e.g. `: int` or `-> bool`) if the parser is sitting on
+    /// `introducer`.
+    pub fn parse_optional(
+        parser: &mut Parser<'_>,
+        introducer: TokenKind,
+    ) -> Result<Option<Self>, ParserError> {
+        if parser
+            .token()
+            .is_some_and(|token| token.kind() == introducer)
+        {
+            parser.advance();
+            Self::parse(parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match *self {
+            Self::Named(ref token) => token.span(),
+            Self::Function {
+                ref token,
+                ref result,
+                ..
+            } => token.span().to(result.span()),
+        }
+    }
+}
+
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Named(ref token) => token.literal().fmt(f),
+            Self::Function {
+                ref parameters,
+                ref result,
+                ..
+            } => {
+                f.write_str("fn(")?;
+
+                for (index, parameter) in parameters.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+
+                    parameter.fmt(f)?;
+                }
+
+                f.write_str(") -> ")?;
+                result.fmt(f)
+            }
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Static checks that run over a parsed `Program` without evaluating it.
+
+use crate::diagnostic::Diagnostic;
+use crate::expression::{Expression, Match};
+use crate::pattern::Pattern;
+use crate::prelude;
+use crate::program::Program;
+use crate::resolver::{BUILTINS, resolve};
+use crate::statement::{Block, Enum, Statement};
+use crate::types;
+use crate::visitor::{Visitor, walk_block, walk_match, walk_program};
+
+/// Report undefined names, duplicate parameters, calls with the wrong number of arguments to
+/// functions whose definition is known, type mismatches, statements that can never run, and
+/// `match`es over booleans or enum variants that miss a case. Diagnostics are sorted by where
+/// they appear in the source.
+pub fn check(program: &Program) -> Vec<Diagnostic> {
+    let resolution = resolve(program);
+    let mut diagnostics = Vec::new();
+
+    for reference in resolution.references() {
+        if reference.binding().is_none()
+            && !BUILTINS.contains(&reference.name())
+            && !prelude::NAMES.contains(&reference.name())
+        {
+            let message = format!("identifier {:?} is not defined", reference.name());
+            diagnostics.push(Diagnostic::error(reference.span(), message));
+        }
+    }
+
+    for &index in resolution.duplicate_parameters() {
+        let binding = &resolution.bindings()[index];
+        let message = format!("duplicate parameter {:?}", binding.name());
+        diagnostics.push(Diagnostic::error(binding.span(), message));
+    }
+
+    for call in resolution.calls() {
+        let reference = &resolution.references()[call.reference()];
+
+        let Some(binding) = reference
+            .binding()
+            .map(|index| &resolution.bindings()[index])
+        else {
+            continue;
+        };
+
+        if let Some(arity) = binding.arity()
+            && arity != call.arguments()
+        {
+            let plural = if arity == 1 { "" } else { "s" };
+            let message = format!(
+                "{:?} expects {arity} argument{plural}, got {}",
+                binding.name(),
+                call.arguments()
+            );
+            diagnostics.push(Diagnostic::error(call.span(), message));
+        }
+    }
+
+    diagnostics.extend_from_slice(types::infer(program).diagnostics());
+
+    let mut unreachable = Unreachable::default();
+    unreachable.visit_program(program);
+    diagnostics.extend(unreachable.diagnostics);
+
+    let mut exhaustiveness = Exhaustiveness::default();
+    exhaustiveness.visit_program(program);
+    diagnostics.extend(exhaustiveness.diagnostics);
+
+    diagnostics.sort_by_key(|diagnostic| diagnostic.span().start());
+    diagnostics
+}
+
+#[derive(Default)]
+struct Unreachable {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Unreachable {
+    fn check(&mut self, statements: &[Statement]) {
+        let Some((index, keyword)) =
+            statements
+                .iter()
+                .enumerate()
+                .find_map(|(index, statement)| match *statement {
+                    Statement::Return(_) => Some((index, "return")),
+                    Statement::Break(_) => Some((index, "break")),
+                    Statement::Continue(_) => Some((index, "continue")),
+                    _ => None,
+                })
+        else {
+            return;
+        };
+
+        if let (Some(first), Some(last)) = (statements.get(index + 1), statements.last()) {
+            let span = first.span().to(last.span());
+            let message = format!("unreachable statement after `{keyword}`");
+            self.diagnostics.push(Diagnostic::warning(span, message));
+        }
+    }
+}
+
+impl Visitor for Unreachable {
+    fn visit_program(&mut self, node: &Program) {
+        self.check(node.statements());
+        walk_program(self, node);
+    }
+
+    fn visit_block(&mut self, node: &Block) {
+        self.check(node.statements());
+        walk_block(self, node);
+    }
+}
+
+/// Finds `match`es that can fall through every arm. Only booleans and enum variants have a known
+/// set of values, so a match is only checked when its patterns say it is over one of those.
+#[derive(Default)]
+struct Exhaustiveness {
+    /// Every `enum` seen so far, with its variants.
+    enums: Vec<(String, Vec<String>)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Exhaustiveness {
+    /// What the arms leave out, or `None` if the match is exhaustive or can't be checked.
+    fn missing(&self, node: &Match) -> Option<Vec<String>> {
+        // Guarded arms may not run, so they cover nothing.
+        let patterns = node
+            .arms()
+            .iter()
+            .filter(|arm| arm.guard().is_none())
+            .map(|arm| arm.pattern())
+            .collect::<Vec<_>>();
+
+        if patterns.iter().any(|pattern| pattern.is_irrefutable()) {
+            return None;
+        }
+
+        let values = if let Some((module, name)) =
+            patterns.iter().find_map(|pattern| match **pattern {
+                Pattern::Variant {
+                    ref module,
+                    ref name,
+                    ..
+                } => Some((module, name.token().literal())),
+                _ => None,
+            }) {
+            // The variants of an enum from another module aren't known here.
+            if module.is_some() {
+                return None;
+            }
+
+            let (_, variants) = self.enums.iter().rev().find(|(key, _)| key == name)?;
+            variants
+                .iter()
+                .map(|variant| (format!("{name}::{variant}"), variant.as_str()))
+                .collect::<Vec<_>>()
+        } else if node
+            .arms()
+            .iter()
+            .any(|arm| boolean(arm.pattern()).is_some())
+        {
+            vec![("true".to_owned(), "true"), ("false".to_owned(), "false")]
+        } else {
+            return None;
+        };
+
+        let missing = values
+            .into_iter()
+            .filter(|&(_, value)| !patterns.iter().any(|pattern| covers(pattern, value)))
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+
+        (!missing.is_empty()).then_some(missing)
+    }
+}
+
+/// The value of a `true` or `false` pattern.
+fn boolean(pattern: &Pattern) -> Option<bool> {
+    match *pattern {
+        Pattern::Literal(ref literal) => match **literal {
+            Expression::Boolean(ref inner) => Some(inner.value()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether `pattern` matches every value that is `value`, a boolean or a variant name.
+fn covers(pattern: &Pattern, value: &str) -> bool {
+    match *pattern {
+        Pattern::Variant {
+            ref variant,
+            ref arguments,
+            ..
+        } => variant.token().literal() == value && arguments.iter().all(Pattern::is_irrefutable),
+        _ => boolean(pattern).is_some_and(|boolean| boolean.to_string() == value),
+    }
+}
+
+impl Visitor for Exhaustiveness {
+    fn visit_enum(&mut self, node: &Enum) {
+        let variants = node
+            .variants()
+            .iter()
+            .map(|variant| variant.name().token().literal().to_owned())
+            .collect();
+
+        let name = node.name().token().literal().to_owned();
+        self.enums.push((name, variants));
+    }
+
+    fn visit_match(&mut self, node: &Match) {
+        if let Some(missing) = self.missing(node) {
+            let span = node.token().span().to(node.value().span());
+            let message = format!("non-exhaustive match: missing {}", missing.join(", "));
+            self.diagnostics.push(Diagnostic::warning(span, message));
+        }
+
+        walk_match(self, node);
+    }
+}
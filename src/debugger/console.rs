@@ -0,0 +1,210 @@
+use std::io::{BufRead, Write};
+use std::rc::Rc;
+
+use crate::debugger::{Resume, Stepper, evaluate_in, load};
+use crate::evaluator::Evaluate;
+use crate::object::Object;
+use crate::prelude;
+use crate::runtime::{Control, Frame, Hook, Runtime};
+use crate::token::Span;
+
+const HELP: &str = "\
+commands:
+  break LINE     (b)   pause before statements on LINE
+  delete LINE    (d)   remove the breakpoint on LINE
+  continue       (c)   run until the next breakpoint
+  step           (s)   run to the next statement, entering calls
+  next           (n)   run to the next statement in this frame
+  finish               run until the current call returns
+  backtrace      (bt)  show the call stack
+  frame N        (f)   select frame N of the backtrace
+  locals               show the bindings of the selected frame
+  print EXPR     (p)   evaluate EXPR in the selected frame
+  list                 show the source around the selected frame
+  quit           (q)   stop the program";
+
+/// Run `source` under an interactive debugger reading commands from `input`, pausing before the
+/// first statement. Returns what the program evaluated to, or why it could not be loaded.
+pub fn debug(
+    source: String,
+    input: impl BufRead + 'static,
+    output: impl Write + 'static,
+) -> Result<Object, String> {
+    let lines = source.lines().map(str::to_owned).collect();
+    let program = load(source)?;
+    let mut env = prelude::environment(Rc::default());
+
+    env.runtime().set_hook(Box::new(Console {
+        stepper: Stepper::new(true),
+        lines,
+        input: Box::new(input),
+        output: Box::new(output),
+    }));
+
+    let value = program.evaluate(&mut env);
+    _ = env.runtime().take_hook();
+    Ok(value)
+}
+
+struct Console {
+    stepper: Stepper,
+    lines: Vec<String>,
+    input: Box<dyn BufRead>,
+    output: Box<dyn Write>,
+}
+
+impl Console {
+    fn show_line(&mut self, line: usize, marker: &str) {
+        if let Some(text) = self.lines.get(line.wrapping_sub(1)) {
+            _ = writeln!(self.output, "{marker:>2} {line:>4} | {text}");
+        }
+    }
+
+    fn backtrace(&mut self, frames: &[Frame], selected: usize) {
+        for (number, frame) in frames.iter().rev().enumerate() {
+            let marker = if number == selected { '>' } else { ' ' };
+            _ = writeln!(
+                self.output,
+                "{marker} #{number} {} at line {}",
+                frame.name(),
+                frame.span().start().line()
+            );
+        }
+    }
+
+    fn locals(&mut self, frame: &Frame) {
+        let bindings = frame.env().bindings();
+
+        if bindings.is_empty() {
+            _ = writeln!(self.output, "no bindings");
+        }
+
+        for (name, value) in bindings {
+            _ = writeln!(self.output, "{name} = {value}");
+        }
+    }
+
+    /// Read one command; `None` at the end of the input.
+    fn prompt(&mut self) -> Option<String> {
+        _ = write!(self.output, "(debug) ");
+        _ = self.output.flush();
+
+        let mut line = String::new();
+
+        match self.input.read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line.trim().to_owned()),
+        }
+    }
+}
+
+impl Hook for Console {
+    fn statement(&mut self, runtime: &Runtime, span: Span) -> Control {
+        let depth = runtime.depth();
+
+        let Some(reason) = self.stepper.check(span, depth) else {
+            return Control::Continue;
+        };
+
+        let frames = runtime.frames();
+        // Frames are numbered from the innermost, as in the backtrace.
+        let mut selected = 0;
+        let line = span.start().line();
+
+        _ = writeln!(self.output, "paused ({reason}) at line {line}");
+        self.show_line(line, ">");
+
+        loop {
+            let Some(command) = self.prompt() else {
+                // Nobody is left to answer; let the program finish.
+                _ = writeln!(self.output);
+                self.stepper.breakpoints_mut().clear();
+                self.stepper.resume(Resume::Continue, depth);
+                return Control::Continue;
+            };
+
+            let (name, argument) = command
+                .split_once(' ')
+                .map_or((command.as_str(), ""), |(name, argument)| {
+                    (name, argument.trim())
+                });
+
+            let frame = &frames[frames.len() - 1 - selected];
+
+            let resume = match name {
+                "c" | "continue" => Resume::Continue,
+                "s" | "step" => Resume::StepIn,
+                "n" | "next" => Resume::StepOver,
+                "finish" => Resume::StepOut,
+                "q" | "quit" => return Control::Terminate,
+                "b" | "break" | "d" | "delete" => {
+                    let Ok(line) = argument.parse::<usize>() else {
+                        _ = writeln!(self.output, "expected a line number");
+                        continue;
+                    };
+
+                    if name.starts_with('b') {
+                        _ = self.stepper.breakpoints_mut().insert(line);
+                        _ = writeln!(self.output, "breakpoint set at line {line}");
+                    } else if self.stepper.breakpoints_mut().remove(&line) {
+                        _ = writeln!(self.output, "breakpoint at line {line} deleted");
+                    } else {
+                        _ = writeln!(self.output, "no breakpoint at line {line}");
+                    }
+
+                    continue;
+                }
+                "bt" | "backtrace" => {
+                    self.backtrace(&frames, selected);
+                    continue;
+                }
+                "f" | "frame" => {
+                    match argument.parse::<usize>() {
+                        Ok(number) if number < frames.len() => {
+                            selected = number;
+                            let frame = &frames[frames.len() - 1 - selected];
+                            let line = frame.span().start().line();
+                            _ = writeln!(self.output, "#{number} {} at line {line}", frame.name());
+                        }
+                        _ => _ = writeln!(self.output, "no such frame"),
+                    }
+
+                    continue;
+                }
+                "locals" => {
+                    self.locals(frame);
+                    continue;
+                }
+                "p" | "print" => {
+                    match evaluate_in(frame, argument) {
+                        Ok(value) => _ = writeln!(self.output, "{value}"),
+                        Err(err) => _ = writeln!(self.output, "error: {err}"),
+                    }
+
+                    continue;
+                }
+                "list" => {
+                    let current = frame.span().start().line();
+
+                    for line in current.saturating_sub(3).max(1)..=current + 3 {
+                        let marker = if line == current { ">" } else { "" };
+                        self.show_line(line, marker);
+                    }
+
+                    continue;
+                }
+                "h" | "help" | "" => {
+                    _ = writeln!(self.output, "{HELP}");
+                    continue;
+                }
+                _ => {
+                    _ = writeln!(self.output, "unknown command {name:?}; try \"help\"");
+                    continue;
+                }
+            };
+
+            self.stepper.resume(resume, depth);
+            return Control::Continue;
+        }
+    }
+}
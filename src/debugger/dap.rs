@@ -0,0 +1,449 @@
+use std::cell::RefCell;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::rc::Rc;
+
+use crate::debugger::{Reason, Resume, Stepper, evaluate_in, load};
+use crate::evaluator::Evaluate;
+use crate::json::Json;
+use crate::object::{Error, Object};
+use crate::prelude;
+use crate::runtime::{Control, Frame, Hook, Runtime};
+use crate::token::Span;
+use crate::transport::{read_message, write_message};
+
+/// Monkey programs are single-threaded; this is the one thread reported to the client.
+const THREAD_ID: i64 = 1;
+
+/// Serve the Debug Adapter Protocol on `input` and `output`.
+///
+/// The client sends `initialize`, then `launch` with the `program` to run (and optionally
+/// `stopOnEntry`), any `setBreakpoints`, and finally `configurationDone`, which starts the program.
+pub fn serve(input: impl BufRead + 'static, output: impl Write + 'static) -> io::Result<()> {
+    let session = Rc::new(RefCell::new(Session {
+        input: Box::new(input),
+        output: Box::new(output),
+        seq: 0,
+        stepper: Stepper::new(false),
+        program: None,
+        disconnected: false,
+    }));
+
+    // Configuration: everything up to `configurationDone`.
+    loop {
+        let Some(request) = session.borrow_mut().next_request()? else {
+            return Ok(());
+        };
+
+        if !session.borrow_mut().configure(&request)? {
+            break;
+        }
+    }
+
+    let Some(path) = session.borrow().program.clone() else {
+        return Ok(());
+    };
+
+    let value = match fs::read_to_string(&path)
+        .map_err(|err| format!("{path}: {err}"))
+        .and_then(load)
+    {
+        Ok(program) => {
+            let mut env = prelude::environment(Rc::default());
+            env.runtime()
+                .set_hook(Box::new(Adapter(Rc::clone(&session))));
+            let value = program.evaluate(&mut env);
+            _ = env.runtime().take_hook();
+            value
+        }
+        Err(err) => Error::new(err).into(),
+    };
+
+    let mut session = session.borrow_mut();
+
+    if session.disconnected {
+        return Ok(());
+    }
+
+    let exit_code = i64::from(matches!(value, Object::Error(_)));
+    session.event(
+        "output",
+        Json::object([
+            ("category", Json::from("stdout")),
+            ("output", Json::from(format!("{value}\n"))),
+        ]),
+    )?;
+    session.event(
+        "exited",
+        Json::object([("exitCode", Json::Integer(exit_code))]),
+    )?;
+    session.event("terminated", Json::object::<&str>([]))?;
+
+    // The program is over, but the client may still ask about threads before disconnecting.
+    while let Some(request) = session.next_request()? {
+        match command(&request) {
+            "disconnect" => return session.respond(&request, Json::Null),
+            "threads" => session.respond(&request, threads())?,
+            _ => session.fail(&request, "the program has finished")?,
+        }
+    }
+
+    Ok(())
+}
+
+struct Session {
+    input: Box<dyn BufRead>,
+    output: Box<dyn Write>,
+    seq: i64,
+    stepper: Stepper,
+    program: Option<String>,
+    disconnected: bool,
+}
+
+fn command(request: &Json) -> &str {
+    request.get("command").and_then(Json::as_str).unwrap_or("")
+}
+
+fn arguments(request: &Json) -> &Json {
+    request.get("arguments").unwrap_or(&Json::Null)
+}
+
+fn threads() -> Json {
+    Json::object([(
+        "threads",
+        Json::Array(vec![Json::object([
+            ("id", Json::Integer(THREAD_ID)),
+            ("name", Json::from("main")),
+        ])]),
+    )])
+}
+
+impl Session {
+    fn next_request(&mut self) -> io::Result<Option<Json>> {
+        loop {
+            let Some(body) = read_message(&mut self.input)? else {
+                return Ok(None);
+            };
+
+            // Anything that isn't valid JSON can't be answered, because it has no `seq`.
+            if let Ok(message) = Json::parse(&body) {
+                return Ok(Some(message));
+            }
+        }
+    }
+
+    fn send(&mut self, kind: &str, mut members: Vec<(String, Json)>) -> io::Result<()> {
+        self.seq += 1;
+        members.insert(0, ("seq".to_owned(), Json::Integer(self.seq)));
+        members.insert(1, ("type".to_owned(), Json::from(kind)));
+        write_message(&mut self.output, &Json::Object(members))
+    }
+
+    /// Answer `request` successfully; a `Json::Null` body is left out.
+    fn respond(&mut self, request: &Json, body: Json) -> io::Result<()> {
+        let request_seq = request.get("seq").cloned().unwrap_or(Json::Null);
+        let mut members = vec![
+            ("request_seq".to_owned(), request_seq),
+            ("success".to_owned(), Json::from(true)),
+            ("command".to_owned(), Json::from(command(request))),
+        ];
+
+        if body != Json::Null {
+            members.push(("body".to_owned(), body));
+        }
+
+        self.send("response", members)
+    }
+
+    fn fail(&mut self, request: &Json, message: &str) -> io::Result<()> {
+        let request_seq = request.get("seq").cloned().unwrap_or(Json::Null);
+        self.send(
+            "response",
+            vec![
+                ("request_seq".to_owned(), request_seq),
+                ("success".to_owned(), Json::from(false)),
+                ("command".to_owned(), Json::from(command(request))),
+                ("message".to_owned(), Json::from(message)),
+            ],
+        )
+    }
+
+    fn event(&mut self, event: &str, body: Json) -> io::Result<()> {
+        self.send(
+            "event",
+            vec![
+                ("event".to_owned(), Json::from(event)),
+                ("body".to_owned(), body),
+            ],
+        )
+    }
+
+    /// Handle a request made before the program starts. Returns `false` once it should start.
+    fn configure(&mut self, request: &Json) -> io::Result<bool> {
+        let arguments = arguments(request);
+
+        match command(request) {
+            "initialize" => {
+                self.respond(
+                    request,
+                    Json::object([
+                        ("supportsConfigurationDoneRequest", Json::from(true)),
+                        ("supportsEvaluateForHovers", Json::from(true)),
+                        ("supportsTerminateRequest", Json::from(true)),
+                    ]),
+                )?;
+                self.event("initialized", Json::object::<&str>([]))?;
+            }
+            "launch" => {
+                let Some(program) = arguments.get("program").and_then(Json::as_str) else {
+                    self.fail(request, "launch needs a \"program\" to run")?;
+                    return Ok(true);
+                };
+
+                self.program = Some(program.to_owned());
+
+                if arguments
+                    .get("stopOnEntry")
+                    .and_then(Json::as_bool)
+                    .unwrap_or(false)
+                {
+                    self.stepper.stop_on_entry();
+                }
+
+                self.respond(request, Json::Null)?;
+            }
+            "setBreakpoints" => self.set_breakpoints(request)?,
+            "setExceptionBreakpoints" => {
+                self.respond(
+                    request,
+                    Json::object([("breakpoints", Json::Array(Vec::new()))]),
+                )?;
+            }
+            "threads" => self.respond(request, threads())?,
+            "configurationDone" => {
+                self.respond(request, Json::Null)?;
+                return Ok(false);
+            }
+            "disconnect" => {
+                self.disconnected = true;
+                self.respond(request, Json::Null)?;
+                return Ok(false);
+            }
+            _ => self.fail(request, "not supported before the program starts")?,
+        }
+
+        Ok(true)
+    }
+
+    fn set_breakpoints(&mut self, request: &Json) -> io::Result<()> {
+        let lines = arguments(request)
+            .get("breakpoints")
+            .and_then(Json::as_array)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|breakpoint| breakpoint.get("line").and_then(Json::as_i64))
+            .filter_map(|line| usize::try_from(line).ok())
+            .collect::<Vec<_>>();
+
+        // There is only ever one source, so a new set replaces the old one.
+        let breakpoints = self.stepper.breakpoints_mut();
+        breakpoints.clear();
+        breakpoints.extend(lines.iter().copied());
+
+        let verified = lines
+            .into_iter()
+            .map(|line| Json::object([("verified", Json::from(true)), ("line", Json::from(line))]))
+            .collect();
+
+        self.respond(
+            request,
+            Json::object([("breakpoints", Json::Array(verified))]),
+        )
+    }
+
+    fn source(&self) -> Json {
+        let path = self.program.clone().unwrap_or_default();
+        let name = path.rsplit('/').next().unwrap_or_default().to_owned();
+        Json::object([("name", Json::from(name)), ("path", Json::from(path))])
+    }
+
+    /// Pause before `span`, answering requests until the client resumes the program.
+    fn pause(&mut self, runtime: &Runtime, reason: Reason) -> io::Result<Control> {
+        let depth = runtime.depth();
+        let frames = runtime.frames();
+
+        self.event(
+            "stopped",
+            Json::object([
+                ("reason", Json::from(reason.to_string())),
+                ("threadId", Json::Integer(THREAD_ID)),
+                ("allThreadsStopped", Json::from(true)),
+            ]),
+        )?;
+
+        while let Some(request) = self.next_request()? {
+            let arguments = arguments(&request);
+
+            let resume = match command(&request) {
+                "continue" => Resume::Continue,
+                "next" => Resume::StepOver,
+                "stepIn" => Resume::StepIn,
+                "stepOut" => Resume::StepOut,
+                "disconnect" | "terminate" => {
+                    self.disconnected = command(&request) == "disconnect";
+                    self.respond(&request, Json::Null)?;
+                    return Ok(Control::Terminate);
+                }
+                "threads" => {
+                    self.respond(&request, threads())?;
+                    continue;
+                }
+                "setBreakpoints" => {
+                    self.set_breakpoints(&request)?;
+                    continue;
+                }
+                "stackTrace" => {
+                    let body = self.stack_trace(&frames);
+                    self.respond(&request, body)?;
+                    continue;
+                }
+                "scopes" => {
+                    let body = scopes(arguments, &frames);
+                    self.respond(&request, body)?;
+                    continue;
+                }
+                "variables" => {
+                    let body = variables(arguments, &frames);
+                    self.respond(&request, body)?;
+                    continue;
+                }
+                "evaluate" => {
+                    let frame = frame(arguments.get("frameId"), &frames);
+                    let expression = arguments
+                        .get("expression")
+                        .and_then(Json::as_str)
+                        .unwrap_or_default();
+
+                    match evaluate_in(frame, expression) {
+                        Ok(value) => {
+                            let body = Json::object([
+                                ("result", Json::from(value.to_string())),
+                                ("variablesReference", Json::Integer(0)),
+                            ]);
+                            self.respond(&request, body)?;
+                        }
+                        Err(err) => self.fail(&request, &err)?,
+                    }
+
+                    continue;
+                }
+                _ => {
+                    self.fail(&request, "not supported while paused")?;
+                    continue;
+                }
+            };
+
+            self.stepper.resume(resume, depth);
+            let body = Json::object([("allThreadsContinued", Json::from(true))]);
+            self.respond(&request, body)?;
+            return Ok(Control::Continue);
+        }
+
+        // The client went away.
+        self.disconnected = true;
+        Ok(Control::Terminate)
+    }
+
+    fn stack_trace(&self, frames: &[Frame]) -> Json {
+        // Frame ids are positions in `frames`, which lists the outermost frame first.
+        let stack_frames = frames
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(id, frame)| {
+                let position = frame.span().start();
+
+                Json::object([
+                    ("id", Json::from(id)),
+                    ("name", Json::from(frame.name())),
+                    ("source", self.source()),
+                    ("line", Json::from(position.line())),
+                    ("column", Json::from(position.column())),
+                ])
+            })
+            .collect();
+
+        Json::object([
+            ("stackFrames", Json::Array(stack_frames)),
+            ("totalFrames", Json::from(frames.len())),
+        ])
+    }
+}
+
+/// The frame a `frameId` argument refers to, defaulting to the innermost.
+fn frame<'a>(id: Option<&Json>, frames: &'a [Frame]) -> &'a Frame {
+    id.and_then(Json::as_i64)
+        .and_then(|id| usize::try_from(id).ok())
+        .and_then(|id| frames.get(id))
+        .unwrap_or_else(|| frames.last().expect("paused outside of any frame"))
+}
+
+/// Each frame has a "Locals" scope whose `variablesReference` is its id plus one; frames other
+/// than the program's also show the program's bindings as "Globals".
+fn scopes(arguments: &Json, frames: &[Frame]) -> Json {
+    let id = arguments.get("frameId").and_then(Json::as_i64).unwrap_or(0);
+
+    let scope = |name: &str, reference: i64| {
+        Json::object([
+            ("name", Json::from(name)),
+            ("variablesReference", Json::Integer(reference)),
+            ("expensive", Json::from(false)),
+        ])
+    };
+
+    let mut scopes = vec![scope("Locals", id + 1)];
+
+    if id > 0 && !frames.is_empty() {
+        scopes.push(scope("Globals", 1));
+    }
+
+    Json::object([("scopes", Json::Array(scopes))])
+}
+
+fn variables(arguments: &Json, frames: &[Frame]) -> Json {
+    let frame = arguments
+        .get("variablesReference")
+        .and_then(Json::as_i64)
+        .and_then(|reference| usize::try_from(reference - 1).ok())
+        .and_then(|id| frames.get(id));
+
+    let variables = frame
+        .map(|frame| frame.env().bindings())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, value)| {
+            Json::object([
+                ("name", Json::from(name)),
+                ("value", Json::from(value.to_string())),
+                ("variablesReference", Json::Integer(0)),
+            ])
+        })
+        .collect();
+
+    Json::object([("variables", Json::Array(variables))])
+}
+
+struct Adapter(Rc<RefCell<Session>>);
+
+impl Hook for Adapter {
+    fn statement(&mut self, runtime: &Runtime, span: Span) -> Control {
+        let mut session = self.0.borrow_mut();
+
+        let Some(reason) = session.stepper.check(span, runtime.depth()) else {
+            return Control::Continue;
+        };
+
+        // If the client can't be reached there is no one left to debug for.
+        session.pause(runtime, reason).unwrap_or(Control::Terminate)
+    }
+}
@@ -0,0 +1,162 @@
+//! A step debugger, built on `runtime::Hook`.
+//!
+//! `Stepper` decides when to pause; the front ends decide what to do while paused. `console` is
+//! the interactive `monkey debug FILE` prompt and `dap` speaks the Debug Adapter Protocol, so
+//! editors can drive the same debugger.
+
+mod console;
+mod dap;
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::lexer::Lexer;
+use crate::macro_expansion::{define_macros, expand_macros};
+use crate::object::Object;
+use crate::parser::Parser;
+use crate::program::Program;
+use crate::runtime::Frame;
+use crate::token::Span;
+
+pub use console::debug;
+pub use dap::serve;
+
+/// Why the debugger paused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reason {
+    Entry,
+    Breakpoint,
+    Step,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Entry => "entry".fmt(f),
+            Self::Breakpoint => "breakpoint".fmt(f),
+            Self::Step => "step".fmt(f),
+        }
+    }
+}
+
+/// How to carry on after a pause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resume {
+    Continue,
+    /// Stop at the very next statement, entering calls.
+    StepIn,
+    /// Stop at the next statement in the current frame or one of its callers.
+    StepOver,
+    /// Stop at the next statement once the current frame has returned.
+    StepOut,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Mode {
+    Entry,
+    Run,
+    StepIn,
+    StepOver(usize),
+    StepOut(usize),
+}
+
+/// Line breakpoints and stepping state.
+#[derive(Debug, Clone)]
+pub struct Stepper {
+    breakpoints: BTreeSet<usize>,
+    mode: Mode,
+    /// The line and call depth of the previous statement, so several statements on the same line
+    /// only hit its breakpoint once.
+    last: Option<(usize, usize)>,
+}
+
+impl Stepper {
+    pub const fn new(stop_on_entry: bool) -> Self {
+        Self {
+            breakpoints: BTreeSet::new(),
+            mode: if stop_on_entry {
+                Mode::Entry
+            } else {
+                Mode::Run
+            },
+            last: None,
+        }
+    }
+
+    /// Pause before the next statement, whatever it is.
+    pub const fn stop_on_entry(&mut self) {
+        self.mode = Mode::Entry;
+    }
+
+    pub const fn breakpoints(&self) -> &BTreeSet<usize> {
+        &self.breakpoints
+    }
+
+    pub const fn breakpoints_mut(&mut self) -> &mut BTreeSet<usize> {
+        &mut self.breakpoints
+    }
+
+    /// Decide whether to pause before the statement at `span`, running `depth` calls deep.
+    pub fn check(&mut self, span: Span, depth: usize) -> Option<Reason> {
+        let line = span.start().line();
+        let new_line = self.last != Some((line, depth));
+        self.last = Some((line, depth));
+
+        let reason = match self.mode {
+            Mode::Entry => Some(Reason::Entry),
+            Mode::StepIn => Some(Reason::Step),
+            Mode::StepOver(from) if depth <= from => Some(Reason::Step),
+            Mode::StepOut(from) if depth < from => Some(Reason::Step),
+            _ if new_line && self.breakpoints.contains(&line) => Some(Reason::Breakpoint),
+            _ => None,
+        };
+
+        if reason.is_some() {
+            self.mode = Mode::Run;
+        }
+
+        reason
+    }
+
+    /// Set what happens next, when paused `depth` calls deep.
+    pub const fn resume(&mut self, resume: Resume, depth: usize) {
+        self.mode = match resume {
+            Resume::Continue => Mode::Run,
+            Resume::StepIn => Mode::StepIn,
+            Resume::StepOver => Mode::StepOver(depth),
+            Resume::StepOut => Mode::StepOut(depth),
+        };
+    }
+}
+
+/// Parse `source` and expand its macros, ready to be evaluated.
+fn load(source: String) -> Result<Program, String> {
+    let lexer = Lexer::new(source);
+    let mut parser = Parser::new(lexer.tokens());
+    let mut program = Program::parse(&mut parser).map_err(|err| err.to_string())?;
+
+    if let Some(err) = program.errors().first() {
+        return Err(format!("{}: {err}", err.span().start()));
+    }
+
+    let mut macro_env = Environment::default();
+    define_macros(&mut program, &mut macro_env);
+    expand_macros(&mut program, &macro_env).map_err(|err| Object::from(err).to_string())?;
+
+    Ok(program)
+}
+
+/// Evaluate `source` as if it were written in `frame`, for inspecting a paused program.
+pub fn evaluate_in(frame: &Frame, source: &str) -> Result<Object, String> {
+    let lexer = Lexer::new(source.to_owned());
+    let mut parser = Parser::new(lexer.tokens());
+    let program = Program::parse(&mut parser).map_err(|err| err.to_string())?;
+
+    if let Some(err) = program.errors().first() {
+        return Err(err.to_string());
+    }
+
+    Ok(program.evaluate(&mut frame.env().clone()))
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+
+use crate::token::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// A problem found in a program without running it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    severity: Severity,
+    span: Span,
+    message: String,
+}
+
+impl Diagnostic {
+    pub fn error(span: Span, message: String) -> Self {
+        Self {
+            severity: Severity::Error,
+            span,
+            message,
+        }
+    }
+
+    pub fn warning(span: Span, message: String) -> Self {
+        Self {
+            severity: Severity::Warning,
+            span,
+            message,
+        }
+    }
+
+    pub const fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub const fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Warning => "warning".fmt(f),
+            Self::Error => "error".fmt(f),
+        }
+    }
+}
+
+/// Formats as `line:column: severity: message`.
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}: {}",
+            self.span.start(),
+            self.severity,
+            self.message
+        )
+    }
+}
@@ -1,29 +1,222 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::fmt;
+use std::rc::{Rc, Weak};
 
 use crate::object::Object;
+use crate::runtime::Runtime;
 
-#[derive(Debug, Clone, Default)]
+/// A scope of bindings, chained to the scope it was created in.
+///
+/// Cloning an `Environment` produces another handle to the same bindings, so a function that
+/// captures its defining environment sees names bound there later, including itself.
+///
+/// A function bound in a scope it captured would keep that scope alive forever, so the copy
+/// kept in the scope holds it, and the scopes around it, weakly: `set` and `assign` weaken it,
+/// and `get` and `bindings` hand out strong copies again. The weak copy can only be reached
+/// through the scope, whose own handle holds the scopes around it, so they are always still
+/// alive when it's read. A function kept inside an array or struct still holds its scope
+/// strongly.
+#[derive(Clone, Default)]
 pub struct Environment {
-    store: HashMap<String, Object>,
+    store: Store,
     outer: Option<Box<Environment>>,
+    runtime: Rc<Runtime>,
+}
+
+type Bindings = RefCell<HashMap<String, Object>>;
+
+#[derive(Clone)]
+enum Store {
+    Strong(Rc<Bindings>),
+    Weak(Weak<Bindings>),
+}
+
+impl Default for Store {
+    fn default() -> Self {
+        Self::Strong(Rc::default())
+    }
 }
 
 impl Environment {
+    /// A new scope, sharing the runtime of `outer` if there is one.
     pub fn new(store: HashMap<String, Object>, outer: Option<Box<Environment>>) -> Self {
-        Self { store, outer }
+        let runtime = outer
+            .as_ref()
+            .map_or_else(Rc::default, |outer| Rc::clone(&outer.runtime));
+
+        Self {
+            store: Store::Strong(Rc::new(RefCell::new(store))),
+            outer,
+            runtime,
+        }
+    }
+
+    /// A new top-level scope in the session of `runtime`.
+    pub fn with_runtime(runtime: Rc<Runtime>) -> Self {
+        Self {
+            store: Store::default(),
+            outer: None,
+            runtime,
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<Object> {
+        match self.bindings_ref().borrow().get(name) {
+            Some(value) => Some(strengthen(value)),
+            None => self.outer.as_ref().and_then(|outer| outer.get(name)),
+        }
+    }
+
+    /// Bind `name` in this scope, returning the value it was previously bound to here.
+    pub fn set(&self, name: String, value: Object) -> Option<Object> {
+        let store = self.bindings_ref();
+        let value = weaken(value, &store);
+        let previous = store.borrow_mut().insert(name, value);
+        previous.as_ref().map(strengthen)
+    }
+
+    /// Whether `name` is bound in this scope, not counting the scopes around it.
+    pub fn contains(&self, name: &str) -> bool {
+        self.bindings_ref().borrow().contains_key(name)
+    }
+
+    /// Rebind `name` in the innermost scope that already binds it. Returns `false`, changing
+    /// nothing, if no scope does.
+    pub fn assign(&self, name: &str, value: Object) -> bool {
+        let store = self.bindings_ref();
+
+        if let Some(slot) = store.borrow_mut().get_mut(name) {
+            *slot = weaken(value, &store);
+            return true;
+        }
+
+        self.outer
+            .as_ref()
+            .is_some_and(|outer| outer.assign(name, value))
+    }
+
+    /// The bindings of this scope only, sorted by name.
+    pub fn bindings(&self) -> Vec<(String, Object)> {
+        let mut bindings = self
+            .bindings_ref()
+            .borrow()
+            .iter()
+            .map(|(name, value)| (name.to_owned(), strengthen(value)))
+            .collect::<Vec<_>>();
+
+        bindings.sort_by(|a, b| a.0.cmp(&b.0));
+        bindings
     }
 
-    pub fn store_mut(&mut self) -> &mut HashMap<String, Object> {
-        &mut self.store
+    pub fn outer(&self) -> Option<&Environment> {
+        self.outer.as_deref()
     }
 
-    pub fn get(&self, name: &str) -> Option<&Object> {
-        let mut value = self.store.get(name);
+    pub const fn runtime(&self) -> &Rc<Runtime> {
+        &self.runtime
+    }
 
-        if value.is_none() && self.outer.is_some() {
-            value = self.outer.as_ref().unwrap().get(name);
+    fn bindings_ref(&self) -> Rc<Bindings> {
+        match self.store {
+            Store::Strong(ref store) => Rc::clone(store),
+            Store::Weak(ref store) => store
+                .upgrade()
+                .expect("a scope was used after the last strong handle to it was dropped"),
         }
+    }
+
+    /// A copy of this chain of scopes with the links from `store` outwards made weak, or `None`
+    /// if `store` isn't part of it.
+    fn weakened(&self, store: &Rc<Bindings>) -> Option<Self> {
+        let found = matches!(self.store, Store::Strong(ref inner) if Rc::ptr_eq(inner, store));
+
+        if found {
+            return Some(self.weak());
+        }
+
+        Some(Self {
+            store: self.store.clone(),
+            outer: Some(Box::new(self.outer.as_ref()?.weakened(store)?)),
+            runtime: Rc::clone(&self.runtime),
+        })
+    }
+
+    /// A copy of this chain of scopes with every link weak.
+    fn weak(&self) -> Self {
+        let store = match self.store {
+            Store::Strong(ref inner) => Store::Weak(Rc::downgrade(inner)),
+            Store::Weak(ref inner) => Store::Weak(Weak::clone(inner)),
+        };
+
+        Self {
+            store,
+            outer: self.outer.as_ref().map(|outer| Box::new(outer.weak())),
+            runtime: Rc::clone(&self.runtime),
+        }
+    }
+
+    /// A copy of this chain of scopes with every link strong, or `None` if they all are.
+    fn strengthened(&self) -> Option<Self> {
+        let outer = self.outer.as_ref().and_then(|outer| outer.strengthened());
+
+        if outer.is_none() && matches!(self.store, Store::Strong(_)) {
+            return None;
+        }
+
+        Some(Self {
+            store: Store::Strong(self.bindings_ref()),
+            outer: outer.map(Box::new).or_else(|| self.outer.clone()),
+            runtime: Rc::clone(&self.runtime),
+        })
+    }
+}
+
+/// `value` as it should be kept in `store`: a function or macro that captured `store` holds it
+/// weakly, so the two don't keep each other alive.
+fn weaken(value: Object, store: &Rc<Bindings>) -> Object {
+    match value {
+        Object::Function(ref inner) => match inner.env().weakened(store) {
+            Some(env) => inner.with_env(env).into(),
+            None => value,
+        },
+        Object::Macro(ref inner) => match inner.env().weakened(store) {
+            Some(env) => inner.with_env(env).into(),
+            None => value,
+        },
+        _ => value,
+    }
+}
+
+/// A value kept in a scope, as it should be handed out: holding everything it captured.
+fn strengthen(value: &Object) -> Object {
+    match *value {
+        Object::Function(ref inner) => match inner.env().strengthened() {
+            Some(env) => inner.with_env(env).into(),
+            None => value.clone(),
+        },
+        Object::Macro(ref inner) => match inner.env().strengthened() {
+            Some(env) => inner.with_env(env).into(),
+            None => value.clone(),
+        },
+        _ => value.clone(),
+    }
+}
+
+/// Only lists the names in each scope: environments can contain functions that capture them.
+impl fmt::Debug for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names = self
+            .bindings_ref()
+            .borrow()
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        names.sort();
 
-        value
+        f.debug_struct("Environment")
+            .field("names", &names)
+            .field("outer", &self.outer)
+            .finish()
     }
 }
@@ -0,0 +1,63 @@
+use std::fmt::Write as _;
+
+use crate::export::{Child, Node};
+
+/// Render `program` as a Graphviz digraph. Edges are labelled with the child slot they fill.
+pub fn to_dot(program: &Node) -> String {
+    let mut buffer = String::from("digraph ast {\n    node [shape=box];\n");
+    let mut next_id = 0;
+    write_node(&mut buffer, program, &mut next_id);
+    buffer.push_str("}\n");
+    buffer
+}
+
+/// Write `node` and everything below it, returning the id it was given.
+fn write_node(buffer: &mut String, node: &Node, next_id: &mut usize) -> usize {
+    let id = *next_id;
+    *next_id += 1;
+
+    let mut label = node.kind().to_owned();
+
+    if let Some(token) = node.token() {
+        label.push('\n');
+        label.push_str(token);
+    }
+
+    _ = writeln!(buffer, "    n{id} [label=\"{}\"];", escape(&label));
+
+    for (name, child) in node.children() {
+        match *child {
+            Child::Node(ref inner) => {
+                let child_id = write_node(buffer, inner, next_id);
+                _ = writeln!(buffer, "    n{id} -> n{child_id} [label=\"{name}\"];");
+            }
+            Child::List(ref inner) => {
+                for (index, node) in inner.iter().enumerate() {
+                    let child_id = write_node(buffer, node, next_id);
+                    _ = writeln!(
+                        buffer,
+                        "    n{id} -> n{child_id} [label=\"{name}[{index}]\"];"
+                    );
+                }
+            }
+            Child::Absent => {}
+        }
+    }
+
+    id
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,63 @@
+use crate::export::{Child, Node};
+use crate::json::Json;
+use crate::parser::ParserError;
+use crate::token::{Position, Span};
+
+/// Bumped whenever the shape of the exported document changes in a way that could break readers.
+pub const SCHEMA_VERSION: i64 = 1;
+
+/// Render `program` as a versioned document:
+///
+/// ```json
+/// { "version": 1, "program": <node>, "errors": [<error>] }
+/// ```
+///
+/// where every node is `{ "kind", "token", "span", "children" }` and `children` maps each slot
+/// name to a node, an array of nodes, or `null`. Every error is `{ "message", "span" }`.
+pub fn to_json(program: &Node, errors: &[ParserError]) -> Json {
+    Json::object([
+        ("version", Json::Integer(SCHEMA_VERSION)),
+        ("program", node(program)),
+        ("errors", Json::Array(errors.iter().map(error).collect())),
+    ])
+}
+
+fn node(node: &Node) -> Json {
+    let children = node.children().iter().map(|(name, child)| {
+        let value = match *child {
+            Child::Node(ref inner) => self::node(inner),
+            Child::List(ref inner) => Json::Array(inner.iter().map(self::node).collect()),
+            Child::Absent => Json::Null,
+        };
+
+        (*name, value)
+    });
+
+    Json::object([
+        ("kind", Json::from(node.kind())),
+        ("token", Json::from(node.token())),
+        ("span", span(node.span())),
+        ("children", Json::object(children)),
+    ])
+}
+
+fn error(error: &ParserError) -> Json {
+    Json::object([
+        ("message", Json::from(error.to_string())),
+        ("span", span(error.span())),
+    ])
+}
+
+fn span(span: Span) -> Json {
+    Json::object([
+        ("start", position(span.start())),
+        ("end", position(span.end())),
+    ])
+}
+
+fn position(position: Position) -> Json {
+    Json::object([
+        ("line", Json::from(position.line())),
+        ("column", Json::from(position.column())),
+    ])
+}
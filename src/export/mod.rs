@@ -0,0 +1,445 @@
+//! Exporters that render a parsed `Program` for tools outside the interpreter.
+//!
+//! Every format is produced from the same intermediate `Node` tree, so adding a new kind of AST
+//! node only means describing it once in this module.
+
+mod dot;
+mod json;
+mod sexp;
+
+use std::str::FromStr;
+use std::{error, fmt};
+
+use crate::annotation::Annotation;
+use crate::expression::{Expression, FunctionLiteral, Identifier};
+use crate::pattern::Pattern;
+use crate::program::Program;
+use crate::statement::{Block, Statement};
+use crate::token::Span;
+
+pub use dot::to_dot;
+pub use json::to_json;
+pub use sexp::to_sexp;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Sexp,
+    Dot,
+}
+
+impl Format {
+    pub fn export(self, program: &Program) -> String {
+        let node = Node::from(program);
+
+        match self {
+            Self::Json => to_json(&node, program.errors()).to_string(),
+            Self::Sexp => to_sexp(&node),
+            Self::Dot => to_dot(&node),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = UnknownFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "sexp" => Ok(Self::Sexp),
+            "dot" => Ok(Self::Dot),
+            _ => Err(UnknownFormat(s.to_owned())),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UnknownFormat(String);
+
+impl fmt::Display for UnknownFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown format {:?}, expected one of: json, sexp, dot",
+            self.0
+        )
+    }
+}
+
+impl error::Error for UnknownFormat {}
+
+/// A format-agnostic view of one AST node.
+#[derive(Debug, Clone)]
+pub struct Node {
+    kind: &'static str,
+    token: Option<String>,
+    span: Span,
+    children: Vec<(&'static str, Child)>,
+}
+
+/// What sits in one of a node's named child slots.
+#[derive(Debug, Clone)]
+pub enum Child {
+    Node(Node),
+    List(Vec<Node>),
+    Absent,
+}
+
+impl Node {
+    fn new(kind: &'static str, token: Option<&str>, span: Span) -> Self {
+        Self {
+            kind,
+            token: token.map(str::to_owned),
+            span,
+            children: Vec::new(),
+        }
+    }
+
+    fn child(mut self, name: &'static str, child: impl Into<Child>) -> Self {
+        self.children.push((name, child.into()));
+        self
+    }
+
+    pub const fn kind(&self) -> &'static str {
+        self.kind
+    }
+
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    pub const fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn children(&self) -> &[(&'static str, Child)] {
+        &self.children
+    }
+}
+
+impl From<Node> for Child {
+    fn from(value: Node) -> Self {
+        Self::Node(value)
+    }
+}
+
+impl From<Vec<Node>> for Child {
+    fn from(value: Vec<Node>) -> Self {
+        Self::List(value)
+    }
+}
+
+impl From<Option<Node>> for Child {
+    fn from(value: Option<Node>) -> Self {
+        value.map_or(Self::Absent, Self::Node)
+    }
+}
+
+impl From<&Program> for Node {
+    fn from(value: &Program) -> Self {
+        let statements = value
+            .statements()
+            .iter()
+            .map(Node::from)
+            .collect::<Vec<_>>();
+        Node::new("Program", None, value.span()).child("statements", statements)
+    }
+}
+
+impl From<&Statement> for Node {
+    fn from(value: &Statement) -> Self {
+        match *value {
+            Statement::Let(ref inner) => {
+                Node::new("Let", Some(inner.token().literal()), inner.span())
+                    .child("name", Node::from(inner.pattern()))
+                    .child("annotation", inner.annotation().map(Node::from))
+                    .child("value", Node::from(inner.value()))
+            }
+            Statement::Return(ref inner) => {
+                Node::new("Return", Some(inner.token().literal()), inner.span())
+                    .child("value", Node::from(inner.value()))
+            }
+            Statement::Expression(ref inner) => {
+                Node::new("ExpressionStatement", None, inner.span())
+                    .child("expression", Node::from(inner.expression()))
+            }
+            Statement::Block(ref inner) => Node::from(inner),
+            Statement::While(ref inner) => {
+                Node::new("While", Some(inner.token().literal()), inner.span())
+                    .child("condition", Node::from(inner.condition()))
+                    .child("body", Node::from(inner.body()))
+            }
+            Statement::For(ref inner) => {
+                Node::new("For", Some(inner.token().literal()), inner.span())
+                    .child("variable", Node::from(inner.variable()))
+                    .child("iterable", Node::from(inner.iterable()))
+                    .child("body", Node::from(inner.body()))
+            }
+            Statement::Break(ref inner) => {
+                Node::new("Break", Some(inner.token().literal()), inner.span())
+            }
+            Statement::Continue(ref inner) => {
+                Node::new("Continue", Some(inner.token().literal()), inner.span())
+            }
+            Statement::Assign(ref inner) => {
+                // Plain assignments have no path, and read as before without an empty list.
+                let fields = match inner.fields() {
+                    [] => Child::Absent,
+                    fields => Child::List(fields.iter().map(Node::from).collect()),
+                };
+                Node::new("Assign", Some(inner.token().literal()), inner.span())
+                    .child("name", Node::from(inner.name()))
+                    .child("fields", fields)
+                    .child("value", Node::from(inner.value()))
+            }
+            Statement::Impl(ref inner) => {
+                let methods = inner
+                    .methods()
+                    .iter()
+                    .map(|method| {
+                        let name = method.name();
+                        Node::new("Method", Some(name.token().literal()), method.span())
+                            .child("function", Node::from(method.function()))
+                    })
+                    .collect::<Vec<_>>();
+                Node::new("Impl", Some(inner.token().literal()), inner.span())
+                    .child("name", Node::from(inner.name()))
+                    .child("methods", methods)
+            }
+            Statement::Enum(ref inner) => {
+                let variants = inner
+                    .variants()
+                    .iter()
+                    .map(|variant| {
+                        let fields = variant.fields().iter().map(Node::from).collect::<Vec<_>>();
+                        let name = variant.name().token().literal();
+                        Node::new("Variant", Some(name), variant.span()).child("fields", fields)
+                    })
+                    .collect::<Vec<_>>();
+                Node::new("Enum", Some(inner.token().literal()), inner.span())
+                    .child("name", Node::from(inner.name()))
+                    .child("variants", variants)
+            }
+            Statement::Struct(ref inner) => {
+                let fields = inner.fields().iter().map(Node::from).collect::<Vec<_>>();
+                Node::new("Struct", Some(inner.token().literal()), inner.span())
+                    .child("name", Node::from(inner.name()))
+                    .child("fields", fields)
+            }
+            Statement::Import(ref inner) => {
+                let path = inner.path();
+                Node::new("Import", Some(inner.token().literal()), inner.span())
+                    .child(
+                        "path",
+                        Node::new("StringLiteral", Some(path.token().literal()), path.span()),
+                    )
+                    .child("name", Node::from(inner.name()))
+            }
+            Statement::Export(ref inner) => {
+                Node::new("Export", Some(inner.token().literal()), inner.span())
+                    .child("statement", Node::from(inner.statement()))
+            }
+        }
+    }
+}
+
+impl From<&Block> for Node {
+    fn from(value: &Block) -> Self {
+        let statements = value
+            .statements()
+            .iter()
+            .map(Node::from)
+            .collect::<Vec<_>>();
+        Node::new("Block", Some(value.token().literal()), value.span())
+            .child("statements", statements)
+    }
+}
+
+impl From<&Identifier> for Node {
+    fn from(value: &Identifier) -> Self {
+        Node::new("Identifier", Some(value.token().literal()), value.span())
+    }
+}
+
+impl From<&Annotation> for Node {
+    fn from(value: &Annotation) -> Self {
+        Node::new("Annotation", Some(&value.to_string()), value.span())
+    }
+}
+
+impl From<&Expression> for Node {
+    fn from(value: &Expression) -> Self {
+        match *value {
+            Expression::Identifier(ref inner) => Node::from(inner),
+            Expression::IntegerLiteral(ref inner) => Node::new(
+                "IntegerLiteral",
+                Some(inner.token().literal()),
+                inner.span(),
+            ),
+            Expression::FloatLiteral(ref inner) => {
+                Node::new("FloatLiteral", Some(inner.token().literal()), inner.span())
+            }
+            Expression::StringLiteral(ref inner) => {
+                Node::new("StringLiteral", Some(inner.token().literal()), inner.span())
+            }
+            Expression::Boolean(ref inner) => {
+                Node::new("Boolean", Some(inner.token().literal()), inner.span())
+            }
+            Expression::Prefix(ref inner) => {
+                Node::new("Prefix", Some(inner.token().literal()), inner.span())
+                    .child("right", Node::from(inner.right()))
+            }
+            Expression::Infix(ref inner) => {
+                Node::new("Infix", Some(inner.token().literal()), inner.span())
+                    .child("left", Node::from(inner.left()))
+                    .child("right", Node::from(inner.right()))
+            }
+            Expression::If(ref inner) => {
+                Node::new("If", Some(inner.token().literal()), inner.span())
+                    .child("condition", Node::from(inner.condition()))
+                    .child("consequence", Node::from(inner.consequence()))
+                    .child("alternative", inner.alternative().map(Node::from))
+            }
+            Expression::FunctionLiteral(ref inner) => Node::from(inner),
+            Expression::MacroLiteral(ref inner) => {
+                let parameters = inner
+                    .parameters()
+                    .iter()
+                    .map(Node::from)
+                    .collect::<Vec<_>>();
+                Node::new("MacroLiteral", Some(inner.token().literal()), inner.span())
+                    .child("parameters", parameters)
+                    .child("body", Node::from(inner.body()))
+            }
+            Expression::Call(ref inner) => {
+                let arguments = inner.arguments().iter().map(Node::from).collect::<Vec<_>>();
+                Node::new("Call", Some(inner.token().literal()), inner.span())
+                    .child("function", Node::from(inner.function()))
+                    .child("arguments", arguments)
+            }
+            Expression::ArrayLiteral(ref inner) => {
+                let elements = inner.elements().iter().map(Node::from).collect::<Vec<_>>();
+                Node::new("ArrayLiteral", Some(inner.token().literal()), inner.span())
+                    .child("elements", elements)
+            }
+            Expression::Index(ref inner) => {
+                Node::new("Index", Some(inner.token().literal()), inner.span())
+                    .child("left", Node::from(inner.left()))
+                    .child("index", Node::from(inner.index()))
+            }
+            Expression::StructLiteral(ref inner) => {
+                let fields = inner
+                    .fields()
+                    .iter()
+                    .map(|(field, value)| Node::from(field).child("value", Node::from(value)))
+                    .collect::<Vec<_>>();
+                Node::new("StructLiteral", None, inner.span())
+                    .child("module", inner.module().map(Node::from))
+                    .child("name", Node::from(inner.name()))
+                    .child("fields", fields)
+            }
+            Expression::FieldAccess(ref inner) => {
+                Node::new("FieldAccess", Some(inner.token().literal()), inner.span())
+                    .child("left", Node::from(inner.left()))
+                    .child("field", Node::from(inner.field()))
+            }
+            Expression::Match(ref inner) => {
+                let arms = inner
+                    .arms()
+                    .iter()
+                    .map(|arm| {
+                        Node::new("Arm", None, arm.span())
+                            .child("pattern", Node::from(arm.pattern()))
+                            .child("guard", arm.guard().map(Node::from))
+                            .child("body", Node::from(arm.body()))
+                    })
+                    .collect::<Vec<_>>();
+                Node::new("Match", Some(inner.token().literal()), inner.span())
+                    .child("value", Node::from(inner.value()))
+                    .child("arms", arms)
+            }
+            Expression::Path(ref inner) => {
+                Node::new("Path", Some(inner.token().literal()), inner.span())
+                    .child("left", Node::from(inner.left()))
+                    .child("variant", Node::from(inner.variant()))
+            }
+        }
+    }
+}
+
+impl From<&FunctionLiteral> for Node {
+    fn from(value: &FunctionLiteral) -> Self {
+        let parameters = value
+            .parameters()
+            .iter()
+            .zip(value.annotations())
+            .map(|(parameter, annotation)| match *annotation {
+                Some(ref annotation) => {
+                    Node::from(parameter).child("annotation", Node::from(annotation))
+                }
+                None => Node::from(parameter),
+            })
+            .collect::<Vec<_>>();
+        Node::new(
+            "FunctionLiteral",
+            Some(value.token().literal()),
+            value.span(),
+        )
+        .child("parameters", parameters)
+        .child("result", value.result().map(Node::from))
+        .child("body", Node::from(value.body()))
+    }
+}
+
+impl From<&Pattern> for Node {
+    fn from(value: &Pattern) -> Self {
+        match *value {
+            Pattern::Wildcard(ref token) => {
+                Node::new("Wildcard", Some(token.literal()), value.span())
+            }
+            Pattern::Binding(ref name) => Node::from(name),
+            Pattern::Literal(ref literal) => Node::from(&**literal),
+            Pattern::Array {
+                ref token,
+                ref elements,
+                ref rest,
+                ..
+            } => {
+                let elements = elements.iter().map(Node::from).collect::<Vec<_>>();
+                Node::new("ArrayPattern", Some(token.literal()), value.span())
+                    .child("elements", elements)
+                    .child("rest", rest.as_deref().map(Node::from))
+            }
+            Pattern::Struct {
+                ref module,
+                ref name,
+                ref fields,
+                ..
+            } => {
+                let fields = fields
+                    .iter()
+                    .map(|(field, pattern)| Node::from(field).child("pattern", Node::from(pattern)))
+                    .collect::<Vec<_>>();
+                Node::new("StructPattern", None, value.span())
+                    .child("module", module.as_ref().map(Node::from))
+                    .child("name", name.as_ref().map(Node::from))
+                    .child("fields", fields)
+            }
+            Pattern::Variant {
+                ref module,
+                ref name,
+                ref token,
+                ref variant,
+                ref arguments,
+                ..
+            } => {
+                let arguments = arguments.iter().map(Node::from).collect::<Vec<_>>();
+                Node::new("VariantPattern", Some(token.literal()), value.span())
+                    .child("module", module.as_ref().map(Node::from))
+                    .child("name", Node::from(name))
+                    .child("variant", Node::from(variant))
+                    .child("arguments", arguments)
+            }
+        }
+    }
+}
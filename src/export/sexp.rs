@@ -0,0 +1,83 @@
+use crate::export::{Child, Node};
+
+/// Render `program` as a single-line S-expression, e.g. `(program (let x (+ 1 2)))`.
+///
+/// Leaves are written as their token literal. Other nodes are headed by their keyword or
+/// operator, and lists of children other than the last are wrapped in their own parentheses.
+pub fn to_sexp(program: &Node) -> String {
+    let mut buffer = String::new();
+    write_node(&mut buffer, program);
+    buffer
+}
+
+fn write_node(buffer: &mut String, node: &Node) {
+    if node.children().is_empty() {
+        return buffer.push_str(node.token().unwrap_or(node.kind()));
+    }
+
+    let head = match node.kind() {
+        "Program" => "program",
+        "Block" => "block",
+        "Call" => "call",
+        "ArrayLiteral" => "array",
+        "Index" => "index",
+        "StructLiteral" => "struct-literal",
+        "Arm" => "arm",
+        "ArrayPattern" => "array",
+        "StructPattern" => "struct-pattern",
+        "ExpressionStatement" => {
+            // Expression statements only wrap an expression; there is nothing to add.
+            for (_, child) in node.children() {
+                write_child(buffer, child);
+            }
+
+            return;
+        }
+        kind => node.token().unwrap_or(kind),
+    };
+
+    buffer.push('(');
+    buffer.push_str(head);
+
+    let count = node.children().len();
+
+    for (index, (_, child)) in node.children().iter().enumerate() {
+        match *child {
+            // A trailing list is spliced in, so calls read `(call f a b)` rather than
+            // `(call f (a b))`.
+            Child::List(ref inner) if index + 1 == count => {
+                for node in inner {
+                    buffer.push(' ');
+                    write_node(buffer, node);
+                }
+            }
+            Child::Absent => {}
+            _ => {
+                buffer.push(' ');
+                write_child(buffer, child);
+            }
+        }
+    }
+
+    buffer.push(')');
+}
+
+fn write_child(buffer: &mut String, child: &Child) {
+    match *child {
+        Child::Node(ref inner) => write_node(buffer, inner),
+        Child::List(ref inner) => {
+            buffer.push('(');
+
+            for (index, node) in inner.iter().enumerate() {
+                if index > 0 {
+                    buffer.push(' ');
+                }
+
+                write_node(buffer, node);
+            }
+
+            buffer.push(')');
+        }
+        Child::Absent => {}
+    }
+}
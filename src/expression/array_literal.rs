@@ -0,0 +1,95 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression::{Expression, parse_expression_list};
+use crate::object::{Array, Object};
+use crate::parser::{ParsePrefix, Parser, ParserError};
+use crate::token::{Span, Token, TokenKind};
+
+#[derive(Debug, Clone)]
+pub struct ArrayLiteral {
+    token: Token,
+    elements: Vec<Expression>,
+    rbracket: Token,
+}
+
+impl ArrayLiteral {
+    pub const fn new(token: Token, elements: Vec<Expression>, rbracket: Token) -> Self {
+        Self {
+            token,
+            elements,
+            rbracket,
+        }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn elements(&self) -> &[Expression] {
+        &self.elements
+    }
+
+    pub fn elements_mut(&mut self) -> &mut [Expression] {
+        &mut self.elements
+    }
+
+    pub const fn span(&self) -> Span {
+        self.token.span().to(self.rbracket.span())
+    }
+}
+
+impl ParsePrefix for ArrayLiteral {
+    fn parse_prefix(parser: &mut Parser<'_>) -> Result<Expression, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::LBracket)?;
+        let elements = parse_expression_list(parser, TokenKind::RBracket)?;
+        let rbracket = parser.expect_token_with_kind(TokenKind::RBracket)?;
+
+        let expression = Self::new(token, elements, rbracket);
+        Ok(expression.into())
+    }
+}
+
+impl Evaluate for ArrayLiteral {
+    fn evaluate(&self, env: &mut Environment) -> Object {
+        let mut elements = Vec::with_capacity(self.elements.len());
+
+        for element in &self.elements {
+            let value = element.evaluate(env);
+
+            if matches!(value, Object::Error(_)) {
+                return value;
+            }
+
+            elements.push(value);
+        }
+
+        let runtime = env.runtime();
+
+        if let Err(err) = runtime
+            .check_size(elements.len())
+            .and_then(|()| runtime.allocate(1))
+        {
+            return err.into();
+        }
+
+        Array::new(elements).into()
+    }
+}
+
+impl fmt::Display for ArrayLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+
+        for (index, element) in self.elements.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+
+            element.fmt(f)?;
+        }
+
+        f.write_str("]")
+    }
+}
@@ -1,9 +1,11 @@
+use std::fmt;
+
 use crate::environment::Environment;
 use crate::evaluator::Evaluate;
 use crate::expression::Expression;
 use crate::object::{self, Object};
 use crate::parser::{ParsePrefix, Parser, ParserError};
-use crate::token::{Token, TokenKind};
+use crate::token::{Span, Token, TokenKind};
 
 #[derive(Debug, Clone)]
 pub struct Boolean {
@@ -23,6 +25,10 @@ impl Boolean {
     pub const fn value(&self) -> bool {
         self.value
     }
+
+    pub const fn span(&self) -> Span {
+        self.token.span()
+    }
 }
 
 impl ParsePrefix for Boolean {
@@ -46,3 +52,9 @@ impl Evaluate for Boolean {
         object::Boolean::new(self.value).into()
     }
 }
+
+impl fmt::Display for Boolean {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
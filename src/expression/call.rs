@@ -1,103 +1,266 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
 
 use crate::environment::Environment;
 use crate::evaluator::Evaluate;
-use crate::expression::Expression;
-use crate::object::Object;
+use crate::expression::{Expression, FieldAccess, parse_expression_list};
+use crate::macro_expansion;
+use crate::object::{Constructor, Error, Object};
 use crate::parser::{ParseInfix, Parser, ParserError};
 use crate::precedence::Precedence;
-use crate::token::{Token, TokenKind};
+use crate::runtime::Frame;
+use crate::token::{Span, Token, TokenKind};
 
 #[derive(Debug, Clone)]
 pub struct Call {
+    token: Token,
     function: Box<Expression>,
     arguments: Vec<Expression>,
+    rparenthesis: Token,
 }
 
 impl Call {
-    pub const fn new(function: Box<Expression>, arguments: Vec<Expression>) -> Self {
+    pub const fn new(
+        token: Token,
+        function: Box<Expression>,
+        arguments: Vec<Expression>,
+        rparenthesis: Token,
+    ) -> Self {
         Self {
+            token,
             function,
             arguments,
+            rparenthesis,
         }
     }
-}
 
-impl ParseInfix for Call {
-    fn parse_infix(parser: &mut Parser<'_>, left: Expression) -> Result<Expression, ParserError> {
-        _ = parser.expect_token_with_kind(TokenKind::LParenthesis)?;
-        let arguments = parse_call_arguments(parser)?;
-        _ = parser.expect_token_with_kind(TokenKind::RParenthesis)?;
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
 
-        let expression = Self::new(Box::new(left), arguments);
-        Ok(expression.into())
+    pub fn function(&self) -> &Expression {
+        &self.function
     }
-}
 
-fn parse_call_arguments(parser: &mut Parser<'_>) -> Result<Vec<Expression>, ParserError> {
-    let mut arguments = Vec::new();
+    pub fn arguments(&self) -> &[Expression] {
+        &self.arguments
+    }
 
-    if parser
-        .token()
-        .is_some_and(|token| token.kind() == TokenKind::RParenthesis)
-    {
-        return Ok(arguments);
+    pub fn function_mut(&mut self) -> &mut Expression {
+        &mut self.function
     }
 
-    arguments.push(Expression::parse(parser, Precedence::Lowest)?);
+    pub fn arguments_mut(&mut self) -> &mut [Expression] {
+        &mut self.arguments
+    }
 
-    while let Some(TokenKind::Comma) = parser.token().map(Token::kind) {
-        _ = parser.expect_token_with_kind(TokenKind::Comma)?;
-        arguments.push(Expression::parse(parser, Precedence::Lowest)?);
+    pub fn span(&self) -> Span {
+        self.function.span().to(self.rparenthesis.span())
     }
+}
+
+impl ParseInfix for Call {
+    fn parse_infix(parser: &mut Parser<'_>, left: Expression) -> Result<Expression, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::LParenthesis)?;
+        let arguments = parse_expression_list(parser, TokenKind::RParenthesis)?;
+        let rparenthesis = parser.expect_token_with_kind(TokenKind::RParenthesis)?;
 
-    Ok(arguments)
+        let expression = Self::new(token, Box::new(left), arguments, rparenthesis);
+        Ok(expression.into())
+    }
 }
 
 impl Evaluate for Call {
     fn evaluate(&self, env: &mut Environment) -> Object {
-        let function = (*self.function).evaluate(env);
+        if let Expression::Identifier(ref identifier) = *self.function
+            && identifier.token().literal() == "quote"
+            && self.arguments.len() == 1
+        {
+            return macro_expansion::quote(&self.arguments[0], env);
+        }
+
+        let (function, receiver) = match *self.function {
+            Expression::FieldAccess(ref access) => match method(access, env) {
+                Ok(found) => found,
+                Err(err) => return err,
+            },
+            _ => ((*self.function).evaluate(env), None),
+        };
 
         if matches!(function, Object::Error(_)) {
             return function;
         }
 
-        let arguments = evaluate_call_arguments(&self.arguments, env);
+        let mut arguments = evaluate_call_arguments(&self.arguments, env);
 
         if arguments.len() == 1 && matches!(arguments.first().unwrap(), Object::Error(_)) {
             return arguments.into_iter().next().unwrap();
         }
 
-        let function = match function {
-            Object::Function(inner) => inner,
-            _ => panic!("expected Function object"),
+        // The receiver is passed as `self`, but isn't counted as one of the caller's arguments.
+        let implicit = usize::from(receiver.is_some());
+        arguments.splice(0..0, receiver);
+
+        let name = match *self.function {
+            Expression::Identifier(ref identifier) => identifier.token().literal().to_owned(),
+            Expression::FieldAccess(ref access) => access.to_string(),
+            _ => "<anonymous>".to_owned(),
         };
 
-        let outer = Box::new(function.env().to_owned());
-        let mut env_extended = Environment::new(HashMap::new(), Some(outer));
+        apply(function, arguments, implicit, name, self.span(), env)
+    }
+}
+
+/// Call `function` on behalf of a builtin, such as the comparator passed to `sort`. The call is
+/// attributed to the statement the builtin was called from.
+pub fn call_function(function: &Object, arguments: Vec<Object>, env: &mut Environment) -> Object {
+    let span = env
+        .runtime()
+        .frames()
+        .last()
+        .map_or_else(Span::default, Frame::span);
+
+    apply(
+        function.clone(),
+        arguments,
+        0,
+        "<anonymous>".to_owned(),
+        span,
+        env,
+    )
+}
 
-        for (index, parameter) in function.parameters().iter().enumerate() {
-            let key = parameter.token().literal();
-            let value = arguments.get(index).unwrap();
+/// Call `function` from the code at `span`, in a frame named `name`. The first `implicit`
+/// arguments were not written by the caller, so they are left out of error messages.
+fn apply(
+    function: Object,
+    arguments: Vec<Object>,
+    implicit: usize,
+    name: String,
+    span: Span,
+    env: &mut Environment,
+) -> Object {
+    let function = match function {
+        Object::Function(inner) => inner,
+        Object::Builtin(ref inner) => return inner.call(&arguments, env),
+        Object::Constructor(ref inner) => return construct(inner, arguments, env),
+        _ => return Error::new(format!("not a function: {function}")).into(),
+    };
 
-            if env_extended
-                .store_mut()
-                .insert(key.to_owned(), value.to_owned())
-                .is_some()
-            {
-                panic!("variable named {key:?} already exists");
+    if arguments.len() != function.parameters().len() {
+        let message = format!(
+            "wrong number of arguments: expected {}, got {}",
+            function.parameters().len() - implicit,
+            arguments.len() - implicit
+        );
+        return Error::new(message).into();
+    }
+
+    if let Err(err) = env.runtime().allocate(1) {
+        return err.into();
+    }
+
+    let outer = Box::new(function.env().to_owned());
+    let mut env_extended = Environment::new(HashMap::new(), Some(outer));
+
+    for (parameter, value) in function.parameters().iter().zip(&arguments) {
+        for name in parameter.bindings() {
+            let key = name.token().literal();
+
+            if env_extended.contains(key) {
+                return Error::new(format!("duplicate parameter {key:?}")).into();
             }
         }
 
-        let value = function.body().evaluate(&mut env_extended);
+        if let Err(err) = parameter.destructure(value, &mut env_extended) {
+            return err;
+        }
+    }
+
+    let runtime = Rc::clone(env_extended.runtime());
+    if let Err(err) = runtime.enter(name, env_extended.clone(), span) {
+        return err.into();
+    }
+
+    let value = function.body().evaluate(&mut env_extended);
+    runtime.exit();
 
-        match value {
-            Object::Return(ref inner) => inner.value().to_owned(),
-            _ => value,
+    match value {
+        Object::Return(ref inner) => inner.value().to_owned(),
+        // The parser reports these, but a program can be run in spite of its errors.
+        Object::Break(_) | Object::Continue(_) => {
+            Error::new(format!("`{value}` outside of a loop")).into()
         }
+        _ => value,
+    }
+}
+
+/// Look up what `receiver.name(...)` calls: a function stored in a field, a method with the
+/// receiver to pass as `self`, an associated function of a struct type, or a module's export.
+fn method(access: &FieldAccess, env: &mut Environment) -> Result<(Object, Option<Object>), Object> {
+    let receiver = access.left().evaluate(env);
+    let name = access.field().token().literal();
+
+    match receiver {
+        Object::Error(_) => Err(receiver),
+        Object::Struct(ref inner) => {
+            if let Some(value) = inner.get(name) {
+                return Ok((value.clone(), None));
+            }
+
+            let ty = inner.ty();
+
+            match ty.method(name) {
+                Some(function) if function.is_method() => Ok((function.into(), Some(receiver))),
+                Some(_) => {
+                    let message = format!(
+                        "{name:?} is an associated function of {0}, call it as {0}.{name}()",
+                        ty.name()
+                    );
+                    Err(Error::new(message).into())
+                }
+                None => {
+                    let message = format!("no method {name:?} on struct {}", ty.name());
+                    Err(Error::new(message).into())
+                }
+            }
+        }
+        Object::StructType(ref ty) => match ty.method(name) {
+            Some(function) => Ok((function.into(), None)),
+            None => {
+                let message = format!("no associated function {name:?} on struct {}", ty.name());
+                Err(Error::new(message).into())
+            }
+        },
+        Object::Module(ref module) => match module.get(name) {
+            Some(value) => Ok((value.clone(), None)),
+            None => {
+                let message = format!("module {:?} has no export {name:?}", module.path());
+                Err(Error::new(message).into())
+            }
+        },
+        _ => Err(Error::new(format!("no method {name:?} on {receiver}")).into()),
     }
 }
 
+fn construct(constructor: &Constructor, arguments: Vec<Object>, env: &Environment) -> Object {
+    if arguments.len() != constructor.arity() {
+        let message = format!(
+            "wrong number of arguments: expected {}, got {}",
+            constructor.arity(),
+            arguments.len()
+        );
+        return Error::new(message).into();
+    }
+
+    if let Err(err) = env.runtime().allocate(1) {
+        return err.into();
+    }
+
+    constructor.construct(arguments).into()
+}
+
 fn evaluate_call_arguments(arguments: &[Expression], env: &mut Environment) -> Vec<Object> {
     let mut results = Vec::new();
 
@@ -113,3 +276,20 @@ fn evaluate_call_arguments(arguments: &[Expression], env: &mut Environment) -> V
 
     results
 }
+
+impl fmt::Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.function.fmt_operand(f, Precedence::Call)?;
+        f.write_str("(")?;
+
+        for (index, argument) in self.arguments.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+
+            argument.fmt(f)?;
+        }
+
+        f.write_str(")")
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression::{Expression, Identifier, StructLiteral};
+use crate::object::{Error, Module, Object};
+use crate::parser::{ParseInfix, Parser, ParserError};
+use crate::precedence::Precedence;
+use crate::token::{Span, Token, TokenKind};
+
+/// `left.field`.
+#[derive(Debug, Clone)]
+pub struct FieldAccess {
+    token: Token,
+    left: Box<Expression>,
+    field: Identifier,
+}
+
+impl FieldAccess {
+    pub const fn new(token: Token, left: Box<Expression>, field: Identifier) -> Self {
+        Self { token, left, field }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn left(&self) -> &Expression {
+        &self.left
+    }
+
+    pub const fn field(&self) -> &Identifier {
+        &self.field
+    }
+
+    pub fn left_mut(&mut self) -> &mut Expression {
+        &mut self.left
+    }
+
+    pub fn span(&self) -> Span {
+        self.left.span().to(self.field.span())
+    }
+}
+
+impl ParseInfix for FieldAccess {
+    fn parse_infix(parser: &mut Parser<'_>, left: Expression) -> Result<Expression, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::Dot)?;
+
+        let field = parser
+            .expect_token_with_kind(TokenKind::Identifier)
+            .map(Identifier::new)?;
+
+        // `m.Point { ... }` builds a struct that module `m` exports.
+        if let Expression::Identifier(ref module) = left
+            && parser.struct_literals_allowed()
+            && parser
+                .token()
+                .is_some_and(|token| token.kind() == TokenKind::LBrace)
+        {
+            return StructLiteral::parse_fields(parser, Some(module.clone()), field);
+        }
+
+        Ok(Self::new(token, Box::new(left), field).into())
+    }
+}
+
+impl Evaluate for FieldAccess {
+    fn evaluate(&self, env: &mut Environment) -> Object {
+        let left = self.left.evaluate(env);
+        let field = self.field.token().literal();
+
+        match left {
+            Object::Error(_) => left,
+            Object::Struct(ref inner) => match inner.get(field) {
+                Some(value) => value.clone(),
+                None => {
+                    let message = format!("struct {} has no field {field:?}", inner.ty().name());
+                    Error::new(message).into()
+                }
+            },
+            // So that `let make = Point.new;` works like calling `Point.new(...)`.
+            Object::StructType(ref inner) => match inner.method(field) {
+                Some(function) => function.into(),
+                None => {
+                    let message = format!(
+                        "no associated function {field:?} on struct {}",
+                        inner.name()
+                    );
+                    Error::new(message).into()
+                }
+            },
+            Object::Module(ref inner) => export(inner, field),
+            _ => Error::new(format!("cannot access field {field:?} of {left}")).into(),
+        }
+    }
+}
+
+/// The value of `name`, or, with a `module`, what the module bound to it exports as `name`. This
+/// is how struct literals and patterns find their type, as in `m.Point { x, y }`.
+pub fn qualified(module: Option<&Identifier>, name: &Identifier, env: &mut Environment) -> Object {
+    let Some(module) = module else {
+        return name.evaluate(env);
+    };
+
+    match module.evaluate(env) {
+        Object::Module(ref inner) => export(inner, name.token().literal()),
+        Object::Error(err) => err.into(),
+        other => Error::new(format!("{other} is not a module")).into(),
+    }
+}
+
+fn export(module: &Module, name: &str) -> Object {
+    match module.get(name) {
+        Some(value) => value.clone(),
+        None => {
+            let message = format!("module {:?} has no export {name:?}", module.path());
+            Error::new(message).into()
+        }
+    }
+}
+
+impl fmt::Display for FieldAccess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.left.fmt_operand(f, Precedence::Call)?;
+        write!(f, ".{}", self.field)
+    }
+}
@@ -0,0 +1,66 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression::Expression;
+use crate::object::{Float, Object};
+use crate::parser::{ParsePrefix, Parser, ParserError};
+use crate::token::{Span, Token, TokenKind};
+
+#[derive(Debug, Clone)]
+pub struct FloatLiteral {
+    token: Token,
+    value: f64,
+}
+
+impl FloatLiteral {
+    pub fn new(token: Token, value: f64) -> Self {
+        Self { token, value }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub const fn span(&self) -> Span {
+        self.token.span()
+    }
+
+    pub const fn value(&self) -> f64 {
+        self.value
+    }
+}
+
+impl ParsePrefix for FloatLiteral {
+    fn parse_prefix(parser: &mut Parser<'_>) -> Result<Expression, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::Float)?;
+
+        // Rust reads literals too large for an f64 as infinity.
+        let Some(value) = token
+            .literal()
+            .replace('_', "")
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+        else {
+            return Err(ParserError::InvalidFloat {
+                literal: token.literal().to_owned(),
+                span: token.span(),
+            });
+        };
+
+        Ok(Self::new(token, value).into())
+    }
+}
+
+impl Evaluate for FloatLiteral {
+    fn evaluate(&self, _: &mut Environment) -> Object {
+        Float::new(self.value).into()
+    }
+}
+
+impl fmt::Display for FloatLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Float::new(self.value).fmt(f)
+    }
+}
@@ -1,38 +1,137 @@
+use std::fmt;
+
+use crate::annotation::Annotation;
 use crate::environment::Environment;
 use crate::evaluator::Evaluate;
-use crate::expression::{Expression, Identifier};
+use crate::expression::Expression;
 use crate::object::{Function, Object};
 use crate::parser::{Parse, ParsePrefix, Parser, ParserError};
+use crate::pattern::Pattern;
 use crate::statement::Block;
-use crate::token::{Token, TokenKind};
+use crate::token::{Span, Token, TokenKind};
 
 #[derive(Debug, Clone)]
 pub struct FunctionLiteral {
-    parameters: Vec<Identifier>,
+    token: Token,
+    parameters: Vec<Pattern>,
+    annotations: Vec<Option<Annotation>>,
+    result: Option<Annotation>,
     body: Block,
 }
 
 impl FunctionLiteral {
-    pub fn new(parameters: Vec<Identifier>, body: Block) -> Self {
-        Self { parameters, body }
+    pub fn new(
+        token: Token,
+        parameters: Vec<Pattern>,
+        annotations: Vec<Option<Annotation>>,
+        result: Option<Annotation>,
+        body: Block,
+    ) -> Self {
+        assert_eq!(parameters.len(), annotations.len());
+        Self {
+            token,
+            parameters,
+            annotations,
+            result,
+            body,
+        }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    /// Each parameter's name, or the pattern taking its argument apart.
+    pub fn parameters(&self) -> &[Pattern] {
+        &self.parameters
+    }
+
+    /// The annotation written after each parameter, in the same order as `parameters`.
+    pub fn annotations(&self) -> &[Option<Annotation>] {
+        &self.annotations
+    }
+
+    /// The annotation written after `->`, if any.
+    pub const fn result(&self) -> Option<&Annotation> {
+        self.result.as_ref()
+    }
+
+    pub const fn body(&self) -> &Block {
+        &self.body
+    }
+
+    pub fn parameters_mut(&mut self) -> &mut [Pattern] {
+        &mut self.parameters
+    }
+
+    pub const fn body_mut(&mut self) -> &mut Block {
+        &mut self.body
+    }
+
+    pub fn span(&self) -> Span {
+        self.token.span().to(self.body.span())
+    }
+
+    /// Parse everything after `fn`, so named functions can share it.
+    pub(crate) fn parse_signature_and_body(
+        parser: &mut Parser<'_>,
+        token: Token,
+    ) -> Result<Self, ParserError> {
+        let (parameters, annotations) =
+            parse_function_literal_parameters(parser, true, Pattern::parse)?;
+        let result = Annotation::parse_optional(parser, TokenKind::Arrow)?;
+        let body = parser.in_function_body(Block::parse)?;
+
+        Ok(Self::new(token, parameters, annotations, result, body))
+    }
+
+    /// Write everything after `fn`.
+    pub(crate) fn fmt_signature_and_body(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("(")?;
+
+        for (index, (parameter, annotation)) in
+            self.parameters.iter().zip(&self.annotations).enumerate()
+        {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+
+            fmt::Display::fmt(parameter, f)?;
+
+            if let Some(annotation) = annotation {
+                f.write_str(": ")?;
+                fmt::Display::fmt(annotation, f)?;
+            }
+        }
+
+        f.write_str(") ")?;
+
+        if let Some(ref result) = self.result {
+            f.write_str("-> ")?;
+            fmt::Display::fmt(result, f)?;
+            f.write_str(" ")?;
+        }
+
+        fmt::Display::fmt(&self.body, f)
     }
 }
 
 impl ParsePrefix for FunctionLiteral {
     fn parse_prefix(parser: &mut Parser<'_>) -> Result<Expression, ParserError> {
-        _ = parser.expect_token_with_kind(TokenKind::Function)?;
-        let parameters = parse_function_literal_parameters(parser)?;
-        let body = Block::parse(parser)?;
-
-        let expression = Self::new(parameters, body);
-        Ok(expression.into())
+        let token = parser.expect_token_with_kind(TokenKind::Function)?;
+        Self::parse_signature_and_body(parser, token).map(Expression::from)
     }
 }
 
-fn parse_function_literal_parameters(
+/// Parse `(a, b)`, reading each parameter with `parameter`, and also accepting `(a: int, b)` when
+/// `annotated` is set.
+pub(crate) fn parse_function_literal_parameters<P>(
     parser: &mut Parser<'_>,
-) -> Result<Vec<Identifier>, ParserError> {
+    annotated: bool,
+    mut parameter: impl FnMut(&mut Parser<'_>) -> Result<P, ParserError>,
+) -> Result<(Vec<P>, Vec<Option<Annotation>>), ParserError> {
     let mut parameters = Vec::new();
+    let mut annotations = Vec::new();
 
     _ = parser.expect_token_with_kind(TokenKind::LParenthesis)?;
 
@@ -41,31 +140,43 @@ fn parse_function_literal_parameters(
         .is_some_and(|token| token.kind() == TokenKind::RParenthesis)
     {
         parser.advance();
-        return Ok(parameters);
+        return Ok((parameters, annotations));
     }
 
-    parameters.push(
-        parser
-            .expect_token_with_kind(TokenKind::Identifier)
-            .map(Identifier::new)?,
-    );
+    loop {
+        parameters.push(parameter(parser)?);
+
+        annotations.push(if annotated {
+            Annotation::parse_optional(parser, TokenKind::Colon)?
+        } else {
+            None
+        });
 
-    while let Some(TokenKind::Comma) = parser.token().map(Token::kind) {
-        _ = parser.expect_token_with_kind(TokenKind::Comma)?;
-        parameters.push(
-            parser
-                .expect_token_with_kind(TokenKind::Identifier)
-                .map(Identifier::new)?,
-        );
+        if let Some(TokenKind::Comma) = parser.token().map(Token::kind) {
+            _ = parser.expect_token_with_kind(TokenKind::Comma)?;
+        } else {
+            break;
+        }
     }
 
     _ = parser.expect_token_with_kind(TokenKind::RParenthesis)?;
 
-    Ok(parameters)
+    Ok((parameters, annotations))
 }
 
 impl Evaluate for FunctionLiteral {
     fn evaluate(&self, env: &mut Environment) -> Object {
+        if let Err(err) = env.runtime().allocate(1) {
+            return err.into();
+        }
+
         Function::new(self.parameters.clone(), self.body.clone(), env.clone()).into()
     }
 }
+
+impl fmt::Display for FunctionLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("fn")?;
+        self.fmt_signature_and_body(f)
+    }
+}
@@ -9,7 +9,8 @@ pub struct Grouped;
 impl ParsePrefix for Grouped {
     fn parse_prefix(parser: &mut Parser<'_>) -> Result<Expression, ParserError> {
         _ = parser.expect_token_with_kind(TokenKind::LParenthesis)?;
-        let expression = Expression::parse(parser, Precedence::Lowest)?;
+        let expression =
+            parser.with_struct_literals(|parser| Expression::parse(parser, Precedence::Lowest))?;
         _ = parser.expect_token_with_kind(TokenKind::RParenthesis)?;
         Ok(expression)
     }
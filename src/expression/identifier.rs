@@ -1,9 +1,11 @@
+use std::fmt;
+
 use crate::environment::Environment;
 use crate::evaluator::Evaluate;
-use crate::expression::Expression;
+use crate::expression::{Expression, StructLiteral};
 use crate::object::{Error, Object};
 use crate::parser::{ParsePrefix, Parser, ParserError};
-use crate::token::{Token, TokenKind};
+use crate::token::{Span, Token, TokenKind};
 
 #[derive(Debug, Clone)]
 pub struct Identifier {
@@ -18,14 +20,27 @@ impl Identifier {
     pub const fn token(&self) -> &Token {
         &self.token
     }
+
+    pub const fn span(&self) -> Span {
+        self.token.span()
+    }
 }
 
 impl ParsePrefix for Identifier {
     fn parse_prefix(parser: &mut Parser<'_>) -> Result<Expression, ParserError> {
-        parser
+        let identifier = parser
             .expect_token_with_kind(TokenKind::Identifier)
-            .map(Self::new)
-            .map(Expression::from)
+            .map(Self::new)?;
+
+        if parser.struct_literals_allowed()
+            && parser
+                .token()
+                .is_some_and(|token| token.kind() == TokenKind::LBrace)
+        {
+            return StructLiteral::parse_fields(parser, None, identifier);
+        }
+
+        Ok(identifier.into())
     }
 }
 
@@ -34,8 +49,14 @@ impl Evaluate for Identifier {
         let identifier = self.token.literal();
 
         match env.get(identifier) {
-            Some(value) => value.to_owned(),
+            Some(value) => value,
             None => Error::new(format!("identifier {identifier:?} is not defined")).into(),
         }
     }
 }
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.token.literal().fmt(f)
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use crate::environment::Environment;
 use crate::evaluator::Evaluate;
 use crate::expression::Expression;
@@ -5,29 +7,71 @@ use crate::object::{NULL, Object};
 use crate::parser::{Parse, ParsePrefix, Parser, ParserError};
 use crate::precedence::Precedence;
 use crate::statement::Block;
-use crate::token::TokenKind;
+use crate::token::{Span, Token, TokenKind};
 
 #[derive(Debug, Clone)]
 pub struct If {
+    token: Token,
     condition: Box<Expression>,
     consequence: Block,
     alternative: Option<Block>,
 }
 
 impl If {
-    pub fn new(condition: Box<Expression>, consequence: Block, alternative: Option<Block>) -> Self {
+    pub fn new(
+        token: Token,
+        condition: Box<Expression>,
+        consequence: Block,
+        alternative: Option<Block>,
+    ) -> Self {
         Self {
+            token,
             condition,
             consequence,
             alternative,
         }
     }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn condition(&self) -> &Expression {
+        &self.condition
+    }
+
+    pub const fn consequence(&self) -> &Block {
+        &self.consequence
+    }
+
+    pub const fn alternative(&self) -> Option<&Block> {
+        self.alternative.as_ref()
+    }
+
+    pub fn condition_mut(&mut self) -> &mut Expression {
+        &mut self.condition
+    }
+
+    pub const fn consequence_mut(&mut self) -> &mut Block {
+        &mut self.consequence
+    }
+
+    pub const fn alternative_mut(&mut self) -> Option<&mut Block> {
+        self.alternative.as_mut()
+    }
+
+    pub fn span(&self) -> Span {
+        let last = self.alternative.as_ref().unwrap_or(&self.consequence);
+        self.token.span().to(last.span())
+    }
 }
 
 impl ParsePrefix for If {
     fn parse_prefix(parser: &mut Parser<'_>) -> Result<Expression, ParserError> {
-        _ = parser.expect_token_with_kind(TokenKind::If)?;
-        let condition = Box::new(Expression::parse(parser, Precedence::Lowest)?);
+        let token = parser.expect_token_with_kind(TokenKind::If)?;
+        let condition = parser.without_struct_literals(|parser| {
+            Expression::parse(parser, Precedence::Lowest).map(Box::new)
+        })?;
         let consequence = Block::parse(parser)?;
 
         let alternative = if parser
@@ -40,7 +84,7 @@ impl ParsePrefix for If {
             None
         };
 
-        let expression = Self::new(condition, consequence, alternative);
+        let expression = Self::new(token, condition, consequence, alternative);
         Ok(expression.into())
     }
 }
@@ -62,3 +106,28 @@ impl Evaluate for If {
         }
     }
 }
+
+impl fmt::Display for If {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("if ")?;
+
+        // In alternate mode operators already bring their own parentheses.
+        if f.alternate() && self.condition.precedence() < Precedence::Call {
+            self.condition.fmt(f)?;
+        } else {
+            f.write_str("(")?;
+            self.condition.fmt(f)?;
+            f.write_str(")")?;
+        }
+
+        f.write_str(" ")?;
+        self.consequence.fmt(f)?;
+
+        if let Some(ref alternative) = self.alternative {
+            f.write_str(" else ")?;
+            alternative.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
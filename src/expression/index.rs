@@ -0,0 +1,109 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression::Expression;
+use crate::object::{Error, NULL, Object};
+use crate::parser::{ParseInfix, Parser, ParserError};
+use crate::precedence::Precedence;
+use crate::token::{Span, Token, TokenKind};
+
+/// `left[index]`.
+#[derive(Debug, Clone)]
+pub struct Index {
+    token: Token,
+    left: Box<Expression>,
+    index: Box<Expression>,
+    rbracket: Token,
+}
+
+impl Index {
+    pub const fn new(
+        token: Token,
+        left: Box<Expression>,
+        index: Box<Expression>,
+        rbracket: Token,
+    ) -> Self {
+        Self {
+            token,
+            left,
+            index,
+            rbracket,
+        }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn left(&self) -> &Expression {
+        &self.left
+    }
+
+    pub fn index(&self) -> &Expression {
+        &self.index
+    }
+
+    pub fn left_mut(&mut self) -> &mut Expression {
+        &mut self.left
+    }
+
+    pub fn index_mut(&mut self) -> &mut Expression {
+        &mut self.index
+    }
+
+    pub fn span(&self) -> Span {
+        self.left.span().to(self.rbracket.span())
+    }
+}
+
+impl ParseInfix for Index {
+    fn parse_infix(parser: &mut Parser<'_>, left: Expression) -> Result<Expression, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::LBracket)?;
+        let index =
+            parser.with_struct_literals(|parser| Expression::parse(parser, Precedence::Lowest))?;
+        let rbracket = parser.expect_token_with_kind(TokenKind::RBracket)?;
+
+        let expression = Self::new(token, Box::new(left), Box::new(index), rbracket);
+        Ok(expression.into())
+    }
+}
+
+impl Evaluate for Index {
+    fn evaluate(&self, env: &mut Environment) -> Object {
+        let left = self.left.evaluate(env);
+
+        if matches!(left, Object::Error(_)) {
+            return left;
+        }
+
+        let index = self.index.evaluate(env);
+
+        if matches!(index, Object::Error(_)) {
+            return index;
+        }
+
+        match (&left, &index) {
+            // Out of range reads give `null`, as in most scripting languages.
+            (Object::Array(array), Object::Integer(index)) => usize::try_from(index.value())
+                .ok()
+                .and_then(|index| array.elements().get(index))
+                .cloned()
+                .unwrap_or(NULL),
+            (Object::Array(_), Object::BigInteger(_)) => NULL,
+            (Object::Array(_), _) => {
+                Error::new(format!("index must be an integer, got {index}")).into()
+            }
+            _ => Error::new(format!("index operator not supported: {left}")).into(),
+        }
+    }
+}
+
+impl fmt::Display for Index {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.left.fmt_operand(f, Precedence::Call)?;
+        f.write_str("[")?;
+        self.index.fmt(f)?;
+        f.write_str("]")
+    }
+}
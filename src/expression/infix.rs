@@ -1,12 +1,16 @@
-use std::mem;
+use std::{fmt, mem};
+
+use num_bigint::BigInt;
+use num_traits::{Signed, ToPrimitive, Zero};
 
 use crate::environment::Environment;
 use crate::evaluator::Evaluate;
 use crate::expression::Expression;
-use crate::object::{Boolean, Error, Integer, NULL, Object};
+use crate::object::{Boolean, Error, Float, Integer, NULL, Object, Str};
 use crate::parser::{ParseInfix, Parser, ParserError};
 use crate::precedence::{PRECEDENCES, Precedence};
-use crate::token::{Token, TokenKind};
+use crate::runtime::Overflow;
+use crate::token::{Span, Token, TokenKind};
 
 #[derive(Debug, Clone)]
 pub struct Infix {
@@ -19,17 +23,58 @@ impl Infix {
     pub fn new(token: Token, left: Box<Expression>, right: Box<Expression>) -> Self {
         Self { token, left, right }
     }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn left(&self) -> &Expression {
+        &self.left
+    }
+
+    pub fn right(&self) -> &Expression {
+        &self.right
+    }
+
+    pub fn left_mut(&mut self) -> &mut Expression {
+        &mut self.left
+    }
+
+    pub fn right_mut(&mut self) -> &mut Expression {
+        &mut self.right
+    }
+
+    pub fn span(&self) -> Span {
+        self.left.span().to(self.right.span())
+    }
+
+    pub fn precedence(&self) -> Precedence {
+        PRECEDENCES
+            .get(&self.token.kind())
+            .copied()
+            .unwrap_or(Precedence::Lowest)
+    }
+
+    /// Whether `a op b op c` groups as `a op (b op c)`. Only `**` does.
+    pub fn is_right_associative(&self) -> bool {
+        self.token.kind() == TokenKind::DoubleAsterisk
+    }
 }
 
 impl ParseInfix for Infix {
     fn parse_infix(parser: &mut Parser<'_>, left: Expression) -> Result<Expression, ParserError> {
         let token = parser.token().unwrap().to_owned();
-        let precedence = PRECEDENCES
-            .get(&token.kind())
-            .unwrap_or(&Precedence::Lowest);
+        let precedence = match token.kind() {
+            // Parsing the right operand one level looser lets it take in another `**`.
+            TokenKind::DoubleAsterisk => Precedence::Prefix,
+            kind => PRECEDENCES
+                .get(&kind)
+                .copied()
+                .unwrap_or(Precedence::Lowest),
+        };
         parser.advance();
 
-        let right = Expression::parse(parser, precedence.to_owned())?;
+        let right = Expression::parse(parser, precedence)?;
 
         let expression = Self::new(token, Box::new(left), Box::new(right));
         Ok(expression.into())
@@ -44,13 +89,49 @@ impl Evaluate for Infix {
             return left;
         }
 
+        let operator = self.token.kind();
+
+        // `&&` and `||` only evaluate their right operand if the left one doesn't settle it.
+        if matches!(operator, TokenKind::DoubleAmpersand | TokenKind::DoublePipe) {
+            if left.as_boolean().value() == (operator == TokenKind::DoublePipe) {
+                return left.as_boolean().into();
+            }
+
+            let right = (*self.right).evaluate(env);
+
+            return match right {
+                Object::Error(_) => right,
+                _ => right.as_boolean().into(),
+            };
+        }
+
         let right = (*self.right).evaluate(env);
 
         if matches!(right, Object::Error(_)) {
             return right;
         }
 
-        let operator = self.token.kind();
+        // An integer next to a float is promoted to a float.
+        if matches!(
+            (&left, &right),
+            (Object::Float(_), _) | (_, Object::Float(_))
+        ) && let (Some(left_value), Some(right_value)) = (as_float(&left), as_float(&right))
+        {
+            return evaluate_floats(operator, left_value, right_value).unwrap_or_else(|| {
+                let message = format!("unknown operator: {left:?} {operator:?} {right:?}");
+                Error::new(message).into()
+            });
+        }
+
+        // Likewise for an integer next to a big one.
+        if matches!(
+            (&left, &right),
+            (Object::BigInteger(_), _) | (_, Object::BigInteger(_))
+        ) && let (Some(left_value), Some(right_value)) =
+            (as_big_integer(&left), as_big_integer(&right))
+        {
+            return evaluate_big_integers(operator, &left_value, &right_value);
+        }
 
         if mem::discriminant(&left) != mem::discriminant(&right) {
             let message = format!("type mismatch: {left:?} and {right:?}");
@@ -58,25 +139,52 @@ impl Evaluate for Infix {
         }
 
         match (operator, &left, &right) {
-            (_, Object::Integer(inner_left), Object::Integer(inner_right)) => match operator {
-                TokenKind::Plus => Integer::new(inner_left.value() + inner_right.value()).into(),
-                TokenKind::Minus => Integer::new(inner_left.value() - inner_right.value()).into(),
-                TokenKind::Asterisk => {
-                    Integer::new(inner_left.value() * inner_right.value()).into()
-                }
-                TokenKind::Slash => Integer::new(inner_left.value() / inner_right.value()).into(),
-                TokenKind::LessThan => {
-                    Boolean::new(inner_left.value() < inner_right.value()).into()
-                }
-                TokenKind::GreaterThan => {
-                    Boolean::new(inner_left.value() > inner_right.value()).into()
+            (_, Object::Integer(inner_left), Object::Integer(inner_right)) => {
+                let (left, right) = (inner_left.value(), inner_right.value());
+
+                evaluate_integers(operator, left, right).unwrap_or_else(|| {
+                    match env.runtime().overflow() {
+                        Overflow::Promote => {
+                            evaluate_big_integers(operator, &left.into(), &right.into())
+                        }
+                        Overflow::Error => {
+                            let message = format!("integer overflow: {left} {operator} {right}");
+                            Error::new(message).into()
+                        }
+                    }
+                })
+            }
+            (TokenKind::Plus, Object::String(inner_left), Object::String(inner_right)) => {
+                let value = [inner_left.value(), inner_right.value()].concat();
+
+                if let Err(err) = env.runtime().check_size(value.chars().count()) {
+                    return err.into();
                 }
-                TokenKind::Equal => Boolean::new(inner_left.value() == inner_right.value()).into(),
-                TokenKind::NotEqual => {
-                    Boolean::new(inner_left.value() != inner_right.value()).into()
+
+                if let Err(err) = env.runtime().allocate(1) {
+                    return err.into();
                 }
-                _ => NULL,
-            },
+
+                Str::new(value).into()
+            }
+            (
+                TokenKind::LessThan
+                | TokenKind::GreaterThan
+                | TokenKind::LessThanOrEqual
+                | TokenKind::GreaterThanOrEqual,
+                Object::String(inner_left),
+                Object::String(inner_right),
+            ) => {
+                let ordering = inner_left.value().cmp(inner_right.value());
+
+                Boolean::new(match operator {
+                    TokenKind::LessThan => ordering.is_lt(),
+                    TokenKind::GreaterThan => ordering.is_gt(),
+                    TokenKind::LessThanOrEqual => ordering.is_le(),
+                    _ => ordering.is_ge(),
+                })
+                .into()
+            }
             (TokenKind::Equal, _, _) => Boolean::new(left == right).into(),
             (TokenKind::NotEqual, _, _) => Boolean::new(left != right).into(),
             _ => {
@@ -86,3 +194,235 @@ impl Evaluate for Infix {
         }
     }
 }
+
+/// `None` if the result doesn't fit in an `i64`.
+fn evaluate_integers(operator: TokenKind, left: i64, right: i64) -> Option<Object> {
+    let value = match operator {
+        TokenKind::Plus => Integer::new(left.checked_add(right)?).into(),
+        TokenKind::Minus => Integer::new(left.checked_sub(right)?).into(),
+        TokenKind::Asterisk => Integer::new(left.checked_mul(right)?).into(),
+        TokenKind::Slash | TokenKind::Percent if right == 0 => {
+            Error::new("division by zero".to_owned()).into()
+        }
+        // Both round towards zero, so `a == a / b * b + a % b`. Only `i64::MIN / -1` overflows;
+        // the matching remainder is 0.
+        TokenKind::Slash => Integer::new(left.checked_div(right)?).into(),
+        TokenKind::Percent => Integer::new(left.wrapping_rem(right)).into(),
+        TokenKind::DoubleAsterisk => match u32::try_from(right) {
+            Ok(exponent) => Integer::new(left.checked_pow(exponent)?).into(),
+            Err(_) if right < 0 => {
+                Error::new(format!("negative exponent: {left} ** {right}")).into()
+            }
+            Err(_) => return None,
+        },
+        TokenKind::Ampersand => Integer::new(left & right).into(),
+        TokenKind::Pipe => Integer::new(left | right).into(),
+        TokenKind::Caret => Integer::new(left ^ right).into(),
+        TokenKind::ShiftLeft | TokenKind::ShiftRight => {
+            if right < 0 {
+                let message = format!("negative shift amount: {right}");
+                return Some(Error::new(message).into());
+            }
+
+            let amount = u32::try_from(right).unwrap_or(u32::MAX);
+
+            let value = if operator == TokenKind::ShiftLeft {
+                // Bits shifted past the top overflow like any other result too large to hold.
+                match left.checked_shl(amount) {
+                    _ if left == 0 => 0,
+                    Some(value) if value >> amount == left => value,
+                    _ => return None,
+                }
+            } else {
+                // Shifting every bit out leaves only the sign.
+                left.checked_shr(amount)
+                    .unwrap_or(if left < 0 { -1 } else { 0 })
+            };
+
+            Integer::new(value).into()
+        }
+        TokenKind::LessThan => Boolean::new(left < right).into(),
+        TokenKind::GreaterThan => Boolean::new(left > right).into(),
+        TokenKind::LessThanOrEqual => Boolean::new(left <= right).into(),
+        TokenKind::GreaterThanOrEqual => Boolean::new(left >= right).into(),
+        TokenKind::Equal => Boolean::new(left == right).into(),
+        TokenKind::NotEqual => Boolean::new(left != right).into(),
+        _ => NULL,
+    };
+
+    Some(value)
+}
+
+impl fmt::Display for Infix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precedence = self.precedence();
+
+        if f.alternate() {
+            f.write_str("(")?;
+        }
+
+        // Only the operand on the side the operator doesn't associate to needs parentheses when
+        // it binds exactly as tightly as `self`.
+        if !f.alternate() && self.is_right_associative() && self.left.precedence() == precedence {
+            f.write_str("(")?;
+            self.left.fmt(f)?;
+            f.write_str(")")?;
+        } else {
+            self.left.fmt_operand(f, precedence)?;
+        }
+
+        write!(f, " {} ", self.token.literal())?;
+
+        if !f.alternate() && !self.is_right_associative() && self.right.precedence() == precedence {
+            f.write_str("(")?;
+            self.right.fmt(f)?;
+            f.write_str(")")?;
+        } else {
+            self.right.fmt_operand(f, precedence)?;
+        }
+
+        if f.alternate() {
+            f.write_str(")")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Arithmetic follows IEEE 754: dividing by zero gives an infinity (or NaN for `0.0 / 0.0`), and
+/// every comparison with NaN is false except `!=`. `None` for operators floats don't support.
+fn evaluate_floats(operator: TokenKind, left: f64, right: f64) -> Option<Object> {
+    let value = match operator {
+        TokenKind::Plus => Float::new(left + right).into(),
+        TokenKind::Minus => Float::new(left - right).into(),
+        TokenKind::Asterisk => Float::new(left * right).into(),
+        TokenKind::Slash => Float::new(left / right).into(),
+        TokenKind::Percent => Float::new(left % right).into(),
+        TokenKind::DoubleAsterisk => Float::new(left.powf(right)).into(),
+        TokenKind::LessThan => Boolean::new(left < right).into(),
+        TokenKind::GreaterThan => Boolean::new(left > right).into(),
+        TokenKind::LessThanOrEqual => Boolean::new(left <= right).into(),
+        TokenKind::GreaterThanOrEqual => Boolean::new(left >= right).into(),
+        TokenKind::Equal => Boolean::new(left == right).into(),
+        TokenKind::NotEqual => Boolean::new(left != right).into(),
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+/// The most bits a big integer result may need, so a stray `**` or `<<` fails instead of
+/// exhausting memory.
+const MAX_BIG_INTEGER_BITS: u64 = 1 << 24;
+
+fn evaluate_big_integers(operator: TokenKind, left: &BigInt, right: &BigInt) -> Object {
+    let too_large = || Error::new(format!("integer too large: {left} {operator} {right}")).into();
+
+    match operator {
+        TokenKind::Plus => (left + right).into(),
+        TokenKind::Minus => (left - right).into(),
+        TokenKind::Asterisk => (left * right).into(),
+        TokenKind::Slash | TokenKind::Percent if right.is_zero() => {
+            Error::new("division by zero".to_owned()).into()
+        }
+        // Rounding towards zero, as for small integers.
+        TokenKind::Slash => (left / right).into(),
+        TokenKind::Percent => (left % right).into(),
+        TokenKind::DoubleAsterisk if right.is_negative() => {
+            Error::new(format!("negative exponent: {left} ** {right}")).into()
+        }
+        TokenKind::DoubleAsterisk => match right.to_u32() {
+            Some(exponent) if left.bits() * u64::from(exponent) <= MAX_BIG_INTEGER_BITS => {
+                left.pow(exponent).into()
+            }
+            _ => too_large(),
+        },
+        TokenKind::Ampersand => (left & right).into(),
+        TokenKind::Pipe => (left | right).into(),
+        TokenKind::Caret => (left ^ right).into(),
+        TokenKind::ShiftLeft | TokenKind::ShiftRight if right.is_negative() => {
+            Error::new(format!("negative shift amount: {right}")).into()
+        }
+        TokenKind::ShiftLeft => match right.to_u64() {
+            Some(amount) if left.bits().saturating_add(amount) <= MAX_BIG_INTEGER_BITS => {
+                (left << amount).into()
+            }
+            _ if left.is_zero() => Integer::new(0).into(),
+            _ => too_large(),
+        },
+        // Rounds towards negative infinity, like `>>` on small integers.
+        TokenKind::ShiftRight => match right.to_u64() {
+            Some(amount) => (left >> amount).into(),
+            None if left.is_negative() => Integer::new(-1).into(),
+            None => Integer::new(0).into(),
+        },
+        TokenKind::LessThan => Boolean::new(left < right).into(),
+        TokenKind::GreaterThan => Boolean::new(left > right).into(),
+        TokenKind::LessThanOrEqual => Boolean::new(left <= right).into(),
+        TokenKind::GreaterThanOrEqual => Boolean::new(left >= right).into(),
+        TokenKind::Equal => Boolean::new(left == right).into(),
+        TokenKind::NotEqual => Boolean::new(left != right).into(),
+        _ => NULL,
+    }
+}
+
+fn as_float(object: &Object) -> Option<f64> {
+    match *object {
+        Object::Float(ref inner) => Some(inner.value()),
+        Object::Integer(ref inner) => Some(inner.value() as f64),
+        Object::BigInteger(ref inner) => inner.value().to_f64(),
+        _ => None,
+    }
+}
+
+fn as_big_integer(object: &Object) -> Option<BigInt> {
+    match *object {
+        Object::Integer(ref inner) => Some(inner.value().into()),
+        Object::BigInteger(ref inner) => Some(inner.value().clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::Lexer;
+    use crate::program::Program;
+
+    fn evaluate(source: &str, overflow: Overflow) -> String {
+        let lexer = Lexer::new(source.to_owned());
+        let mut parser = Parser::new(lexer.tokens());
+        let program = Program::parse(&mut parser).unwrap();
+        assert!(program.errors().is_empty(), "{source:?}");
+
+        let mut env = Environment::default();
+        env.runtime().set_overflow(overflow);
+        program.evaluate(&mut env).to_string()
+    }
+
+    #[test]
+    fn shifting_left_never_loses_bits() {
+        for (source, expected) in [
+            ("1 << 62", "4611686018427387904"),
+            ("-1 << 63", "-9223372036854775808"),
+            ("0 << 1000", "0"),
+            ("-5 >> 1", "-3"),
+            ("5 >> 64", "0"),
+            ("-5 >> 4294967296", "-1"),
+            ("1 << -1", "ERROR: negative shift amount: -1"),
+        ] {
+            assert_eq!(evaluate(source, Overflow::Error), expected, "{source}");
+        }
+
+        for (source, promoted) in [
+            ("3 << 62", "13835058055282163712"),
+            ("1 << 63", "9223372036854775808"),
+            ("1 << 64", "18446744073709551616"),
+            ("-3 << 62", "-13835058055282163712"),
+        ] {
+            let overflow = format!("ERROR: integer overflow: {source}");
+            assert_eq!(evaluate(source, Overflow::Error), overflow);
+            assert_eq!(evaluate(source, Overflow::Promote), promoted, "{source}");
+        }
+    }
+}
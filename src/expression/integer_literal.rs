@@ -1,39 +1,102 @@
+use std::fmt;
+
+use num_bigint::BigInt;
+
 use crate::environment::Environment;
 use crate::evaluator::Evaluate;
 use crate::expression::Expression;
-use crate::object::{Integer, Object};
+use crate::lexer;
+use crate::object::{Error, Integer, Object};
 use crate::parser::{ParsePrefix, Parser, ParserError};
-use crate::token::TokenKind;
+use crate::runtime::Overflow;
+use crate::token::{Span, Token, TokenKind};
+
+#[derive(Debug, Clone)]
+enum Value {
+    Small(i64),
+    Big(BigInt),
+}
 
 #[derive(Debug, Clone)]
 pub struct IntegerLiteral {
-    value: i64,
+    token: Token,
+    value: Value,
 }
 
 impl IntegerLiteral {
-    pub fn new(value: i64) -> Self {
-        Self { value }
+    pub fn new(token: Token, value: i64) -> Self {
+        Self {
+            token,
+            value: Value::Small(value),
+        }
+    }
+
+    /// A literal too large for an `i64`.
+    pub fn big(token: Token, value: BigInt) -> Self {
+        Self {
+            token,
+            value: Value::Big(value),
+        }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
     }
 
-    pub const fn value(&self) -> i64 {
-        self.value
+    pub const fn span(&self) -> Span {
+        self.token.span()
+    }
+
+    /// `None` for literals too large for an `i64`.
+    pub const fn value(&self) -> Option<i64> {
+        match self.value {
+            Value::Small(value) => Some(value),
+            Value::Big(_) => None,
+        }
     }
 }
 
 impl ParsePrefix for IntegerLiteral {
     fn parse_prefix(parser: &mut Parser<'_>) -> Result<Expression, ParserError> {
-        parser
-            .expect_token_with_kind(TokenKind::Integer)
-            .map(|token| {
-                let value = token.literal().parse::<i64>().expect("expected valid i64");
-                Self::new(value)
-            })
-            .map(Expression::from)
+        let token = parser.expect_token_with_kind(TokenKind::Integer)?;
+        let (radix, digits) = lexer::split_radix(token.literal());
+        let digits = digits.replace('_', "");
+
+        if let Ok(value) = i64::from_str_radix(&digits, radix) {
+            return Ok(Self::new(token, value).into());
+        }
+
+        let Some(value) = BigInt::parse_bytes(digits.as_bytes(), radix) else {
+            return Err(ParserError::InvalidInteger {
+                literal: token.literal().to_owned(),
+                span: token.span(),
+            });
+        };
+
+        Ok(Self::big(token, value).into())
     }
 }
 
 impl Evaluate for IntegerLiteral {
-    fn evaluate(&self, _: &mut Environment) -> Object {
-        Integer::new(self.value).into()
+    fn evaluate(&self, env: &mut Environment) -> Object {
+        match self.value {
+            Value::Small(value) => Integer::new(value).into(),
+            Value::Big(ref value) => match env.runtime().overflow() {
+                Overflow::Promote => value.clone().into(),
+                Overflow::Error => {
+                    let message = format!("integer literal {value} does not fit in 64 bits");
+                    Error::new(message).into()
+                }
+            },
+        }
+    }
+}
+
+impl fmt::Display for IntegerLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value {
+            Value::Small(value) => value.fmt(f),
+            Value::Big(ref value) => value.fmt(f),
+        }
     }
 }
@@ -0,0 +1,93 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression::function_literal::parse_function_literal_parameters;
+use crate::expression::{Expression, Identifier};
+use crate::object::{Macro, Object};
+use crate::parser::{Parse, ParsePrefix, Parser, ParserError};
+use crate::statement::Block;
+use crate::token::{Span, Token, TokenKind};
+
+#[derive(Debug, Clone)]
+pub struct MacroLiteral {
+    token: Token,
+    parameters: Vec<Identifier>,
+    body: Block,
+}
+
+impl MacroLiteral {
+    pub fn new(token: Token, parameters: Vec<Identifier>, body: Block) -> Self {
+        Self {
+            token,
+            parameters,
+            body,
+        }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn parameters(&self) -> &[Identifier] {
+        &self.parameters
+    }
+
+    pub const fn body(&self) -> &Block {
+        &self.body
+    }
+
+    pub fn parameters_mut(&mut self) -> &mut [Identifier] {
+        &mut self.parameters
+    }
+
+    pub const fn body_mut(&mut self) -> &mut Block {
+        &mut self.body
+    }
+
+    pub fn span(&self) -> Span {
+        self.token.span().to(self.body.span())
+    }
+}
+
+impl ParsePrefix for MacroLiteral {
+    fn parse_prefix(parser: &mut Parser<'_>) -> Result<Expression, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::Macro)?;
+        let (parameters, _) = parse_function_literal_parameters(parser, false, |parser| {
+            parser
+                .expect_token_with_kind(TokenKind::Identifier)
+                .map(Identifier::new)
+        })?;
+        let body = parser.in_function_body(Block::parse)?;
+
+        let expression = Self::new(token, parameters, body);
+        Ok(expression.into())
+    }
+}
+
+impl Evaluate for MacroLiteral {
+    fn evaluate(&self, env: &mut Environment) -> Object {
+        if let Err(err) = env.runtime().allocate(1) {
+            return err.into();
+        }
+
+        Macro::new(self.parameters.clone(), self.body.clone(), env.clone()).into()
+    }
+}
+
+impl fmt::Display for MacroLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("macro(")?;
+
+        for (index, parameter) in self.parameters.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+
+            parameter.fmt(f)?;
+        }
+
+        f.write_str(") ")?;
+        self.body.fmt(f)
+    }
+}
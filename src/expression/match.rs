@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression::Expression;
+use crate::object::{Error, Object};
+use crate::parser::{Parse, ParsePrefix, Parser, ParserError};
+use crate::pattern::Pattern;
+use crate::precedence::Precedence;
+use crate::statement::{self, Block, Statement};
+use crate::token::{Span, Token, TokenKind};
+
+/// `pattern => body` or `pattern if guard => body` in a `match`. The body is an expression, or
+/// a block for more than one statement.
+#[derive(Debug, Clone)]
+pub struct Arm {
+    pattern: Pattern,
+    guard: Option<Expression>,
+    body: Statement,
+}
+
+impl Arm {
+    pub const fn new(pattern: Pattern, guard: Option<Expression>, body: Statement) -> Self {
+        Self {
+            pattern,
+            guard,
+            body,
+        }
+    }
+
+    pub const fn pattern(&self) -> &Pattern {
+        &self.pattern
+    }
+
+    pub const fn guard(&self) -> Option<&Expression> {
+        self.guard.as_ref()
+    }
+
+    pub const fn body(&self) -> &Statement {
+        &self.body
+    }
+
+    pub const fn pattern_mut(&mut self) -> &mut Pattern {
+        &mut self.pattern
+    }
+
+    pub const fn guard_mut(&mut self) -> Option<&mut Expression> {
+        self.guard.as_mut()
+    }
+
+    pub const fn body_mut(&mut self) -> &mut Statement {
+        &mut self.body
+    }
+
+    pub fn span(&self) -> Span {
+        self.pattern.span().to(self.body.span())
+    }
+
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
+        let pattern = Pattern::parse(parser)?;
+
+        let guard = if parser
+            .token()
+            .is_some_and(|token| token.kind() == TokenKind::If)
+        {
+            parser.advance();
+            Some(Expression::parse(parser, Precedence::Lowest)?)
+        } else {
+            None
+        };
+
+        _ = parser.expect_token_with_kind(TokenKind::FatArrow)?;
+
+        let body = if parser
+            .token()
+            .is_some_and(|token| token.kind() == TokenKind::LBrace)
+        {
+            Block::parse(parser)?.into()
+        } else {
+            statement::Expression::new(Expression::parse(parser, Precedence::Lowest)?).into()
+        };
+
+        if parser
+            .token()
+            .is_some_and(|token| token.kind() == TokenKind::Comma)
+        {
+            parser.advance();
+        }
+
+        Ok(Self::new(pattern, guard, body))
+    }
+}
+
+impl fmt::Display for Arm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.pattern.fmt(f)?;
+
+        if let Some(ref guard) = self.guard {
+            write!(f, " if {guard}")?;
+        }
+
+        f.write_str(" => ")?;
+
+        match self.body {
+            Statement::Expression(ref inner) => inner.expression().fmt(f),
+            ref body => body.fmt(f),
+        }
+    }
+}
+
+/// `match (value) { arm, ... }`: the body of the first arm whose pattern matches and whose guard,
+/// if any, is truthy. Each arm runs in a scope of its own.
+#[derive(Debug, Clone)]
+pub struct Match {
+    token: Token,
+    value: Box<Expression>,
+    arms: Vec<Arm>,
+    rbrace: Token,
+}
+
+impl Match {
+    pub const fn new(token: Token, value: Box<Expression>, arms: Vec<Arm>, rbrace: Token) -> Self {
+        Self {
+            token,
+            value,
+            arms,
+            rbrace,
+        }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn value(&self) -> &Expression {
+        &self.value
+    }
+
+    pub fn arms(&self) -> &[Arm] {
+        &self.arms
+    }
+
+    pub fn value_mut(&mut self) -> &mut Expression {
+        &mut self.value
+    }
+
+    pub fn arms_mut(&mut self) -> &mut [Arm] {
+        &mut self.arms
+    }
+
+    pub const fn span(&self) -> Span {
+        self.token.span().to(self.rbrace.span())
+    }
+}
+
+impl ParsePrefix for Match {
+    fn parse_prefix(parser: &mut Parser<'_>) -> Result<Expression, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::Match)?;
+        let value = parser.without_struct_literals(|parser| {
+            Expression::parse(parser, Precedence::Lowest).map(Box::new)
+        })?;
+
+        _ = parser.expect_token_with_kind(TokenKind::LBrace)?;
+
+        let arms = parser.with_struct_literals(|parser| {
+            let mut arms = Vec::new();
+
+            while parser
+                .token()
+                .is_some_and(|token| token.kind() != TokenKind::RBrace)
+            {
+                arms.push(Arm::parse(parser)?);
+            }
+
+            Ok(arms)
+        })?;
+
+        let rbrace = parser.expect_token_with_kind(TokenKind::RBrace)?;
+
+        Ok(Self::new(token, value, arms, rbrace).into())
+    }
+}
+
+impl Evaluate for Match {
+    fn evaluate(&self, env: &mut Environment) -> Object {
+        let value = self.value.evaluate(env);
+
+        if matches!(value, Object::Error(_)) {
+            return value;
+        }
+
+        for arm in &self.arms {
+            if let Err(err) = env.runtime().allocate(1) {
+                return err.into();
+            }
+
+            let mut scope = Environment::new(HashMap::new(), Some(Box::new(env.clone())));
+
+            match arm.pattern.matches(&value, &mut scope) {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(err) => return err,
+            }
+
+            if let Some(ref guard) = arm.guard {
+                let guard = guard.evaluate(&mut scope);
+
+                if matches!(guard, Object::Error(_)) {
+                    return guard;
+                }
+
+                if !guard.as_boolean().value() {
+                    continue;
+                }
+            }
+
+            return arm.body.evaluate(&mut scope);
+        }
+
+        Error::new(format!("no match arm for {value}")).into()
+    }
+}
+
+impl fmt::Display for Match {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("match (")?;
+        self.value.fmt(f)?;
+        f.write_str(") {")?;
+
+        for (index, arm) in self.arms.iter().enumerate() {
+            f.write_str(if index > 0 { ", " } else { " " })?;
+            arm.fmt(f)?;
+        }
+
+        if self.arms.is_empty() {
+            f.write_str("}")
+        } else {
+            f.write_str(" }")
+        }
+    }
+}
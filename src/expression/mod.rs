@@ -1,48 +1,97 @@
+mod array_literal;
 mod boolean;
 mod call;
+mod field_access;
+mod float_literal;
 mod function_literal;
 mod grouped;
 mod identifier;
 mod r#if;
+mod index;
 mod infix;
 mod integer_literal;
+mod macro_literal;
+mod r#match;
+mod path;
 mod prefix;
+mod string_literal;
+mod struct_literal;
+
+use std::fmt;
 
 use crate::evaluator::Evaluate;
 use crate::parser::{INFIX, PREFIX, Parser, ParserError};
 use crate::precedence::{PRECEDENCES, Precedence};
-use crate::token::TokenKind;
+use crate::token::{Span, Token, TokenKind};
 
+pub use array_literal::ArrayLiteral;
 pub use boolean::Boolean;
-pub use call::Call;
+pub use call::{Call, call_function};
+pub use field_access::{FieldAccess, qualified};
+pub use float_literal::FloatLiteral;
 pub use function_literal::FunctionLiteral;
 pub use grouped::Grouped;
 pub use identifier::Identifier;
 pub use r#if::If;
+pub use index::Index;
 pub use infix::Infix;
 pub use integer_literal::IntegerLiteral;
+pub use macro_literal::MacroLiteral;
+pub use r#match::{Arm, Match};
+pub use path::Path;
 pub use prefix::Prefix;
+pub use string_literal::StringLiteral;
+pub use struct_literal::StructLiteral;
 
 #[derive(Debug, Clone)]
 pub enum Expression {
     Identifier(Identifier),
     IntegerLiteral(IntegerLiteral),
+    FloatLiteral(FloatLiteral),
+    StringLiteral(StringLiteral),
     Prefix(Prefix),
     Infix(Infix),
     Boolean(Boolean),
     If(If),
     FunctionLiteral(FunctionLiteral),
     Call(Call),
+    MacroLiteral(MacroLiteral),
+    ArrayLiteral(ArrayLiteral),
+    Index(Index),
+    StructLiteral(StructLiteral),
+    FieldAccess(FieldAccess),
+    Path(Path),
+    Match(Match),
 }
 
 impl Expression {
     pub fn parse(parser: &mut Parser<'_>, precedence: Precedence) -> Result<Self, ParserError> {
-        assert_ne!(parser.token(), None, "Expression::parse called after EOF");
-        let token = parser.token().unwrap().to_owned();
-        let mut left = PREFIX
-            .get(&token.kind())
-            .map(|callback| callback(parser)) // NOTE: `callback` advances the parser.
-            .unwrap_or_else(|| panic!("missing parse_prefix fn: {:?}", token))?;
+        parser.nested(|parser| Self::parse_nested(parser, precedence))
+    }
+
+    fn parse_nested(parser: &mut Parser<'_>, precedence: Precedence) -> Result<Self, ParserError> {
+        let Some(token) = parser.token() else {
+            return Err(ParserError::UnexpectedEof {
+                span: parser.span(),
+            });
+        };
+
+        // Skip the bad literal, so the error is reported once rather than again for what follows.
+        if let TokenKind::Malformed(error) = token.kind() {
+            let span = token.span();
+            parser.advance();
+            return Err(ParserError::Lex { error, span });
+        }
+
+        let Some(&callback) = PREFIX.get(&token.kind()) else {
+            return Err(ParserError::UnexpectedToken {
+                actual: token.kind(),
+                span: token.span(),
+            });
+        };
+
+        // NOTE: `callback` advances the parser.
+        let mut left = callback(parser)?;
 
         while parser
             .token()
@@ -53,7 +102,7 @@ impl Expression {
                 .get(&token.kind())
                 .unwrap_or(&Precedence::Lowest);
 
-            if *peek_precedence < precedence {
+            if *peek_precedence <= precedence {
                 break;
             }
 
@@ -66,6 +115,87 @@ impl Expression {
 
         Ok(left)
     }
+
+    pub fn span(&self) -> Span {
+        match *self {
+            Self::Identifier(ref inner) => inner.span(),
+            Self::IntegerLiteral(ref inner) => inner.span(),
+            Self::FloatLiteral(ref inner) => inner.span(),
+            Self::StringLiteral(ref inner) => inner.span(),
+            Self::Prefix(ref inner) => inner.span(),
+            Self::Infix(ref inner) => inner.span(),
+            Self::Boolean(ref inner) => inner.span(),
+            Self::If(ref inner) => inner.span(),
+            Self::Call(ref inner) => inner.span(),
+            Self::FunctionLiteral(ref inner) => inner.span(),
+            Self::MacroLiteral(ref inner) => inner.span(),
+            Self::ArrayLiteral(ref inner) => inner.span(),
+            Self::Index(ref inner) => inner.span(),
+            Self::StructLiteral(ref inner) => inner.span(),
+            Self::FieldAccess(ref inner) => inner.span(),
+            Self::Path(ref inner) => inner.span(),
+            Self::Match(ref inner) => inner.span(),
+        }
+    }
+
+    /// The binding power of the expression's outermost operator.
+    ///
+    /// Used when printing to decide whether a sub-expression needs to be wrapped in parentheses
+    /// to parse back into the same tree. Anything that isn't an operator binds tightest.
+    pub fn precedence(&self) -> Precedence {
+        match *self {
+            Self::Infix(ref inner) => inner.precedence(),
+            Self::Prefix(_) => Precedence::Prefix,
+            Self::Call(_) => Precedence::Call,
+            _ => Precedence::Index,
+        }
+    }
+
+    /// Write `self`, wrapping it in parentheses if it binds looser than `precedence`.
+    ///
+    /// When the formatter is in alternate mode (`{:#}`), operators already parenthesise
+    /// themselves, so no extra parentheses are added.
+    pub(crate) fn fmt_operand(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        precedence: Precedence,
+    ) -> fmt::Result {
+        if !f.alternate() && self.precedence() < precedence {
+            f.write_str("(")?;
+            fmt::Display::fmt(self, f)?;
+            f.write_str(")")
+        } else {
+            fmt::Display::fmt(self, f)
+        }
+    }
+}
+
+/// Parse comma-separated expressions up to, but not including, `end`.
+pub(crate) fn parse_expression_list(
+    parser: &mut Parser<'_>,
+    end: TokenKind,
+) -> Result<Vec<Expression>, ParserError> {
+    parser.with_struct_literals(|parser| parse_expressions(parser, end))
+}
+
+fn parse_expressions(
+    parser: &mut Parser<'_>,
+    end: TokenKind,
+) -> Result<Vec<Expression>, ParserError> {
+    let mut expressions = Vec::new();
+
+    if parser.token().is_some_and(|token| token.kind() == end) {
+        return Ok(expressions);
+    }
+
+    expressions.push(Expression::parse(parser, Precedence::Lowest)?);
+
+    while let Some(TokenKind::Comma) = parser.token().map(Token::kind) {
+        _ = parser.expect_token_with_kind(TokenKind::Comma)?;
+        expressions.push(Expression::parse(parser, Precedence::Lowest)?);
+    }
+
+    Ok(expressions)
 }
 
 impl Evaluate for Expression {
@@ -73,18 +203,61 @@ impl Evaluate for Expression {
         let inner: &dyn Evaluate = match *self {
             Self::Identifier(ref inner) => inner,
             Self::IntegerLiteral(ref inner) => inner,
+            Self::FloatLiteral(ref inner) => inner,
+            Self::StringLiteral(ref inner) => inner,
             Self::Prefix(ref inner) => inner,
             Self::Infix(ref inner) => inner,
             Self::Boolean(ref inner) => inner,
             Self::If(ref inner) => inner,
             Self::Call(ref inner) => inner,
             Self::FunctionLiteral(ref inner) => inner,
+            Self::MacroLiteral(ref inner) => inner,
+            Self::ArrayLiteral(ref inner) => inner,
+            Self::Index(ref inner) => inner,
+            Self::StructLiteral(ref inner) => inner,
+            Self::FieldAccess(ref inner) => inner,
+            Self::Path(ref inner) => inner,
+            Self::Match(ref inner) => inner,
         };
 
+        if let Err(err) = env.runtime().step() {
+            return err.into();
+        }
+
         (*inner).evaluate(env)
     }
 }
 
+/// Prints canonical Monkey source.
+///
+/// The alternate flag (`{:#}`) fully parenthesises every `Infix` and `Prefix` expression, making
+/// the parsed precedence visible, e.g. `((a + (b * c)) + d)`.
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner: &dyn fmt::Display = match *self {
+            Self::Identifier(ref inner) => inner,
+            Self::IntegerLiteral(ref inner) => inner,
+            Self::FloatLiteral(ref inner) => inner,
+            Self::StringLiteral(ref inner) => inner,
+            Self::Prefix(ref inner) => inner,
+            Self::Infix(ref inner) => inner,
+            Self::Boolean(ref inner) => inner,
+            Self::If(ref inner) => inner,
+            Self::Call(ref inner) => inner,
+            Self::FunctionLiteral(ref inner) => inner,
+            Self::MacroLiteral(ref inner) => inner,
+            Self::ArrayLiteral(ref inner) => inner,
+            Self::Index(ref inner) => inner,
+            Self::StructLiteral(ref inner) => inner,
+            Self::FieldAccess(ref inner) => inner,
+            Self::Path(ref inner) => inner,
+            Self::Match(ref inner) => inner,
+        };
+
+        inner.fmt(f)
+    }
+}
+
 impl From<Identifier> for Expression {
     fn from(value: Identifier) -> Self {
         Self::Identifier(value)
@@ -97,6 +270,18 @@ impl From<IntegerLiteral> for Expression {
     }
 }
 
+impl From<FloatLiteral> for Expression {
+    fn from(value: FloatLiteral) -> Self {
+        Self::FloatLiteral(value)
+    }
+}
+
+impl From<StringLiteral> for Expression {
+    fn from(value: StringLiteral) -> Self {
+        Self::StringLiteral(value)
+    }
+}
+
 impl From<Prefix> for Expression {
     fn from(value: Prefix) -> Self {
         Self::Prefix(value)
@@ -132,3 +317,45 @@ impl From<Call> for Expression {
         Self::Call(value)
     }
 }
+
+impl From<MacroLiteral> for Expression {
+    fn from(value: MacroLiteral) -> Self {
+        Self::MacroLiteral(value)
+    }
+}
+
+impl From<ArrayLiteral> for Expression {
+    fn from(value: ArrayLiteral) -> Self {
+        Self::ArrayLiteral(value)
+    }
+}
+
+impl From<Index> for Expression {
+    fn from(value: Index) -> Self {
+        Self::Index(value)
+    }
+}
+
+impl From<StructLiteral> for Expression {
+    fn from(value: StructLiteral) -> Self {
+        Self::StructLiteral(value)
+    }
+}
+
+impl From<FieldAccess> for Expression {
+    fn from(value: FieldAccess) -> Self {
+        Self::FieldAccess(value)
+    }
+}
+
+impl From<Path> for Expression {
+    fn from(value: Path) -> Self {
+        Self::Path(value)
+    }
+}
+
+impl From<Match> for Expression {
+    fn from(value: Match) -> Self {
+        Self::Match(value)
+    }
+}
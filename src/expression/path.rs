@@ -0,0 +1,88 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression::{Expression, Identifier};
+use crate::object::{Constructor, Enum, Error, Object};
+use crate::parser::{ParseInfix, Parser, ParserError};
+use crate::precedence::Precedence;
+use crate::token::{Span, Token, TokenKind};
+
+/// `Enum::Variant`: the variant itself if it carries nothing, or a function that builds it.
+#[derive(Debug, Clone)]
+pub struct Path {
+    token: Token,
+    left: Box<Expression>,
+    variant: Identifier,
+}
+
+impl Path {
+    pub const fn new(token: Token, left: Box<Expression>, variant: Identifier) -> Self {
+        Self {
+            token,
+            left,
+            variant,
+        }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn left(&self) -> &Expression {
+        &self.left
+    }
+
+    pub const fn variant(&self) -> &Identifier {
+        &self.variant
+    }
+
+    pub fn left_mut(&mut self) -> &mut Expression {
+        &mut self.left
+    }
+
+    pub fn span(&self) -> Span {
+        self.left.span().to(self.variant.span())
+    }
+}
+
+impl ParseInfix for Path {
+    fn parse_infix(parser: &mut Parser<'_>, left: Expression) -> Result<Expression, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::DoubleColon)?;
+
+        let variant = parser
+            .expect_token_with_kind(TokenKind::Identifier)
+            .map(Identifier::new)?;
+
+        Ok(Self::new(token, Box::new(left), variant).into())
+    }
+}
+
+impl Evaluate for Path {
+    fn evaluate(&self, env: &mut Environment) -> Object {
+        let left = self.left.evaluate(env);
+        let variant = self.variant.token().literal();
+
+        let ty = match left {
+            Object::Error(_) => return left,
+            Object::EnumType(ty) => ty,
+            _ => return Error::new(format!("{left} is not an enum")).into(),
+        };
+
+        match ty.variant_index(variant) {
+            Some(index) if ty.variants()[index].1 == 0 => Enum::new(ty, index, Vec::new()).into(),
+            Some(index) => Constructor::new(ty, index).into(),
+            None => {
+                let message = format!("enum {} has no variant {variant:?}", ty.name());
+                Error::new(message).into()
+            }
+        }
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.left.fmt_operand(f, Precedence::Call)?;
+        write!(f, "::{}", self.variant)
+    }
+}
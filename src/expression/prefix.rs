@@ -1,10 +1,13 @@
+use std::fmt;
+
 use crate::environment::Environment;
 use crate::evaluator::Evaluate;
 use crate::expression::Expression;
-use crate::object::{Error, Integer, Object};
+use crate::object::{Error, Float, Integer, Object};
 use crate::parser::{ParsePrefix, Parser, ParserError};
 use crate::precedence::Precedence;
-use crate::token::{Token, TokenKind};
+use crate::runtime::Overflow;
+use crate::token::{Span, Token, TokenKind};
 
 #[derive(Debug, Clone)]
 pub struct Prefix {
@@ -16,13 +19,29 @@ impl Prefix {
     pub fn new(token: Token, right: Box<Expression>) -> Self {
         Self { token, right }
     }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn right(&self) -> &Expression {
+        &self.right
+    }
+
+    pub fn right_mut(&mut self) -> &mut Expression {
+        &mut self.right
+    }
+
+    pub fn span(&self) -> Span {
+        self.token.span().to(self.right.span())
+    }
 }
 
 impl ParsePrefix for Prefix {
     fn parse_prefix(parser: &mut Parser<'_>) -> Result<Expression, ParserError> {
         assert!(matches!(
             parser.token().map(|token| token.kind()),
-            Some(TokenKind::Minus) | Some(TokenKind::Bang)
+            Some(TokenKind::Minus) | Some(TokenKind::Bang) | Some(TokenKind::Tilde)
         ));
         let token = parser.token().unwrap().to_owned();
         parser.advance();
@@ -45,10 +64,43 @@ impl Evaluate for Prefix {
         match self.token.kind() {
             TokenKind::Bang => (!right.as_boolean()).into(),
             TokenKind::Minus => match right {
-                Object::Integer(inner) => Integer::new(-inner.value()).into(),
+                Object::Integer(inner) => match inner.value().checked_neg() {
+                    Some(value) => Integer::new(value).into(),
+                    None => match env.runtime().overflow() {
+                        Overflow::Promote => (-num_bigint::BigInt::from(inner.value())).into(),
+                        Overflow::Error => {
+                            let message = format!("integer overflow: -({})", inner.value());
+                            Error::new(message).into()
+                        }
+                    },
+                },
+                Object::BigInteger(inner) => (-inner.value()).into(),
+                Object::Float(inner) => Float::new(-inner.value()).into(),
                 _ => Error::new(format!("unknown operator: -{right:?}")).into(),
             },
+            TokenKind::Tilde => match right {
+                Object::Integer(inner) => Integer::new(!inner.value()).into(),
+                Object::BigInteger(inner) => (!inner.value()).into(),
+                _ => Error::new(format!("unknown operator: ~{right:?}")).into(),
+            },
             kind => Error::new(format!("unknown operator: {kind:?}")).into(),
         }
     }
 }
+
+impl fmt::Display for Prefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("(")?;
+        }
+
+        self.token.literal().fmt(f)?;
+        self.right.fmt_operand(f, Precedence::Prefix)?;
+
+        if f.alternate() {
+            f.write_str(")")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression::Expression;
+use crate::lexer;
+use crate::object::{Object, Str};
+use crate::parser::{ParsePrefix, Parser, ParserError};
+use crate::token::{Span, Token, TokenKind};
+
+#[derive(Debug, Clone)]
+pub struct StringLiteral {
+    token: Token,
+    value: Str,
+}
+
+impl StringLiteral {
+    pub fn new(token: Token, value: String) -> Self {
+        Self {
+            token,
+            value: Str::new(value),
+        }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub const fn span(&self) -> Span {
+        self.token.span()
+    }
+
+    /// The string with its escapes resolved.
+    pub fn value(&self) -> &str {
+        self.value.value()
+    }
+}
+
+impl ParsePrefix for StringLiteral {
+    fn parse_prefix(parser: &mut Parser<'_>) -> Result<Expression, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::String)?;
+
+        let value = lexer::unescape(token.literal()).map_err(|error| ParserError::Lex {
+            error,
+            span: token.span(),
+        })?;
+
+        Ok(Self::new(token, value).into())
+    }
+}
+
+impl Evaluate for StringLiteral {
+    fn evaluate(&self, _: &mut Environment) -> Object {
+        self.value.clone().into()
+    }
+}
+
+impl fmt::Display for StringLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.value())
+    }
+}
@@ -0,0 +1,194 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression::{Expression, Identifier, qualified};
+use crate::object::{Error, Object, Struct};
+use crate::parser::{Parser, ParserError};
+use crate::precedence::Precedence;
+use crate::token::{Span, Token, TokenKind};
+
+/// `Name { field: value, ... }`, building an instance of the struct bound to `Name`, or
+/// `module.Name { ... }` for one a module exports.
+#[derive(Debug, Clone)]
+pub struct StructLiteral {
+    module: Option<Identifier>,
+    name: Identifier,
+    fields: Vec<(Identifier, Expression)>,
+    rbrace: Token,
+}
+
+impl StructLiteral {
+    pub fn new(
+        module: Option<Identifier>,
+        name: Identifier,
+        fields: Vec<(Identifier, Expression)>,
+        rbrace: Token,
+    ) -> Self {
+        Self {
+            module,
+            name,
+            fields,
+            rbrace,
+        }
+    }
+
+    pub const fn module(&self) -> Option<&Identifier> {
+        self.module.as_ref()
+    }
+
+    pub const fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn fields(&self) -> &[(Identifier, Expression)] {
+        &self.fields
+    }
+
+    pub const fn module_mut(&mut self) -> Option<&mut Identifier> {
+        self.module.as_mut()
+    }
+
+    pub const fn name_mut(&mut self) -> &mut Identifier {
+        &mut self.name
+    }
+
+    pub fn fields_mut(&mut self) -> &mut [(Identifier, Expression)] {
+        &mut self.fields
+    }
+
+    pub fn span(&self) -> Span {
+        self.module
+            .as_ref()
+            .unwrap_or(&self.name)
+            .span()
+            .to(self.rbrace.span())
+    }
+
+    /// Parse the `{ field: value, ... }` after `name`, or after `module.name`.
+    pub(crate) fn parse_fields(
+        parser: &mut Parser<'_>,
+        module: Option<Identifier>,
+        name: Identifier,
+    ) -> Result<Expression, ParserError> {
+        _ = parser.expect_token_with_kind(TokenKind::LBrace)?;
+
+        let mut fields = Vec::<(Identifier, Expression)>::new();
+
+        while parser
+            .token()
+            .is_some_and(|token| token.kind() != TokenKind::RBrace)
+        {
+            let field = parser
+                .expect_token_with_kind(TokenKind::Identifier)
+                .map(Identifier::new)?;
+
+            if fields
+                .iter()
+                .any(|(other, _)| other.token().literal() == field.token().literal())
+            {
+                parser.report(ParserError::DuplicateField {
+                    field: field.token().literal().to_owned(),
+                    span: field.span(),
+                });
+            }
+
+            _ = parser.expect_token_with_kind(TokenKind::Colon)?;
+            let value = parser
+                .with_struct_literals(|parser| Expression::parse(parser, Precedence::Lowest))?;
+            fields.push((field, value));
+
+            if parser
+                .token()
+                .is_none_or(|token| token.kind() != TokenKind::Comma)
+            {
+                break;
+            }
+
+            parser.advance();
+        }
+
+        let rbrace = parser.expect_token_with_kind(TokenKind::RBrace)?;
+        Ok(Self::new(module, name, fields, rbrace).into())
+    }
+}
+
+impl Evaluate for StructLiteral {
+    fn evaluate(&self, env: &mut Environment) -> Object {
+        let ty = match qualified(self.module.as_ref(), &self.name, env) {
+            Object::StructType(ty) => ty,
+            Object::Error(err) => return err.into(),
+            _ => {
+                let message = format!("{:?} is not a struct", self.name.token().literal());
+                return Error::new(message).into();
+            }
+        };
+
+        let mut values = vec![None; ty.fields().len()];
+
+        for (field, expression) in &self.fields {
+            let field = field.token().literal();
+
+            let Some(index) = ty.field_index(field) else {
+                let message = format!("struct {} has no field {field:?}", ty.name());
+                return Error::new(message).into();
+            };
+
+            let value = expression.evaluate(env);
+
+            if matches!(value, Object::Error(_)) {
+                return value;
+            }
+
+            values[index] = Some(value);
+        }
+
+        let values = match values.into_iter().collect::<Option<Vec<_>>>() {
+            Some(values) => values,
+            None => {
+                let missing = ty
+                    .fields()
+                    .iter()
+                    .filter(|&field| {
+                        !self
+                            .fields
+                            .iter()
+                            .any(|(name, _)| name.token().literal() == field)
+                    })
+                    .map(|field| format!("{field:?}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                let message = format!("missing fields in {}: {missing}", ty.name());
+                return Error::new(message).into();
+            }
+        };
+
+        if let Err(err) = env.runtime().allocate(1) {
+            return err.into();
+        }
+
+        Struct::new(ty, values).into()
+    }
+}
+
+impl fmt::Display for StructLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ref module) = self.module {
+            write!(f, "{module}.")?;
+        }
+
+        write!(f, "{} {{", self.name)?;
+
+        for (index, (field, value)) in self.fields.iter().enumerate() {
+            f.write_str(if index > 0 { ", " } else { " " })?;
+            write!(f, "{field}: {value}")?;
+        }
+
+        if self.fields.is_empty() {
+            f.write_str("}")
+        } else {
+            f.write_str(" }")
+        }
+    }
+}
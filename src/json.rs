@@ -0,0 +1,523 @@
+use std::error;
+use std::fmt::{self, Write as _};
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::token::Position;
+
+/// A JSON document, kept in memory so it can be serialised compactly or pretty-printed.
+///
+/// Object members keep their insertion order, so output is stable from run to run.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Build an object from `(key, value)` pairs.
+    pub fn object<K: Into<String>>(members: impl IntoIterator<Item = (K, Json)>) -> Self {
+        Self::Object(
+            members
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        )
+    }
+
+    /// Parse a complete JSON document. A number is an `Integer` if it has no fraction or
+    /// exponent and fits in an `i64`, and a `Float` otherwise.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut reader = Reader {
+            chars: input.chars().peekable(),
+            position: Position::new(1, 1),
+            depth: 0,
+        };
+
+        let value = reader.value()?;
+        reader.skip_whitespace();
+
+        match reader.chars.peek() {
+            None => Ok(value),
+            Some(_) => Err(reader.error("trailing characters after JSON value")),
+        }
+    }
+
+    /// Look up `key` if `self` is an object.
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        match *self {
+            Self::Object(ref members) => members
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub const fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Self::Boolean(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub const fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Self::Integer(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            Self::String(ref value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Self]> {
+        match *self {
+            Self::Array(ref elements) => Some(elements),
+            _ => None,
+        }
+    }
+
+    /// Serialise `self`, placing every array element and object member on its own line indented
+    /// by `indent` spaces per level. An `indent` of zero produces compact output.
+    pub fn stringify(&self, indent: usize) -> String {
+        let mut buffer = String::new();
+        self.write(&mut buffer, indent, 0);
+        buffer
+    }
+
+    fn write(&self, buffer: &mut String, indent: usize, depth: usize) {
+        match *self {
+            Self::Null => buffer.push_str("null"),
+            Self::Boolean(value) => _ = write!(buffer, "{value}"),
+            Self::Integer(value) => _ = write!(buffer, "{value}"),
+            // Debug formatting keeps a fraction or exponent, so the value reads back as a float.
+            Self::Float(value) if value.is_finite() => _ = write!(buffer, "{value:?}"),
+            // JSON can't represent these.
+            Self::Float(_) => buffer.push_str("null"),
+            Self::String(ref value) => write_string(buffer, value),
+            Self::Array(ref elements) => {
+                if elements.is_empty() {
+                    return buffer.push_str("[]");
+                }
+
+                buffer.push('[');
+
+                for (index, element) in elements.iter().enumerate() {
+                    if index > 0 {
+                        buffer.push(',');
+                    }
+
+                    write_newline(buffer, indent, depth + 1);
+                    element.write(buffer, indent, depth + 1);
+                }
+
+                write_newline(buffer, indent, depth);
+                buffer.push(']');
+            }
+            Self::Object(ref members) => {
+                if members.is_empty() {
+                    return buffer.push_str("{}");
+                }
+
+                buffer.push('{');
+
+                for (index, (key, value)) in members.iter().enumerate() {
+                    if index > 0 {
+                        buffer.push(',');
+                    }
+
+                    write_newline(buffer, indent, depth + 1);
+                    write_string(buffer, key);
+                    buffer.push(':');
+
+                    if indent > 0 {
+                        buffer.push(' ');
+                    }
+
+                    value.write(buffer, indent, depth + 1);
+                }
+
+                write_newline(buffer, indent, depth);
+                buffer.push('}');
+            }
+        }
+    }
+}
+
+/// Why `Json::parse` rejected its input, and where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+    position: Position,
+}
+
+impl ParseError {
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub const fn position(&self) -> Position {
+        self.position
+    }
+}
+
+/// Formats as `line:column: message`.
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.position, self.message)
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// How deeply arrays and objects may nest, so a hostile document can't exhaust the stack.
+const MAX_DEPTH: usize = 512;
+
+struct Reader<'a> {
+    chars: Peekable<Chars<'a>>,
+    position: Position,
+    /// The number of arrays and objects the reader is inside.
+    depth: usize,
+}
+
+impl Reader<'_> {
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+
+        self.position = if c == '\n' {
+            Position::new(self.position.line() + 1, 1)
+        } else {
+            Position::new(self.position.line(), self.position.column() + 1)
+        };
+
+        Some(c)
+    }
+
+    fn error(&self, message: &str) -> ParseError {
+        ParseError {
+            message: message.to_owned(),
+            position: self.position,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .chars
+            .peek()
+            .is_some_and(|c| matches!(c, ' ' | '\t' | '\n' | '\r'))
+        {
+            _ = self.bump();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        match self.chars.peek() {
+            Some(&c) if c == expected => {
+                _ = self.bump();
+                Ok(())
+            }
+            Some(&c) => Err(self.error(&format!("expected {expected:?}, got {c:?}"))),
+            None => Err(self.error(&format!("expected {expected:?}, got end of input"))),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str, value: Json) -> Result<Json, ParseError> {
+        for expected in keyword.chars() {
+            self.expect(expected)?;
+        }
+
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<Json, ParseError> {
+        self.skip_whitespace();
+
+        match self.chars.peek() {
+            Some('n') => self.keyword("null", Json::Null),
+            Some('t') => self.keyword("true", Json::Boolean(true)),
+            Some('f') => self.keyword("false", Json::Boolean(false)),
+            Some('"') => self.string().map(Json::String),
+            Some('[') => self.nested(Self::array),
+            Some('{') => self.nested(Self::object),
+            Some('-' | '0'..='9') => self.number(),
+            Some(&c) => Err(self.error(&format!("unexpected character {c:?}"))),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn nested(
+        &mut self,
+        read: fn(&mut Self) -> Result<Json, ParseError>,
+    ) -> Result<Json, ParseError> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error(&format!(
+                "arrays and objects nested more than {MAX_DEPTH} deep"
+            )));
+        }
+
+        self.depth += 1;
+        let value = read(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn array(&mut self) -> Result<Json, ParseError> {
+        self.expect('[')?;
+        let mut elements = Vec::new();
+        self.skip_whitespace();
+
+        if self.chars.peek() == Some(&']') {
+            _ = self.bump();
+            return Ok(Json::Array(elements));
+        }
+
+        loop {
+            elements.push(self.value()?);
+            self.skip_whitespace();
+
+            if self.chars.peek() == Some(&',') {
+                _ = self.bump();
+            } else {
+                self.expect(']')?;
+                return Ok(Json::Array(elements));
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, ParseError> {
+        self.expect('{')?;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+
+        if self.chars.peek() == Some(&'}') {
+            _ = self.bump();
+            return Ok(Json::Object(members));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            members.push((key, self.value()?));
+            self.skip_whitespace();
+
+            if self.chars.peek() == Some(&',') {
+                _ = self.bump();
+            } else {
+                self.expect('}')?;
+                return Ok(Json::Object(members));
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, ParseError> {
+        let start = self.position;
+        let mut literal = String::new();
+
+        if self.chars.peek() == Some(&'-') {
+            literal.extend(self.bump());
+        }
+
+        if self.chars.peek() == Some(&'0') {
+            literal.extend(self.bump());
+
+            if self.chars.peek().is_some_and(char::is_ascii_digit) {
+                return Err(self.error("a number can't start with a leading zero"));
+            }
+        } else {
+            self.digits(&mut literal)?;
+        }
+
+        let mut integer = true;
+
+        if self.chars.peek() == Some(&'.') {
+            integer = false;
+            literal.extend(self.bump());
+            self.digits(&mut literal)?;
+        }
+
+        if let Some('e' | 'E') = self.chars.peek() {
+            integer = false;
+            literal.extend(self.bump());
+
+            if let Some('+' | '-') = self.chars.peek() {
+                literal.extend(self.bump());
+            }
+
+            self.digits(&mut literal)?;
+        }
+
+        if integer && let Ok(value) = literal.parse::<i64>() {
+            return Ok(Json::Integer(value));
+        }
+
+        literal
+            .parse::<f64>()
+            .map(Json::Float)
+            .map_err(|_| ParseError {
+                message: format!("invalid number {literal:?}"),
+                position: start,
+            })
+    }
+
+    /// Read one or more decimal digits onto the end of `literal`.
+    fn digits(&mut self, literal: &mut String) -> Result<(), ParseError> {
+        if !self.chars.peek().is_some_and(char::is_ascii_digit) {
+            return Err(self.error("expected a digit"));
+        }
+
+        while self.chars.peek().is_some_and(char::is_ascii_digit) {
+            literal.extend(self.bump());
+        }
+
+        Ok(())
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        self.expect('"')?;
+        let mut value = String::new();
+
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(value),
+                Some('\\') => match self.bump() {
+                    Some('"') => value.push('"'),
+                    Some('\\') => value.push('\\'),
+                    Some('/') => value.push('/'),
+                    Some('b') => value.push('\u{8}'),
+                    Some('f') => value.push('\u{c}'),
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some('t') => value.push('\t'),
+                    Some('u') => value.push(self.unicode_escape()?),
+                    _ => return Err(self.error("invalid escape sequence")),
+                },
+                Some(c) if c < ' ' => {
+                    return Err(self.error("control character in string"));
+                }
+                Some(c) => value.push(c),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    /// Read the `XXXX` of a `\uXXXX` escape, combining surrogate pairs.
+    fn unicode_escape(&mut self) -> Result<char, ParseError> {
+        let high = self.hex4()?;
+
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| self.error("invalid unicode escape"));
+        }
+
+        self.expect('\\')?;
+        self.expect('u')?;
+        let low = self.hex4()?;
+
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.error("invalid surrogate pair"));
+        }
+
+        let code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, ParseError> {
+        let mut code = 0;
+
+        for _ in 0..4 {
+            let digit = self
+                .bump()
+                .and_then(|c| c.to_digit(16))
+                .ok_or_else(|| self.error("invalid unicode escape"))?;
+            code = code * 16 + digit;
+        }
+
+        Ok(code)
+    }
+}
+
+fn write_newline(buffer: &mut String, indent: usize, depth: usize) {
+    if indent > 0 {
+        buffer.push('\n');
+        buffer.extend(std::iter::repeat_n(' ', indent * depth));
+    }
+}
+
+fn write_string(buffer: &mut String, value: &str) {
+    buffer.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => buffer.push_str("\\\""),
+            '\\' => buffer.push_str("\\\\"),
+            '\n' => buffer.push_str("\\n"),
+            '\r' => buffer.push_str("\\r"),
+            '\t' => buffer.push_str("\\t"),
+            c if c.is_control() => _ = write!(buffer, "\\u{:04x}", c as u32),
+            c => buffer.push(c),
+        }
+    }
+
+    buffer.push('"');
+}
+
+/// Compact by default; the alternate flag (`{:#}`) pretty-prints with two spaces per level.
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let indent = if f.alternate() { 2 } else { 0 };
+        f.write_str(&self.stringify(indent))
+    }
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
+impl From<i64> for Json {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<usize> for Json {
+    fn from(value: usize) -> Self {
+        Self::Integer(value as i64)
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<Vec<Json>> for Json {
+    fn from(value: Vec<Json>) -> Self {
+        Self::Array(value)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
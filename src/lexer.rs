@@ -1,7 +1,183 @@
 use std::iter::Peekable;
 use std::str::Chars;
+use std::{error, fmt};
 
-use crate::token::Token;
+use unicode_normalization::UnicodeNormalization;
+use unicode_xid::UnicodeXID;
+
+use crate::token::{Position, Span, Token, TokenKind};
+
+/// Why a piece of source can't be read. The lexer yields it as a single `TokenKind::Malformed`
+/// token, so the parser can report exactly what is wrong with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LexError {
+    /// A character no token starts with, like a pasted curly quote.
+    UnexpectedCharacter { character: char },
+    /// A string with no closing quote on its line.
+    UnterminatedString,
+    /// A `\` followed by something other than `n`, `t`, `r`, `0`, `\\`, `"` or `u{...}`.
+    InvalidEscape { escape: char },
+    /// `0x`, `0o` or `0b` with no digits after it.
+    MissingDigits { radix: u32 },
+    /// A digit the literal's radix doesn't have, like the `2` in `0b102`.
+    InvalidDigit { digit: char, radix: u32 },
+    /// A `_` that isn't between two digits, as in `1__0` or `1_`.
+    MisplacedSeparator,
+    /// An `e` with no digits after it, as in `1.5e` or `1e+`.
+    MissingExponent,
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::UnexpectedCharacter { character } => {
+                write!(f, "unexpected character U+{:04X}", u32::from(character))?;
+
+                if !character.is_control() && !character.is_whitespace() {
+                    write!(f, " ({character})")?;
+                }
+
+                Ok(())
+            }
+            Self::UnterminatedString => f.write_str("unterminated string"),
+            Self::InvalidEscape { escape } => write!(f, "invalid escape `\\{escape}`"),
+            Self::MissingDigits { radix } => {
+                write!(f, "missing digits after `{}`", radix_prefix(radix))
+            }
+            Self::InvalidDigit { digit, radix } => {
+                write!(
+                    f,
+                    "invalid digit `{digit}` in {} literal",
+                    radix_name(radix)
+                )
+            }
+            Self::MisplacedSeparator => f.write_str("`_` must be between two digits"),
+            Self::MissingExponent => f.write_str("missing digits in exponent"),
+        }
+    }
+}
+
+impl error::Error for LexError {}
+
+const fn radix_prefix(radix: u32) -> &'static str {
+    match radix {
+        16 => "0x",
+        8 => "0o",
+        2 => "0b",
+        _ => "",
+    }
+}
+
+const fn radix_name(radix: u32) -> &'static str {
+    match radix {
+        16 => "hexadecimal",
+        8 => "octal",
+        2 => "binary",
+        _ => "decimal",
+    }
+}
+
+/// The value of a string literal, given with its quotes.
+pub(crate) fn unescape(literal: &str) -> Result<String, LexError> {
+    let inner = literal
+        .strip_prefix('"')
+        .and_then(|inner| inner.strip_suffix('"'))
+        .ok_or(LexError::UnterminatedString)?;
+
+    let mut value = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+
+        let escape = chars.next().ok_or(LexError::UnterminatedString)?;
+
+        value.push(match escape {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '0' => '\0',
+            '\\' | '"' => escape,
+            'u' => {
+                let rest = chars.as_str();
+                let code = rest
+                    .strip_prefix('{')
+                    .and_then(|rest| rest.split_once('}'))
+                    .map(|(code, _)| code)
+                    .filter(|code| (1..=6).contains(&code.len()))
+                    .and_then(|code| u32::from_str_radix(code, 16).ok())
+                    .and_then(char::from_u32)
+                    .ok_or(LexError::InvalidEscape { escape })?;
+
+                // Skip past the closing brace.
+                chars = rest[rest.find('}').unwrap() + 1..].chars();
+                code
+            }
+            _ => return Err(LexError::InvalidEscape { escape }),
+        });
+    }
+
+    Ok(value)
+}
+
+/// The radix of an integer literal and its digits without the prefix, if it has one.
+pub(crate) fn split_radix(literal: &str) -> (u32, &str) {
+    match literal.get(..2) {
+        Some("0x") => (16, &literal[2..]),
+        Some("0o") => (8, &literal[2..]),
+        Some("0b") => (2, &literal[2..]),
+        _ => (10, literal),
+    }
+}
+
+fn number_token(literal: String) -> Token {
+    match validate_number(&literal) {
+        Ok(()) => Token::from(literal),
+        Err(err) => Token::new(TokenKind::Malformed(err), literal),
+    }
+}
+
+/// Check the digits and separators of a numeric literal the lexer has read.
+fn validate_number(literal: &str) -> Result<(), LexError> {
+    let (radix, digits) = split_radix(literal);
+
+    if digits.is_empty() {
+        return Err(LexError::MissingDigits { radix });
+    }
+
+    if radix == 10
+        && let Some(index) = digits.find(['e', 'E'])
+    {
+        let exponent = digits[index + 1..].trim_start_matches(['+', '-']);
+
+        if !exponent.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(LexError::MissingExponent);
+        }
+    }
+
+    let chars = digits.chars().collect::<Vec<_>>();
+
+    for (index, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            let is_digit = |index: Option<usize>| {
+                index
+                    .and_then(|index| chars.get(index))
+                    .is_some_and(|c| c.is_digit(radix))
+            };
+
+            if !is_digit(index.checked_sub(1)) || !is_digit(Some(index + 1)) {
+                return Err(LexError::MisplacedSeparator);
+            }
+        } else if radix != 10 && !c.is_digit(radix) {
+            return Err(LexError::InvalidDigit { digit: c, radix });
+        }
+    }
+
+    Ok(())
+}
 
 #[derive(Debug, Clone)]
 pub struct Lexer {
@@ -18,6 +194,7 @@ impl Lexer {
     pub fn tokens(&self) -> Tokens<'_> {
         Tokens {
             chars: self.input.chars().peekable(),
+            position: Position::new(1, 1),
         }
     }
 }
@@ -25,51 +202,206 @@ impl Lexer {
 #[derive(Debug, Clone)]
 pub struct Tokens<'a> {
     chars: Peekable<Chars<'a>>,
+    position: Position,
+}
+
+impl Tokens<'_> {
+    /// Consume the next character, keeping track of where we are in the input.
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+
+        self.position = if c == '\n' {
+            Position::new(self.position.line() + 1, 1)
+        } else {
+            Position::new(self.position.line(), self.position.column() + 1)
+        };
+
+        Some(c)
+    }
+
+    /// Consume a run of ASCII digits and `_` separators onto `literal`.
+    fn digits(&mut self, literal: &mut String) {
+        while self
+            .chars
+            .peek()
+            .is_some_and(|&c| c.is_ascii_digit() || c == '_')
+        {
+            literal.push(self.bump().unwrap());
+        }
+    }
+
+    /// Skip whitespace and `//` comments.
+    fn skip_trivia(&mut self) {
+        loop {
+            match self.chars.peek().copied() {
+                Some(c) if c.is_whitespace() => _ = self.bump(),
+                Some('/') if self.lookahead(1) == Some('/') => {
+                    while self.chars.peek().is_some_and(|&c| c != '\n') {
+                        _ = self.bump();
+                    }
+                }
+                _ => break,
+            }
+        }
+    }
+
+    /// Read the rest of an identifier or keyword that starts with `first`. Identifiers follow
+    /// Unicode's XID rules and are normalized to NFC, so differently composed spellings of the
+    /// same name are the same name.
+    fn identifier(&mut self, first: char) -> Token {
+        let mut literal = first.to_string();
+
+        while self.chars.peek().is_some_and(|&c| c.is_xid_continue()) {
+            literal.push(self.bump().unwrap());
+        }
+
+        Token::from(literal.nfc().collect::<String>())
+    }
+
+    /// Read the rest of a string literal. A string ends at its closing quote and can't span
+    /// lines.
+    fn string(&mut self) -> Token {
+        let mut literal = String::from('"');
+
+        loop {
+            match self.chars.peek() {
+                None | Some('\n') => break,
+                Some('"') => {
+                    literal.push(self.bump().unwrap());
+                    break;
+                }
+                Some('\\') => {
+                    literal.push(self.bump().unwrap());
+
+                    if self.chars.peek().is_some_and(|&c| c != '\n') {
+                        literal.push(self.bump().unwrap());
+                    }
+                }
+                Some(_) => literal.push(self.bump().unwrap()),
+            }
+        }
+
+        match unescape(&literal) {
+            Ok(_) => Token::new(TokenKind::String, literal),
+            Err(err) => Token::new(TokenKind::Malformed(err), literal),
+        }
+    }
+
+    /// Read the rest of a numeric literal that starts with `first`.
+    fn number(&mut self, first: char) -> Token {
+        let mut literal = first.to_string();
+
+        if first == '0' && matches!(self.chars.peek(), Some('x' | 'o' | 'b')) {
+            // Take every letter too, so `0b102` or `0xfg` is one bad literal rather than a
+            // number followed by an identifier.
+            while self
+                .chars
+                .peek()
+                .is_some_and(|&c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                literal.push(self.bump().unwrap());
+            }
+
+            return number_token(literal);
+        }
+
+        self.digits(&mut literal);
+
+        // A fraction needs a digit after the point, so `1.` stays an integer and a dot.
+        if self.chars.peek() == Some(&'.') && self.lookahead(1).is_some_and(|c| c.is_ascii_digit())
+        {
+            literal.push(self.bump().unwrap());
+            self.digits(&mut literal);
+        }
+
+        // Take the `e` even without digits after it, so `1e` or `1.5e+` is one bad literal
+        // rather than a number followed by an identifier.
+        if self.chars.peek().is_some_and(|&c| c == 'e' || c == 'E') {
+            literal.push(self.bump().unwrap());
+
+            if matches!(self.chars.peek(), Some('+' | '-')) {
+                literal.push(self.bump().unwrap());
+            }
+
+            self.digits(&mut literal);
+        }
+
+        number_token(literal)
+    }
+
+    /// The character `n` places after the next one, without consuming anything.
+    fn lookahead(&self, n: usize) -> Option<char> {
+        self.chars.clone().nth(n)
+    }
 }
 
 impl<'a> Iterator for Tokens<'a> {
     type Item = Token;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while self.chars.peek().is_some_and(|&c| c.is_whitespace()) {
-            _ = self.chars.next();
-        }
+        self.skip_trivia();
 
-        let c = self.chars.next()?;
+        let start = self.position;
+        let c = self.bump()?;
 
-        match c {
-            'a'..='z' | 'A'..='Z' | '_' => {
+        let token = match c {
+            '0'..='9' => self.number(c),
+            '"' => self.string(),
+            _ if c == '_' || c.is_xid_start() => self.identifier(c),
+            '-' => {
                 let mut literal = c.to_string();
 
-                while self
-                    .chars
-                    .peek()
-                    .is_some_and(|&c| c.is_ascii_alphanumeric() || c == '_')
-                {
-                    literal.push(self.chars.next().unwrap());
+                if let Some(&'>') = self.chars.peek() {
+                    literal.push(self.bump().unwrap());
                 }
 
-                Some(Token::from(literal))
+                Token::from(literal)
             }
-            '0'..='9' => {
+            '=' | '!' | '<' | '>' => {
                 let mut literal = c.to_string();
 
-                while self.chars.peek().is_some_and(|&c| c.is_ascii_digit()) {
-                    literal.push(self.chars.next().unwrap());
+                match self.chars.peek() {
+                    Some(&'=') => literal.push(self.bump().unwrap()),
+                    Some(&'>') if c == '=' => literal.push(self.bump().unwrap()),
+                    // `<<` and `>>`
+                    Some(&next) if next == c && matches!(c, '<' | '>') => {
+                        literal.push(self.bump().unwrap());
+                    }
+                    _ => {}
                 }
 
-                Some(Token::from(literal))
+                Token::from(literal)
             }
-            '=' | '!' | '<' | '>' => {
+            // `**`, `&&`, `||` and `::`
+            '*' | '&' | '|' | ':' => {
                 let mut literal = c.to_string();
 
-                if let Some(&'=') = self.chars.peek() {
-                    literal.push(self.chars.next().unwrap());
+                if self.chars.peek() == Some(&c) {
+                    literal.push(self.bump().unwrap());
                 }
 
-                Some(Token::from(literal))
+                Token::from(literal)
             }
-            _ => Some(Token::from(c)),
-        }
+            '.' => {
+                let mut literal = c.to_string();
+
+                // `...`, but a lone `..` is two dots.
+                if self.chars.peek() == Some(&'.') && self.lookahead(1) == Some('.') {
+                    literal.push(self.bump().unwrap());
+                    literal.push(self.bump().unwrap());
+                }
+
+                Token::from(literal)
+            }
+            _ => match Token::from(c) {
+                token if token.kind() == TokenKind::Illegal => Token::new(
+                    TokenKind::Malformed(LexError::UnexpectedCharacter { character: c }),
+                    c.to_string(),
+                ),
+                token => token,
+            },
+        };
+
+        Some(token.with_span(Span::new(start, self.position)))
     }
 }
@@ -0,0 +1,26 @@
+pub mod annotation;
+pub mod checker;
+pub mod debugger;
+pub mod diagnostic;
+pub mod environment;
+pub mod evaluator;
+pub mod export;
+pub mod expression;
+pub mod json;
+pub mod lexer;
+pub mod lsp;
+pub mod macro_expansion;
+pub mod module;
+pub mod object;
+pub mod parser;
+pub mod pattern;
+pub mod precedence;
+pub mod prelude;
+pub mod program;
+pub mod resolver;
+pub mod runtime;
+pub mod statement;
+pub mod token;
+pub mod transport;
+pub mod types;
+pub mod visitor;
@@ -0,0 +1,269 @@
+use crate::checker;
+use crate::diagnostic::Diagnostic;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::prelude;
+use crate::program::Program;
+use crate::resolver::{BUILTINS, Binding, BindingKind, Resolution, resolve};
+use crate::token::{KEYWORDS, Position, Span};
+use crate::types::{Inference, infer};
+
+/// An open file, re-analysed from scratch every time its text changes.
+#[derive(Debug)]
+pub struct Document {
+    lines: Vec<String>,
+    program: Program,
+    resolution: Resolution,
+    inference: Inference,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionKind {
+    Keyword,
+    Function,
+    Variable,
+    Struct,
+    Enum,
+    Module,
+}
+
+impl Document {
+    pub fn new(text: String) -> Self {
+        let lines = text.split('\n').map(str::to_owned).collect();
+        let lexer = Lexer::new(text);
+        let mut parser = Parser::new(lexer.tokens());
+        let program = Program::parse(&mut parser).unwrap_or_default();
+        let resolution = resolve(&program);
+        let inference = infer(&program);
+
+        Self {
+            lines,
+            program,
+            resolution,
+            inference,
+        }
+    }
+
+    /// Syntax errors and everything `monkey check` would report, in source order.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = self
+            .program
+            .errors()
+            .iter()
+            .map(|err| Diagnostic::error(err.span(), err.to_string()))
+            .collect::<Vec<_>>();
+
+        diagnostics.extend(checker::check(&self.program));
+        diagnostics.sort_by_key(|diagnostic| diagnostic.span().start());
+        diagnostics
+    }
+
+    /// Convert a position to LSP's zero-based line and UTF-16 column.
+    pub fn to_utf16(&self, position: Position) -> (usize, usize) {
+        let line = position.line().saturating_sub(1);
+        let column = self.lines.get(line).map_or(0, |text| {
+            text.chars()
+                .take(position.column().saturating_sub(1))
+                .map(char::len_utf16)
+                .sum()
+        });
+
+        (line, column)
+    }
+
+    /// The inverse of `to_utf16`: the position of LSP's zero-based `line` and UTF-16 `character`.
+    pub fn position_at(&self, line: usize, character: usize) -> Position {
+        let mut units = 0;
+        let column = self.lines.get(line).map_or(0, |text| {
+            text.chars()
+                .take_while(|c| {
+                    units += c.len_utf16();
+                    units <= character
+                })
+                .count()
+        });
+
+        Position::new(line + 1, column + 1)
+    }
+
+    /// The binding declared or referred to by the identifier under `position`.
+    pub fn binding_at(&self, position: Position) -> Option<usize> {
+        let touches = |span: Span| span.start() <= position && position <= span.end();
+
+        self.resolution
+            .references()
+            .iter()
+            .find(|reference| touches(reference.span()))
+            .and_then(|reference| reference.binding())
+            .or_else(|| {
+                self.resolution
+                    .bindings()
+                    .iter()
+                    .position(|binding| touches(binding.span()))
+            })
+    }
+
+    pub fn binding(&self, index: usize) -> &Binding {
+        &self.resolution.bindings()[index]
+    }
+
+    pub fn definition(&self, position: Position) -> Option<Span> {
+        self.binding_at(position)
+            .map(|index| self.binding(index).span())
+    }
+
+    pub fn references(&self, position: Position, include_declaration: bool) -> Vec<Span> {
+        let Some(index) = self.binding_at(position) else {
+            return Vec::new();
+        };
+
+        let declaration = include_declaration.then(|| self.binding(index).span());
+        let references = self
+            .resolution
+            .references()
+            .iter()
+            .filter(|reference| reference.binding() == Some(index))
+            .map(|reference| reference.span());
+
+        declaration.into_iter().chain(references).collect()
+    }
+
+    /// Every `let`, `struct`, `enum` and `import` in the document, in source order, with its
+    /// inferred type when known.
+    pub fn symbols(&self) -> Vec<(&Binding, Option<String>)> {
+        self.resolution
+            .bindings()
+            .iter()
+            .filter(|binding| {
+                matches!(
+                    binding.kind(),
+                    BindingKind::Let
+                        | BindingKind::Struct
+                        | BindingKind::Enum
+                        | BindingKind::Module
+                )
+            })
+            .map(|binding| (binding, self.type_of(binding)))
+            .collect()
+    }
+
+    /// Markdown describing the binding under `position`.
+    pub fn hover(&self, position: Position) -> Option<String> {
+        let binding = self.binding(self.binding_at(position)?);
+
+        let mut contents = match binding.kind() {
+            BindingKind::Let | BindingKind::Struct | BindingKind::Enum | BindingKind::Module => {
+                format!("```monkey\n{}\n```", self.source(binding.definition()))
+            }
+            BindingKind::Parameter => format!("```monkey\n(parameter) {}\n```", binding.name()),
+            BindingKind::LoopVariable => {
+                format!("```monkey\n(loop variable) {}\n```", binding.name())
+            }
+            BindingKind::Pattern => {
+                format!("```monkey\n(pattern variable) {}\n```", binding.name())
+            }
+        };
+
+        if let Some(ty) = self.type_of(binding) {
+            contents.push_str(&format!("\n\n`{}: {ty}`", binding.name()));
+        }
+
+        Some(contents)
+    }
+
+    /// Keywords, builtins and every name visible at `position`, without duplicates.
+    pub fn completions(&self, position: Position) -> Vec<(String, CompletionKind)> {
+        let mut completions = KEYWORDS
+            .iter()
+            .map(|&keyword| (keyword.to_owned(), CompletionKind::Keyword))
+            .chain(
+                BUILTINS
+                    .iter()
+                    .chain(prelude::NAMES)
+                    .map(|&builtin| (builtin.to_owned(), CompletionKind::Function)),
+            )
+            .collect::<Vec<_>>();
+
+        let scopes = self.resolution.scopes();
+
+        // Scopes are recorded parent first, so the last one containing `position` is innermost.
+        let innermost = scopes
+            .iter()
+            .rposition(|scope| scope.span().contains(position))
+            .unwrap_or(0);
+
+        let mut visible = Vec::new();
+        let mut current = Some(innermost);
+
+        while let Some(index) = current {
+            visible.push(index);
+            current = scopes[index].parent();
+        }
+
+        // Within the function being edited, names must be bound before they are used; a nested
+        // function only runs later, so it can see everything in the scopes around it.
+        let function = scopes.get(innermost).map(|scope| scope.function());
+
+        for binding in self.resolution.bindings() {
+            let scope = &scopes[binding.scope()];
+
+            if !visible.contains(&binding.scope())
+                || (Some(scope.function()) == function && binding.span().end() > position)
+                || completions.iter().any(|(name, _)| name == binding.name())
+            {
+                continue;
+            }
+
+            let kind = match binding.kind() {
+                BindingKind::Struct => CompletionKind::Struct,
+                BindingKind::Enum => CompletionKind::Enum,
+                BindingKind::Module => CompletionKind::Module,
+                _ if binding.arity().is_some() => CompletionKind::Function,
+                _ => CompletionKind::Variable,
+            };
+
+            completions.push((binding.name().to_owned(), kind));
+        }
+
+        completions
+    }
+
+    fn type_of(&self, binding: &Binding) -> Option<String> {
+        self.inference
+            .bindings()
+            .iter()
+            .find(|typed| typed.span() == binding.span())
+            .map(|typed| typed.ty().to_string())
+    }
+
+    /// The text covered by `span`.
+    fn source(&self, span: Span) -> String {
+        let (start, end) = (span.start(), span.end());
+        let mut text = String::new();
+
+        for line in start.line()..=end.line() {
+            let Some(content) = self.lines.get(line - 1) else {
+                break;
+            };
+
+            let from = if line == start.line() {
+                start.column() - 1
+            } else {
+                0
+            };
+            let to = if line == end.line() {
+                end.column() - 1
+            } else {
+                usize::MAX
+            };
+
+            if line > start.line() {
+                text.push('\n');
+            }
+
+            text.extend(content.chars().skip(from).take(to.saturating_sub(from)));
+        }
+
+        text
+    }
+}
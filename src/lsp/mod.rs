@@ -0,0 +1,498 @@
+//! A Language Server Protocol implementation, speaking JSON-RPC over any reader and writer.
+//!
+//! Documents are synchronised in full on every change. Each change is parsed, resolved and
+//! type-checked, and every diagnostic is published back to the client. `serve` only needs a
+//! `BufRead` and a `Write`, so a scripted client can drive it through pipes.
+
+mod document;
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::json::Json;
+use crate::resolver::BindingKind;
+use crate::token::{Position, Span};
+use crate::transport::{read_message, write_message};
+
+use document::{CompletionKind, Document};
+
+/// JSON-RPC error codes used by the server.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Serve requests from `input` until the client sends `exit` or closes the stream.
+pub fn serve(mut input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    let mut server = Server::default();
+
+    while let Some(body) = read_message(&mut input)? {
+        let message = match Json::parse(&body) {
+            Ok(message) => message,
+            Err(err) => {
+                let response = error_response(Json::Null, PARSE_ERROR, &err.to_string());
+                write_message(&mut output, &response)?;
+                continue;
+            }
+        };
+
+        for outgoing in server.handle(&message) {
+            write_message(&mut output, &outgoing)?;
+        }
+
+        if server.exited {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+fn response(id: Json, result: Json) -> Json {
+    Json::object([
+        ("jsonrpc", Json::from("2.0")),
+        ("id", id),
+        ("result", result),
+    ])
+}
+
+fn error_response(id: Json, code: i64, message: &str) -> Json {
+    Json::object([
+        ("jsonrpc", Json::from("2.0")),
+        ("id", id),
+        (
+            "error",
+            Json::object([
+                ("code", Json::Integer(code)),
+                ("message", Json::from(message)),
+            ]),
+        ),
+    ])
+}
+
+fn notification(method: &str, params: Json) -> Json {
+    Json::object([
+        ("jsonrpc", Json::from("2.0")),
+        ("method", Json::from(method)),
+        ("params", params),
+    ])
+}
+
+#[derive(Debug, Default)]
+struct Server {
+    documents: HashMap<String, Document>,
+    shutdown: bool,
+    exited: bool,
+}
+
+impl Server {
+    /// Handle one incoming message, returning the messages to send back.
+    fn handle(&mut self, message: &Json) -> Vec<Json> {
+        let Some(method) = message.get("method").and_then(Json::as_str) else {
+            // Responses to requests we never send; nothing to do.
+            return Vec::new();
+        };
+
+        let params = message.get("params").unwrap_or(&Json::Null);
+
+        let Some(id) = message.get("id").cloned() else {
+            return self.notify(method, params);
+        };
+
+        if self.shutdown {
+            return vec![error_response(
+                id,
+                INVALID_REQUEST,
+                "the server is shutting down",
+            )];
+        }
+
+        let result = match method {
+            "initialize" => Some(capabilities()),
+            "shutdown" => {
+                self.shutdown = true;
+                Some(Json::Null)
+            }
+            "textDocument/definition" => self.definition(params),
+            "textDocument/references" => self.references(params),
+            "textDocument/documentSymbol" => self.symbols(params),
+            "textDocument/hover" => self.hover(params),
+            "textDocument/completion" => self.completion(params),
+            _ => {
+                let message = format!("unknown method {method:?}");
+                return vec![error_response(id, METHOD_NOT_FOUND, &message)];
+            }
+        };
+
+        match result {
+            Some(result) => vec![response(id, result)],
+            None => vec![error_response(id, INVALID_PARAMS, "invalid parameters")],
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: &Json) -> Vec<Json> {
+        let uri = params
+            .get("textDocument")
+            .and_then(|document| document.get("uri"))
+            .and_then(Json::as_str);
+
+        match (method, uri) {
+            ("exit", _) => {
+                self.exited = true;
+                Vec::new()
+            }
+            ("textDocument/didOpen", Some(uri)) => {
+                let Some(text) = params
+                    .get("textDocument")
+                    .and_then(|document| document.get("text"))
+                    .and_then(Json::as_str)
+                else {
+                    return Vec::new();
+                };
+
+                self.update(uri, text.to_owned())
+            }
+            ("textDocument/didChange", Some(uri)) => {
+                // Only full synchronisation is advertised, so the last change holds the whole text.
+                let Some(text) = params
+                    .get("contentChanges")
+                    .and_then(Json::as_array)
+                    .and_then(<[Json]>::last)
+                    .and_then(|change| change.get("text"))
+                    .and_then(Json::as_str)
+                else {
+                    return Vec::new();
+                };
+
+                self.update(uri, text.to_owned())
+            }
+            ("textDocument/didClose", Some(uri)) => {
+                _ = self.documents.remove(uri);
+                vec![publish_diagnostics(uri, Json::Array(Vec::new()))]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn update(&mut self, uri: &str, text: String) -> Vec<Json> {
+        let document = Document::new(text);
+        let diagnostics = document
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| self::diagnostic(&document, diagnostic))
+            .collect();
+
+        _ = self.documents.insert(uri.to_owned(), document);
+        vec![publish_diagnostics(uri, Json::Array(diagnostics))]
+    }
+
+    /// The document and cursor position a `TextDocumentPositionParams` refers to.
+    fn locate<'a>(&'a self, params: &'a Json) -> Option<(&'a str, &'a Document, Position)> {
+        let uri = params.get("textDocument")?.get("uri")?.as_str()?;
+        let document = self.documents.get(uri)?;
+        let position = params.get("position")?;
+        let line = usize::try_from(position.get("line")?.as_i64()?).ok()?;
+        let character = usize::try_from(position.get("character")?.as_i64()?).ok()?;
+
+        Some((uri, document, document.position_at(line, character)))
+    }
+
+    fn definition(&self, params: &Json) -> Option<Json> {
+        let (uri, document, position) = self.locate(params)?;
+
+        Some(
+            document
+                .definition(position)
+                .map_or(Json::Null, |span| location(uri, document, span)),
+        )
+    }
+
+    fn references(&self, params: &Json) -> Option<Json> {
+        let (uri, document, position) = self.locate(params)?;
+        let include_declaration = params
+            .get("context")
+            .and_then(|context| context.get("includeDeclaration"))
+            .and_then(Json::as_bool)
+            .unwrap_or(true);
+
+        let locations = document
+            .references(position, include_declaration)
+            .into_iter()
+            .map(|span| location(uri, document, span))
+            .collect();
+
+        Some(Json::Array(locations))
+    }
+
+    fn symbols(&self, params: &Json) -> Option<Json> {
+        let uri = params.get("textDocument")?.get("uri")?.as_str()?;
+        let document = self.documents.get(uri)?;
+
+        let symbols = document
+            .symbols()
+            .into_iter()
+            .map(|(binding, ty)| {
+                // SymbolKind: 12 is Function, 13 is Variable, 23 is Struct, 10 is Enum, 2 is
+                // Module.
+                let kind = match binding.kind() {
+                    BindingKind::Struct => 23,
+                    BindingKind::Enum => 10,
+                    BindingKind::Module => 2,
+                    _ if binding.arity().is_some() => 12,
+                    _ => 13,
+                };
+
+                Json::object([
+                    ("name", Json::from(binding.name())),
+                    ("detail", Json::from(ty)),
+                    ("kind", Json::Integer(kind)),
+                    ("range", range(document, binding.definition())),
+                    ("selectionRange", range(document, binding.span())),
+                ])
+            })
+            .collect();
+
+        Some(Json::Array(symbols))
+    }
+
+    fn hover(&self, params: &Json) -> Option<Json> {
+        let (_, document, position) = self.locate(params)?;
+
+        Some(document.hover(position).map_or(Json::Null, |contents| {
+            Json::object([(
+                "contents",
+                Json::object([
+                    ("kind", Json::from("markdown")),
+                    ("value", Json::from(contents)),
+                ]),
+            )])
+        }))
+    }
+
+    fn completion(&self, params: &Json) -> Option<Json> {
+        let (_, document, position) = self.locate(params)?;
+
+        let items = document
+            .completions(position)
+            .into_iter()
+            .map(|(label, kind)| {
+                // CompletionItemKind: 14 is Keyword, 3 is Function, 6 is Variable, 22 is Struct,
+                // 13 is Enum, 9 is Module.
+                let kind = match kind {
+                    CompletionKind::Keyword => 14,
+                    CompletionKind::Function => 3,
+                    CompletionKind::Variable => 6,
+                    CompletionKind::Struct => 22,
+                    CompletionKind::Enum => 13,
+                    CompletionKind::Module => 9,
+                };
+
+                Json::object([("label", Json::from(label)), ("kind", Json::Integer(kind))])
+            })
+            .collect();
+
+        Some(Json::Array(items))
+    }
+}
+
+fn capabilities() -> Json {
+    Json::object([
+        (
+            "capabilities",
+            Json::object([
+                // TextDocumentSyncKind 1: the client sends the full text on every change.
+                ("textDocumentSync", Json::Integer(1)),
+                ("definitionProvider", Json::from(true)),
+                ("referencesProvider", Json::from(true)),
+                ("documentSymbolProvider", Json::from(true)),
+                ("hoverProvider", Json::from(true)),
+                ("completionProvider", Json::object::<&str>([])),
+            ]),
+        ),
+        (
+            "serverInfo",
+            Json::object([
+                ("name", Json::from("monkey")),
+                ("version", Json::from(env!("CARGO_PKG_VERSION"))),
+            ]),
+        ),
+    ])
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Json) -> Json {
+    notification(
+        "textDocument/publishDiagnostics",
+        Json::object([("uri", Json::from(uri)), ("diagnostics", diagnostics)]),
+    )
+}
+
+fn diagnostic(document: &Document, diagnostic: &Diagnostic) -> Json {
+    // DiagnosticSeverity: 1 is Error, 2 is Warning.
+    let severity = match diagnostic.severity() {
+        Severity::Error => 1,
+        Severity::Warning => 2,
+    };
+
+    Json::object([
+        ("range", range(document, diagnostic.span())),
+        ("severity", Json::Integer(severity)),
+        ("source", Json::from("monkey")),
+        ("message", Json::from(diagnostic.message())),
+    ])
+}
+
+fn location(uri: &str, document: &Document, span: Span) -> Json {
+    Json::object([("uri", Json::from(uri)), ("range", range(document, span))])
+}
+
+fn range(document: &Document, span: Span) -> Json {
+    let position = |position| {
+        let (line, character) = document.to_utf16(position);
+        Json::object([
+            ("line", Json::from(line)),
+            ("character", Json::from(character)),
+        ])
+    };
+
+    Json::object([
+        ("start", position(span.start())),
+        ("end", position(span.end())),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    const URI: &str = "file:///test.monkey";
+
+    fn request(id: i64, method: &str, params: Json) -> Json {
+        Json::object([
+            ("jsonrpc", Json::from("2.0")),
+            ("id", Json::Integer(id)),
+            ("method", Json::from(method)),
+            ("params", params),
+        ])
+    }
+
+    fn at(line: i64, character: i64) -> Json {
+        Json::object([
+            ("textDocument", Json::object([("uri", Json::from(URI))])),
+            (
+                "position",
+                Json::object([
+                    ("line", Json::Integer(line)),
+                    ("character", Json::Integer(character)),
+                ]),
+            ),
+        ])
+    }
+
+    /// Feed `messages` to the server as a client would, returning what it sent back.
+    fn exchange(messages: &[Json]) -> Vec<Json> {
+        let mut input = Vec::new();
+
+        for message in messages {
+            write_message(&mut input, message).unwrap();
+        }
+
+        let mut output = Vec::new();
+        serve(Cursor::new(input), &mut output).unwrap();
+
+        let mut output = Cursor::new(output);
+        let mut responses = Vec::new();
+
+        while let Some(body) = read_message(&mut output).unwrap() {
+            responses.push(Json::parse(&body).unwrap());
+        }
+
+        responses
+    }
+
+    #[test]
+    fn a_scripted_session() {
+        let text = "let x = 1;\nlet y = x + x;\ny\n";
+        let open = Json::object([(
+            "textDocument",
+            Json::object([
+                ("uri", Json::from(URI)),
+                ("languageId", Json::from("monkey")),
+                ("version", Json::Integer(1)),
+                ("text", Json::from(text)),
+            ]),
+        )]);
+
+        let responses = exchange(&[
+            request(1, "initialize", Json::object::<&str>([])),
+            notification("textDocument/didOpen", open),
+            request(2, "textDocument/definition", at(1, 8)),
+            request(3, "textDocument/references", at(0, 4)),
+            request(4, "shutdown", Json::Null),
+            notification("exit", Json::Null),
+            // Never read: the server stops at `exit`.
+            request(5, "shutdown", Json::Null),
+        ]);
+
+        assert_eq!(responses.len(), 5);
+
+        assert_eq!(responses[0], response(Json::Integer(1), capabilities()));
+
+        assert_eq!(
+            responses[1],
+            publish_diagnostics(URI, Json::Array(Vec::new()))
+        );
+
+        assert_eq!(responses[2], response(Json::Integer(2), name(0, 4)));
+
+        assert_eq!(
+            responses[3],
+            response(
+                Json::Integer(3),
+                Json::Array(vec![name(0, 4), name(1, 8), name(1, 12)])
+            )
+        );
+
+        assert_eq!(responses[4], response(Json::Integer(4), Json::Null));
+    }
+
+    #[test]
+    fn requests_after_shutdown_are_refused() {
+        let responses = exchange(&[
+            request(1, "shutdown", Json::Null),
+            request(2, "textDocument/hover", at(0, 0)),
+        ]);
+
+        assert_eq!(
+            responses[1],
+            error_response(
+                Json::Integer(2),
+                INVALID_REQUEST,
+                "the server is shutting down"
+            )
+        );
+    }
+
+    /// The location of a one-character name in the test document.
+    fn name(line: i64, character: i64) -> Json {
+        let position = |character| {
+            Json::object([
+                ("line", Json::Integer(line)),
+                ("character", Json::Integer(character)),
+            ])
+        };
+
+        Json::object([
+            ("uri", Json::from(URI)),
+            (
+                "range",
+                Json::object([
+                    ("start", position(character)),
+                    ("end", position(character + 1)),
+                ]),
+            ),
+        ])
+    }
+}
@@ -0,0 +1,204 @@
+//! The macro-expansion phase, run between `Program::parse` and evaluation.
+//!
+//! `define_macros` pulls every top-level `let name = macro(...) { ... };` out of the program and
+//! binds it in a dedicated environment. `expand_macros` then replaces each call to one of those
+//! macros with the AST its body returns. Macros receive their arguments unevaluated, as `Quote`s,
+//! and build their result with `quote(...)`, splicing values in with `unquote(...)`.
+
+use std::collections::HashMap;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression::{Boolean, Call, Expression, FloatLiteral, IntegerLiteral, StringLiteral};
+use crate::object::{Error, Macro, Object, Quote};
+use crate::pattern::Pattern;
+use crate::program::Program;
+use crate::statement::Statement;
+use crate::token::{Span, Token, TokenKind};
+use crate::visitor::{MutVisitor, walk_expression_mut};
+
+/// Remove macro definitions from `program`, binding them in `env` instead.
+pub fn define_macros(program: &mut Program, env: &mut Environment) {
+    program.statements_mut().retain(|statement| {
+        let Statement::Let(ref inner) = *statement else {
+            return true;
+        };
+
+        let Expression::MacroLiteral(ref literal) = *inner.value() else {
+            return true;
+        };
+
+        let Pattern::Binding(ref name) = *inner.pattern() else {
+            return true;
+        };
+
+        let value = literal.evaluate(env);
+        let name = name.token().literal().to_owned();
+        _ = env.set(name, value);
+        false
+    });
+}
+
+/// Rewrite every call to a macro bound in `env` with the result of expanding it.
+pub fn expand_macros(program: &mut Program, env: &Environment) -> Result<(), Error> {
+    let mut expander = Expander { env, error: None };
+    expander.visit_program_mut(program);
+    expander.error.map_or(Ok(()), Err)
+}
+
+struct Expander<'a> {
+    env: &'a Environment,
+    error: Option<Error>,
+}
+
+impl MutVisitor for Expander<'_> {
+    fn visit_expression_mut(&mut self, node: &mut Expression) {
+        walk_expression_mut(self, node);
+
+        if self.error.is_some() {
+            return;
+        }
+
+        let Expression::Call(ref call) = *node else {
+            return;
+        };
+
+        let Expression::Identifier(ref identifier) = *call.function() else {
+            return;
+        };
+
+        let Some(Object::Macro(r#macro)) = self.env.get(identifier.token().literal()) else {
+            return;
+        };
+
+        match expand(call, &r#macro) {
+            Ok(expanded) => *node = expanded,
+            Err(err) => self.error = Some(err),
+        }
+    }
+}
+
+fn expand(call: &Call, r#macro: &Macro) -> Result<Expression, Error> {
+    if call.arguments().len() != r#macro.parameters().len() {
+        let message = format!(
+            "macro expects {} arguments, got {}",
+            r#macro.parameters().len(),
+            call.arguments().len()
+        );
+        return Err(Error::new(message));
+    }
+
+    let store = r#macro
+        .parameters()
+        .iter()
+        .zip(call.arguments())
+        .map(|(parameter, argument)| {
+            let key = parameter.token().literal().to_owned();
+            (key, Quote::new(argument.clone()).into())
+        })
+        .collect::<HashMap<_, _>>();
+
+    let outer = Box::new(r#macro.env().to_owned());
+    let mut env_extended = Environment::new(store, Some(outer));
+
+    match r#macro.body().evaluate(&mut env_extended) {
+        Object::Return(inner) => match inner.value() {
+            Object::Quote(inner) => Ok(inner.node().clone()),
+            value => Err(not_a_quote(value)),
+        },
+        Object::Quote(inner) => Ok(inner.into_node()),
+        Object::Error(err) => Err(err),
+        value => Err(not_a_quote(&value)),
+    }
+}
+
+fn not_a_quote(value: &Object) -> Error {
+    Error::new(format!("macros must return a quote, got {value}"))
+}
+
+/// Evaluate `quote(node)`: capture `node` without evaluating it, except for `unquote(...)` calls,
+/// which are evaluated in `env` and spliced back in.
+pub fn quote(node: &Expression, env: &mut Environment) -> Object {
+    if let Err(err) = env.runtime().allocate(1) {
+        return err.into();
+    }
+
+    let mut node = node.to_owned();
+    let mut unquoter = Unquoter { env, error: None };
+    unquoter.visit_expression_mut(&mut node);
+
+    match unquoter.error {
+        Some(err) => err.into(),
+        None => Quote::new(node).into(),
+    }
+}
+
+struct Unquoter<'a> {
+    env: &'a mut Environment,
+    error: Option<Error>,
+}
+
+impl MutVisitor for Unquoter<'_> {
+    fn visit_expression_mut(&mut self, node: &mut Expression) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let argument = match *node {
+            Expression::Call(ref call) if is_unquote_call(call) => &call.arguments()[0],
+            _ => return walk_expression_mut(self, node),
+        };
+
+        let span = node.span();
+
+        match argument.evaluate(self.env) {
+            Object::Error(err) => self.error = Some(err),
+            value => match into_node(value, span) {
+                Ok(expression) => *node = expression,
+                Err(err) => self.error = Some(err),
+            },
+        }
+    }
+}
+
+fn is_unquote_call(call: &Call) -> bool {
+    call.arguments().len() == 1
+        && matches!(
+            *call.function(),
+            Expression::Identifier(ref identifier) if identifier.token().literal() == "unquote"
+        )
+}
+
+/// Turn an evaluated value back into source, so it can be spliced into a quoted tree.
+fn into_node(value: Object, span: Span) -> Result<Expression, Error> {
+    match value {
+        Object::Integer(inner) => {
+            let token = Token::new(TokenKind::Integer, inner.value().to_string()).with_span(span);
+            Ok(IntegerLiteral::new(token, inner.value()).into())
+        }
+        Object::BigInteger(inner) => {
+            let token = Token::new(TokenKind::Integer, inner.to_string()).with_span(span);
+            Ok(IntegerLiteral::big(token, inner.value().clone()).into())
+        }
+        Object::Float(inner) => {
+            let token = Token::new(TokenKind::Float, inner.to_string()).with_span(span);
+            Ok(FloatLiteral::new(token, inner.value()).into())
+        }
+        Object::String(inner) => {
+            let literal = format!("{:?}", inner.value());
+            let token = Token::new(TokenKind::String, literal).with_span(span);
+            Ok(StringLiteral::new(token, inner.value().to_owned()).into())
+        }
+        Object::Boolean(inner) => {
+            let kind = if inner.value() {
+                TokenKind::True
+            } else {
+                TokenKind::False
+            };
+            let token = Token::new(kind, inner.value().to_string()).with_span(span);
+            Ok(Boolean::new(token, inner.value()).into())
+        }
+        Object::Quote(inner) => Ok(inner.into_node()),
+        value => Err(Error::new(format!("cannot unquote {value}"))),
+    }
+}
@@ -1,56 +1,336 @@
-mod environment;
-mod evaluator;
-mod expression;
-mod lexer;
-mod object;
-mod parser;
-mod precedence;
-mod program;
-mod statement;
-mod token;
-
 use std::io::{self, Write as _};
-use std::{env, fs};
+use std::path::Path;
+use std::process::ExitCode;
+use std::rc::Rc;
+use std::time::Duration;
+use std::{env, fs, thread};
 
 use tracing::level_filters::LevelFilter;
 use tracing_subscriber::prelude::*;
 
-use crate::environment::Environment;
-use crate::evaluator::Evaluate;
-use crate::lexer::Lexer;
-use crate::parser::Parser;
-use crate::program::Program;
+use monkey::checker;
+use monkey::debugger;
+use monkey::diagnostic::Severity;
+use monkey::environment::Environment;
+use monkey::evaluator::Evaluate;
+use monkey::export::Format;
+use monkey::lexer::Lexer;
+use monkey::lsp;
+use monkey::macro_expansion::{define_macros, expand_macros};
+use monkey::object::{ErrorKind, Object};
+use monkey::parser::Parser;
+use monkey::prelude;
+use monkey::program::Program;
+use monkey::runtime::{Limits, Overflow, Permissions};
+use monkey::types;
 
-fn main() {
+fn main() -> ExitCode {
     tracing_subscriber::registry()
-        .with(tracing_subscriber::fmt::layer().with_filter(LevelFilter::TRACE))
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(io::stderr)
+                .with_filter(LevelFilter::TRACE),
+        )
         .init();
 
-    if env::args().len() == 2 {
-        let input = fs::read_to_string(env::args().nth(1).unwrap()).expect("failed to read file");
-        let mut env = Environment::default();
-        let lexer = Lexer::new(input);
-        let mut parser = Parser::new(lexer.tokens());
-        let program = Program::parse(&mut parser).expect("failed to parse program");
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    // The evaluator recurses for every nested call, so give it room for `--max-depth` to be
+    // raised well past the default.
+    thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || match args.first().map(String::as_str) {
+            Some("ast") => ast(&args[1..]),
+            Some("check") => check(&args[1..]),
+            Some("lsp") if args.len() == 1 => language_server(),
+            Some("debug") => debug(&args[1..]),
+            _ => run(&args),
+        })
+        .expect("failed to start the interpreter thread")
+        .join()
+        .unwrap_or(ExitCode::FAILURE)
+}
+
+const STACK_SIZE: usize = 256 * 1024 * 1024;
+
+fn usage() -> ExitCode {
+    _ = writeln!(
+        io::stderr(),
+        "usage: monkey [--max-steps N] [--max-depth N] [--max-allocations N] [--max-size N] [--timeout MS] [--overflow error|promote] [--allow-read[=PATH,...]] [--allow-write[=PATH,...]] [--allow-env[=NAME,...]] [--allow-process] [FILE [ARG...]]\n       monkey ast [--format json|sexp|dot] FILE\n       monkey check [--types] FILE\n       monkey debug FILE | --dap\n       monkey lsp"
+    );
+    ExitCode::from(2)
+}
+
+fn print_errors(program: &Program) {
+    if !program.errors().is_empty() {
+        _ = writeln!(io::stderr(), "errors:");
+
+        for err in program.errors() {
+            _ = writeln!(io::stderr(), "  {err}");
+        }
+    }
+}
+
+fn run(args: &[String]) -> ExitCode {
+    let mut limits = Limits::default();
+    let mut overflow = Overflow::default();
+    let mut permissions = Permissions::default();
+    let mut path = None;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        // Everything after the file is passed to the script.
+        if !arg.starts_with("--") {
+            path = Some(arg);
+            break;
+        }
+
+        if let Some(grant) = arg.strip_prefix("--allow-") {
+            permissions = match allow(permissions, grant) {
+                Some(permissions) => permissions,
+                None => return usage(),
+            };
+
+            continue;
+        }
+
+        if arg == "--overflow" {
+            overflow = match args.next().map(String::as_str) {
+                Some("error") => Overflow::Error,
+                Some("promote") => Overflow::Promote,
+                _ => return usage(),
+            };
+
+            continue;
+        }
 
-        if !program.errors().is_empty() {
-            _ = writeln!(io::stderr(), "errors:");
+        let Some(value) = args.next().and_then(|value| value.parse::<u64>().ok()) else {
+            return usage();
+        };
 
-            for err in program.errors() {
-                _ = writeln!(io::stderr(), "  {err}");
+        limits = match arg.as_str() {
+            "--max-steps" => limits.with_steps(value),
+            "--max-depth" => limits.with_depth(value as usize),
+            "--max-allocations" => limits.with_allocations(value),
+            "--max-size" => limits.with_collection_size(value as usize),
+            "--timeout" => limits.with_time(Duration::from_millis(value)),
+            _ => return usage(),
+        };
+    }
+
+    let Some(path) = path else {
+        return repl(limits, overflow, permissions);
+    };
+
+    let input = fs::read_to_string(path).expect("failed to read file");
+    let mut env = prelude::environment(Rc::default());
+    env.runtime().set_limits(limits);
+    env.runtime().set_overflow(overflow);
+    env.runtime().set_permissions(permissions);
+    env.runtime().set_args(args.cloned().collect());
+    env.runtime().set_file(Path::new(path));
+    let mut macro_env = Environment::default();
+    let lexer = Lexer::new(input);
+    let mut parser = Parser::new(lexer.tokens());
+    let mut program = Program::parse(&mut parser).expect("failed to parse program");
+
+    print_errors(&program);
+
+    define_macros(&mut program, &mut macro_env);
+
+    if let Err(err) = expand_macros(&mut program, &macro_env) {
+        _ = writeln!(io::stdout(), "{}", Object::from(err));
+        return ExitCode::FAILURE;
+    }
+
+    let value = program.evaluate(&mut env);
+
+    if let Some(code) = exit_code(&value) {
+        return code;
+    }
+
+    _ = writeln!(io::stdout(), "{}", value);
+
+    ExitCode::SUCCESS
+}
+
+/// Add the capability granted by `--allow-NAME` or `--allow-NAME=A,B` to `permissions`.
+fn allow(permissions: Permissions, grant: &str) -> Option<Permissions> {
+    let (name, values) = match grant.split_once('=') {
+        Some((name, values)) => (
+            name,
+            Some(values.split(',').filter(|value| !value.is_empty())),
+        ),
+        None => (grant, None),
+    };
+
+    let permissions = match (name, values) {
+        ("read", None) => permissions.allow_read_all(),
+        ("read", Some(paths)) => paths.fold(permissions, Permissions::allow_read),
+        ("write", None) => permissions.allow_write_all(),
+        ("write", Some(paths)) => paths.fold(permissions, Permissions::allow_write),
+        ("env", None) => permissions.allow_env_all(),
+        ("env", Some(names)) => names.fold(permissions, Permissions::allow_env),
+        ("process", None) => permissions.allow_process(),
+        _ => return None,
+    };
+
+    Some(permissions)
+}
+
+/// The status to leave with if the program called `exit`.
+fn exit_code(value: &Object) -> Option<ExitCode> {
+    match *value {
+        Object::Error(ref err) => match err.kind() {
+            ErrorKind::Exit(code) => Some(ExitCode::from(code)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn ast(args: &[String]) -> ExitCode {
+    let mut format = Format::Json;
+    let mut path = None;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let value = match arg.as_str() {
+            "--format" => args.next().map(String::as_str),
+            _ if arg.starts_with("--format=") => arg.strip_prefix("--format="),
+            _ if path.is_none() => {
+                path = Some(arg);
+                continue;
             }
+            _ => return usage(),
+        };
+
+        match value.map(str::parse::<Format>) {
+            Some(Ok(value)) => format = value,
+            Some(Err(err)) => {
+                _ = writeln!(io::stderr(), "error: {err}");
+                return ExitCode::from(2);
+            }
+            None => return usage(),
         }
+    }
 
-        // _ = writeln!(io::stdout(), "AST: {:#?}", program.statements());
+    let Some(path) = path else {
+        return usage();
+    };
 
-        let value = program.evaluate(&mut env);
-        _ = writeln!(io::stdout(), "{}", value);
+    let input = fs::read_to_string(path).expect("failed to read file");
+    let lexer = Lexer::new(input);
+    let mut parser = Parser::new(lexer.tokens());
+    let program = Program::parse(&mut parser).expect("failed to parse program");
+
+    if format != Format::Json {
+        print_errors(&program);
+    }
+
+    _ = writeln!(io::stdout(), "{}", format.export(&program));
 
-        return;
+    if program.errors().is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
     }
+}
+
+fn check(args: &[String]) -> ExitCode {
+    let (path, show_types) = match *args {
+        [ref path] => (path, false),
+        [ref flag, ref path] if flag == "--types" => (path, true),
+        _ => return usage(),
+    };
+
+    let input = fs::read_to_string(path).expect("failed to read file");
+    let lexer = Lexer::new(input);
+    let mut parser = Parser::new(lexer.tokens());
+    let program = Program::parse(&mut parser).expect("failed to parse program");
+
+    let mut failed = false;
 
+    for err in program.errors() {
+        _ = writeln!(io::stderr(), "{path}:{}: error: {err}", err.span().start());
+        failed = true;
+    }
+
+    for diagnostic in checker::check(&program) {
+        _ = writeln!(io::stderr(), "{path}:{diagnostic}");
+        failed |= diagnostic.severity() == Severity::Error;
+    }
+
+    if show_types {
+        for binding in types::infer(&program).bindings() {
+            _ = writeln!(
+                io::stdout(),
+                "{}: {}: {}",
+                binding.span().start(),
+                binding.name(),
+                binding.ty()
+            );
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn debug(args: &[String]) -> ExitCode {
+    let result = match *args {
+        [ref flag] if flag == "--dap" => {
+            return match debugger::serve(io::stdin().lock(), io::stdout()) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(err) => {
+                    _ = writeln!(io::stderr(), "error: {err}");
+                    ExitCode::FAILURE
+                }
+            };
+        }
+        [ref path] => fs::read_to_string(path)
+            .map_err(|err| format!("{path}: {err}"))
+            .and_then(|input| debugger::debug(input, io::stdin().lock(), io::stdout())),
+        _ => return usage(),
+    };
+
+    match result {
+        Ok(value) => {
+            _ = writeln!(io::stdout(), "{value}");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            _ = writeln!(io::stderr(), "error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn language_server() -> ExitCode {
+    match lsp::serve(io::stdin().lock(), io::stdout().lock()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            _ = writeln!(io::stderr(), "error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn repl(limits: Limits, overflow: Overflow, permissions: Permissions) -> ExitCode {
     let mut buffer = String::new();
-    let mut env = Environment::default();
+    let mut env = prelude::environment(Rc::default());
+    env.runtime().set_limits(limits);
+    env.runtime().set_overflow(overflow);
+    env.runtime().set_permissions(permissions);
+    let mut macro_env = Environment::default();
+
+    // Ctrl-C cancels the running input instead of the whole session.
+    let interrupt = env.runtime().interrupt_handle();
+    signal_hook::flag::register(signal_hook::consts::SIGINT, interrupt.flag())
+        .expect("failed to handle Ctrl-C");
 
     _ = writeln!(
         io::stdout(),
@@ -62,25 +342,37 @@ fn main() {
 
         io::stdout().flush().expect("failed to print full prompt");
 
-        io::stdin()
+        let read = io::stdin()
             .read_line(&mut buffer)
             .expect("failed to read from stdin");
 
+        if read == 0 {
+            _ = writeln!(io::stdout());
+            return ExitCode::SUCCESS;
+        }
+
         let lexer = Lexer::new(buffer.to_owned());
         let mut parser = Parser::new(lexer.tokens());
-        let program = Program::parse(&mut parser).expect("failed to parse program");
-
-        if !program.errors().is_empty() {
-            _ = writeln!(io::stderr(), "errors:");
+        let mut program = Program::parse(&mut parser).expect("failed to parse program");
 
-            for err in program.errors() {
-                _ = writeln!(io::stderr(), "  {err}");
-            }
-        }
+        print_errors(&program);
 
         // _ = writeln!(io::stdout(), "AST: {:#?}", program.statements());
 
+        define_macros(&mut program, &mut macro_env);
+
+        if let Err(err) = expand_macros(&mut program, &macro_env) {
+            _ = writeln!(io::stdout(), "{}", Object::from(err));
+            buffer.clear();
+            continue;
+        }
+
         let value = program.evaluate(&mut env);
+
+        if let Some(code) = exit_code(&value) {
+            return code;
+        }
+
         _ = writeln!(io::stdout(), "{}", value);
 
         buffer.clear();
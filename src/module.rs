@@ -0,0 +1,146 @@
+//! Loading the files that `import` names.
+//!
+//! A path is found relative to the directory of the file importing it, or else in each directory
+//! listed in the `MONKEY_PATH` environment variable, in order. Each file is evaluated once per
+//! session, in a top-level environment of its own; importing it again gives the same module.
+//!
+//! Modules in the program's own directory, or in a `MONKEY_PATH` directory, can always be
+//! imported. Any other file needs the same permission as `read_file`, so that `import` can't be
+//! used to get around `--allow-read`.
+
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::{env, fs};
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::lexer::Lexer;
+use crate::macro_expansion::{define_macros, expand_macros};
+use crate::object::{Error, Module, NULL, Object};
+use crate::parser::{Parser, ParserError};
+use crate::prelude;
+use crate::program::Program;
+use crate::statement::Statement;
+
+/// The environment variable listing the directories searched for modules.
+pub const MONKEY_PATH: &str = "MONKEY_PATH";
+
+/// Evaluate the file that `path` names, unless it has been already, and return what it exports.
+pub fn import(path: &str, env: &Environment) -> Result<Module, Error> {
+    let runtime = env.runtime();
+    let files = runtime.files();
+
+    let file = find(Path::new(path), files.last().map(PathBuf::as_path))
+        .and_then(|file| file.canonicalize().ok())
+        .ok_or_else(|| Error::new(format!("cannot find module {path:?}")))?;
+
+    if let Some(module) = runtime.module(&file) {
+        return Ok(module);
+    }
+
+    if !is_trusted(&file, &files) {
+        runtime.permissions().check_read(&file)?;
+    }
+
+    if let Some(start) = files.iter().position(|other| *other == file) {
+        let cycle = files[start..]
+            .iter()
+            .chain([&file])
+            .map(|file| display(file))
+            .collect::<Vec<_>>();
+        return Err(Error::new(format!("import cycle: {}", cycle.join(" -> "))));
+    }
+
+    let input = fs::read_to_string(&file)
+        .map_err(|err| Error::new(format!("cannot read module {path:?}: {err}")))?;
+
+    let lexer = Lexer::new(input);
+    let mut parser = Parser::new(lexer.tokens());
+    let mut program = Program::parse(&mut parser).map_err(|err| located(&file, &err))?;
+
+    if let Some(err) = program.errors().first() {
+        return Err(located(&file, err));
+    }
+
+    let mut macro_env = Environment::default();
+    define_macros(&mut program, &mut macro_env);
+    expand_macros(&mut program, &macro_env)?;
+
+    let mut scope = prelude::environment(Rc::clone(runtime));
+    runtime.enter_file(file.clone());
+    let value = program.evaluate(&mut scope);
+    runtime.exit_file();
+
+    if let Object::Error(err) = value {
+        return Err(err);
+    }
+
+    let exports = program
+        .statements()
+        .iter()
+        .filter_map(|statement| match *statement {
+            Statement::Export(ref inner) => Some(inner.names()),
+            _ => None,
+        })
+        .flatten()
+        .map(|name| {
+            let name = name.token().literal();
+            (name.to_owned(), scope.get(name).unwrap_or(NULL))
+        })
+        .collect();
+
+    let module = Module::new(path.to_owned(), exports);
+    runtime.add_module(file, module.clone());
+    Ok(module)
+}
+
+/// Where the module `path` is, when imported from the file `importer`.
+fn find(path: &Path, importer: Option<&Path>) -> Option<PathBuf> {
+    let relative = match importer.and_then(Path::parent) {
+        Some(directory) => directory.join(path),
+        None => path.to_owned(),
+    };
+
+    if relative.is_file() || path.is_absolute() {
+        return Some(relative);
+    }
+
+    let directories = env::var_os(MONKEY_PATH)?;
+
+    env::split_paths(&directories)
+        .map(|directory| directory.join(path))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Whether the module at the canonical path `file` is in the directory of the program, or the
+/// current directory if there is no program file, or in a `MONKEY_PATH` directory.
+fn is_trusted(file: &Path, files: &[PathBuf]) -> bool {
+    let program = match files.first().and_then(|program| program.parent()) {
+        Some(directory) => directory.to_owned(),
+        None => env::current_dir().unwrap_or_default(),
+    };
+
+    let search = env::var_os(MONKEY_PATH)
+        .map(|directories| env::split_paths(&directories).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    [program]
+        .into_iter()
+        .chain(search)
+        .filter_map(|directory| directory.canonicalize().ok())
+        .any(|directory| file.starts_with(directory))
+}
+
+/// A syntax error in the module at `file`, pointing at where it is.
+fn located(file: &Path, err: &ParserError) -> Error {
+    Error::new(format!("{}:{}: {err}", display(file), err.span().start()))
+}
+
+/// `path` relative to the current directory when it is inside it, so messages stay short.
+fn display(path: &Path) -> String {
+    let current = env::current_dir().unwrap_or_default();
+    path.strip_prefix(current)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
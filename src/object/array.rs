@@ -0,0 +1,21 @@
+use std::rc::Rc;
+
+use crate::object::Object;
+
+/// An immutable list of values. Clones share the elements.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Array {
+    elements: Rc<[Object]>,
+}
+
+impl Array {
+    pub fn new(elements: Vec<Object>) -> Self {
+        Self {
+            elements: elements.into(),
+        }
+    }
+
+    pub fn elements(&self) -> &[Object] {
+        &self.elements
+    }
+}
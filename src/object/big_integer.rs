@@ -0,0 +1,42 @@
+use std::fmt;
+use std::rc::Rc;
+
+use num_bigint::{BigInt, Sign};
+use num_traits::ToPrimitive;
+
+use crate::object::{Integer, Object};
+
+/// An integer too large for an `Integer`. Only made when the runtime promotes on overflow, and
+/// never holds a value that would fit in an `i64`: build one through `Object::from(BigInt)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigInteger {
+    value: Rc<BigInt>,
+}
+
+impl BigInteger {
+    pub fn value(&self) -> &BigInt {
+        &self.value
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.value.sign() == Sign::Plus
+    }
+}
+
+impl fmt::Display for BigInteger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+/// An `Integer` when the value fits in one, otherwise a `BigInteger`.
+impl From<BigInt> for Object {
+    fn from(value: BigInt) -> Self {
+        match value.to_i64() {
+            Some(value) => Integer::new(value).into(),
+            None => Object::BigInteger(BigInteger {
+                value: Rc::new(value),
+            }),
+        }
+    }
+}
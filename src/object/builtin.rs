@@ -0,0 +1,62 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::object::{Error, Object};
+
+/// What a builtin does with its arguments, which there are always `arity` of.
+pub type NativeFunction = fn(&[Object], &mut Environment) -> Result<Object, Error>;
+
+/// A function written in Rust, such as `len` or `split` from the prelude.
+#[derive(Clone, Copy)]
+pub struct Builtin {
+    name: &'static str,
+    arity: usize,
+    function: NativeFunction,
+}
+
+impl Builtin {
+    pub const fn new(name: &'static str, arity: usize, function: NativeFunction) -> Self {
+        Self {
+            name,
+            arity,
+            function,
+        }
+    }
+
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub const fn arity(&self) -> usize {
+        self.arity
+    }
+
+    pub fn call(&self, arguments: &[Object], env: &mut Environment) -> Object {
+        if arguments.len() != self.arity {
+            let message = format!(
+                "wrong number of arguments: expected {}, got {}",
+                self.arity,
+                arguments.len()
+            );
+            return Error::new(message).into();
+        }
+
+        (self.function)(arguments, env).unwrap_or_else(Object::from)
+    }
+}
+
+impl fmt::Debug for Builtin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Builtin")
+            .field("name", &self.name)
+            .field("arity", &self.arity)
+            .finish()
+    }
+}
+
+/// Builtins are equal if they are the same one.
+impl PartialEq for Builtin {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
@@ -0,0 +1,19 @@
+/// Signals a `break` on its way out to the nearest loop.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Break;
+
+impl Break {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+/// Signals a `continue` on its way out to the nearest loop.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Continue;
+
+impl Continue {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
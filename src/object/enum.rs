@@ -0,0 +1,109 @@
+use std::rc::Rc;
+
+use crate::object::Object;
+
+#[derive(Debug)]
+struct Definition {
+    name: String,
+    /// Each variant's name and how many values it carries.
+    variants: Vec<(String, usize)>,
+}
+
+/// What an `enum` declaration evaluates to. Like `StructType`, two declarations are different
+/// types even if they look alike.
+#[derive(Debug, Clone)]
+pub struct EnumType {
+    definition: Rc<Definition>,
+}
+
+impl EnumType {
+    pub fn new(name: String, variants: Vec<(String, usize)>) -> Self {
+        Self {
+            definition: Rc::new(Definition { name, variants }),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.definition.name
+    }
+
+    pub fn variants(&self) -> &[(String, usize)] {
+        &self.definition.variants
+    }
+
+    pub fn variant_index(&self, variant: &str) -> Option<usize> {
+        self.variants().iter().position(|(name, _)| name == variant)
+    }
+}
+
+impl PartialEq for EnumType {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.definition, &other.definition)
+    }
+}
+
+/// A value of an enum: one of its variants and the values it carries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Enum {
+    ty: EnumType,
+    index: usize,
+    payload: Rc<[Object]>,
+}
+
+impl Enum {
+    pub fn new(ty: EnumType, index: usize, payload: Vec<Object>) -> Self {
+        assert_eq!(
+            ty.variants()[index].1,
+            payload.len(),
+            "a value for every field"
+        );
+
+        Self {
+            ty,
+            index,
+            payload: payload.into(),
+        }
+    }
+
+    pub const fn ty(&self) -> &EnumType {
+        &self.ty
+    }
+
+    pub fn variant(&self) -> &str {
+        &self.ty.variants()[self.index].0
+    }
+
+    pub fn payload(&self) -> &[Object] {
+        &self.payload
+    }
+}
+
+/// `Shape::Circle` for a variant that carries values: calling it builds the variant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constructor {
+    ty: EnumType,
+    index: usize,
+}
+
+impl Constructor {
+    pub const fn new(ty: EnumType, index: usize) -> Self {
+        Self { ty, index }
+    }
+
+    pub const fn ty(&self) -> &EnumType {
+        &self.ty
+    }
+
+    pub fn variant(&self) -> &str {
+        &self.ty.variants()[self.index].0
+    }
+
+    pub fn arity(&self) -> usize {
+        self.ty.variants()[self.index].1
+    }
+
+    /// Build the variant, once the caller has checked there are `arity` values.
+    pub fn construct(&self, payload: Vec<Object>) -> Enum {
+        Enum::new(self.ty.clone(), self.index, payload)
+    }
+}
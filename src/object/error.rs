@@ -1,11 +1,44 @@
+/// What kind of failure an `Error` reports, so embedders can tell a script's own mistakes apart
+/// from it running out of budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The script did something invalid, e.g. called a non-function.
+    Runtime,
+    /// The script ran more evaluation steps than `Limits::steps` allows.
+    StepLimit,
+    /// The call stack grew deeper than `Limits::depth` allows.
+    DepthLimit,
+    /// The script allocated more values than `Limits::allocations` allows.
+    AllocationLimit,
+    /// A collection grew larger than `Limits::collection_size` allows.
+    SizeLimit,
+    /// The script ran past the deadline set by `Limits::time`.
+    Deadline,
+    /// The evaluation was cancelled through `runtime::Interrupt`.
+    Interrupted,
+    /// The script tried something its `runtime::Permissions` don't allow.
+    PermissionDenied,
+    /// The script called `exit` with this status code.
+    Exit(u8),
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Error {
+    kind: ErrorKind,
     message: String,
 }
 
 impl Error {
     pub fn new(message: String) -> Self {
-        Self { message }
+        Self::with_kind(ErrorKind::Runtime, message)
+    }
+
+    pub const fn with_kind(kind: ErrorKind, message: String) -> Self {
+        Self { kind, message }
+    }
+
+    pub const fn kind(&self) -> ErrorKind {
+        self.kind
     }
 
     pub fn message(&self) -> &str {
@@ -0,0 +1,24 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Float {
+    value: f64,
+}
+
+impl Float {
+    pub const fn new(value: f64) -> Self {
+        Self { value }
+    }
+
+    pub const fn value(&self) -> f64 {
+        self.value
+    }
+}
+
+/// Always shows a fraction or an exponent (`1.0`, `1e-9`), so floats read back as floats. The
+/// special values print as `NaN`, `inf` and `-inf`.
+impl fmt::Display for Float {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.value)
+    }
+}
@@ -1,32 +1,51 @@
-use crate::{environment::Environment, expression::Identifier, statement::Block};
+use std::rc::Rc;
+
+use crate::{environment::Environment, pattern::Pattern, statement::Block};
 
 #[derive(Debug, Clone)]
 pub struct Function {
-    parameters: Vec<Identifier>,
-    body: Block,
+    parameters: Vec<Pattern>,
+    body: Rc<Block>,
     env: Environment,
 }
 
 impl Function {
-    pub fn new(parameters: Vec<Identifier>, body: Block, env: Environment) -> Self {
+    pub fn new(parameters: Vec<Pattern>, body: Block, env: Environment) -> Self {
         Self {
             parameters,
-            body,
+            body: Rc::new(body),
             env,
         }
     }
 
-    pub fn parameters(&self) -> &[Identifier] {
+    pub fn parameters(&self) -> &[Pattern] {
         &self.parameters
     }
 
-    pub const fn body(&self) -> &Block {
+    pub fn body(&self) -> &Block {
         &self.body
     }
 
     pub const fn env(&self) -> &Environment {
         &self.env
     }
+
+    /// The same function, closing over `env` instead.
+    pub(crate) fn with_env(&self, env: Environment) -> Self {
+        Self {
+            parameters: self.parameters.clone(),
+            body: Rc::clone(&self.body),
+            env,
+        }
+    }
+
+    /// Whether this takes its receiver as a first parameter named `self`.
+    pub fn is_method(&self) -> bool {
+        matches!(
+            self.parameters.first(),
+            Some(Pattern::Binding(name)) if name.token().literal() == "self"
+        )
+    }
 }
 
 impl PartialEq for Function {
@@ -1,5 +1,3 @@
-use crate::expression;
-
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Integer {
     value: i64,
@@ -14,11 +12,3 @@ impl Integer {
         self.value
     }
 }
-
-impl From<expression::IntegerLiteral> for Integer {
-    fn from(value: expression::IntegerLiteral) -> Self {
-        Self {
-            value: value.value(),
-        }
-    }
-}
@@ -0,0 +1,49 @@
+use std::rc::Rc;
+
+use crate::environment::Environment;
+use crate::expression::Identifier;
+use crate::statement::Block;
+
+#[derive(Debug, Clone)]
+pub struct Macro {
+    parameters: Vec<Identifier>,
+    body: Rc<Block>,
+    env: Environment,
+}
+
+impl Macro {
+    pub fn new(parameters: Vec<Identifier>, body: Block, env: Environment) -> Self {
+        Self {
+            parameters,
+            body: Rc::new(body),
+            env,
+        }
+    }
+
+    pub fn parameters(&self) -> &[Identifier] {
+        &self.parameters
+    }
+
+    pub fn body(&self) -> &Block {
+        &self.body
+    }
+
+    pub const fn env(&self) -> &Environment {
+        &self.env
+    }
+
+    /// The same macro, closing over `env` instead.
+    pub(crate) fn with_env(&self, env: Environment) -> Self {
+        Self {
+            parameters: self.parameters.clone(),
+            body: Rc::clone(&self.body),
+            env,
+        }
+    }
+}
+
+impl PartialEq for Macro {
+    fn eq(&self, _: &Self) -> bool {
+        false
+    }
+}
@@ -1,18 +1,42 @@
+mod array;
+mod big_integer;
 mod boolean;
+mod builtin;
+mod control;
+mod r#enum;
 mod error;
+mod float;
 mod function;
 mod integer;
+mod r#macro;
+mod module;
 mod null;
+mod quote;
 mod r#return;
+mod string;
+mod r#struct;
 
 use std::fmt;
 
+use crate::token::{Token, TokenKind};
+
+pub use array::Array;
+pub use big_integer::BigInteger;
 pub use boolean::Boolean;
-pub use error::Error;
+pub use builtin::{Builtin, NativeFunction};
+pub use control::{Break, Continue};
+pub use r#enum::{Constructor, Enum, EnumType};
+pub use error::{Error, ErrorKind};
+pub use float::Float;
 pub use function::Function;
 pub use integer::Integer;
+pub use r#macro::Macro;
+pub use module::Module;
 pub use null::Null;
+pub use quote::Quote;
 pub use r#return::Return;
+pub use string::Str;
+pub use r#struct::{Struct, StructType};
 
 pub const NULL: Object = Object::Null(Null::new());
 pub const TRUE: Boolean = Boolean::new(true);
@@ -20,17 +44,40 @@ pub const FALSE: Boolean = Boolean::new(false);
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Object {
+    Array(Array),
+    BigInteger(BigInteger),
     Boolean(Boolean),
+    Break(Break),
+    Builtin(Builtin),
+    Constructor(Constructor),
+    Continue(Continue),
+    Enum(Enum),
+    EnumType(EnumType),
     Error(Error),
+    Float(Float),
     Function(Function),
     Integer(Integer),
+    Macro(Macro),
+    Module(Module),
     Null(Null),
+    Quote(Quote),
     Return(Return),
+    String(Str),
+    Struct(Struct),
+    StructType(StructType),
 }
 
 impl Object {
     pub fn as_boolean(&self) -> Boolean {
         match *self {
+            Self::Array(_) => FALSE,
+            Self::BigInteger(ref inner) => {
+                if inner.is_positive() {
+                    TRUE
+                } else {
+                    FALSE
+                }
+            }
             Self::Boolean(ref inner) => {
                 if inner.value() {
                     TRUE
@@ -38,7 +85,17 @@ impl Object {
                     FALSE
                 }
             }
+            Self::Break(_) | Self::Continue(_) => FALSE,
+            Self::Builtin(_) => FALSE,
+            Self::Constructor(_) | Self::Enum(_) | Self::EnumType(_) => FALSE,
             Self::Error(_) => FALSE,
+            Self::Float(ref inner) => {
+                if inner.value() > 0.0 {
+                    TRUE
+                } else {
+                    FALSE
+                }
+            }
             Self::Function(_) => FALSE,
             Self::Integer(ref inner) => {
                 if inner.value() > 0 {
@@ -47,8 +104,27 @@ impl Object {
                     FALSE
                 }
             }
+            Self::Macro(_) | Self::Module(_) => FALSE,
             Self::Null(_) => FALSE,
+            Self::Quote(_) => FALSE,
             Self::Return(ref inner) => inner.value().as_boolean(),
+            Self::String(ref inner) => {
+                if inner.value().is_empty() {
+                    FALSE
+                } else {
+                    TRUE
+                }
+            }
+            Self::Struct(_) | Self::StructType(_) => FALSE,
+        }
+    }
+
+    /// Display as part of a collection, where strings are quoted so `["a, b"]` can't be
+    /// mistaken for `["a", "b"]`.
+    fn fmt_element(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::String(ref inner) => write!(f, "{:?}", inner.value()),
+            _ => fmt::Display::fmt(self, f),
         }
     }
 }
@@ -56,28 +132,146 @@ impl Object {
 impl fmt::Display for Object {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
+            Self::Array(ref inner) => {
+                f.write_str("[")?;
+
+                for (index, element) in inner.elements().iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+
+                    element.fmt_element(f)?;
+                }
+
+                f.write_str("]")
+            }
+            Self::BigInteger(ref inner) => inner.fmt(f),
             Self::Boolean(ref inner) => inner.value().fmt(f),
+            Self::Break(_) => "break".fmt(f),
+            Self::Builtin(ref inner) => write!(f, "builtin {}", inner.name()),
+            Self::Constructor(ref inner) => {
+                write!(f, "constructor {}::{}", inner.ty().name(), inner.variant())
+            }
+            Self::Continue(_) => "continue".fmt(f),
+            Self::Enum(ref inner) => {
+                write!(f, "{}::{}", inner.ty().name(), inner.variant())?;
+
+                if inner.payload().is_empty() {
+                    return Ok(());
+                }
+
+                f.write_str("(")?;
+
+                for (index, value) in inner.payload().iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+
+                    value.fmt_element(f)?;
+                }
+
+                f.write_str(")")
+            }
+            Self::EnumType(ref inner) => write!(f, "enum {}", inner.name()),
             Self::Error(ref inner) => write!(f, "ERROR: {}", inner.message()),
+            Self::Float(ref inner) => inner.fmt(f),
             Self::Function(_) => "function".fmt(f),
             Self::Integer(ref inner) => inner.value().fmt(f),
+            Self::Macro(_) => "macro".fmt(f),
+            Self::Module(ref inner) => write!(f, "module {:?}", inner.path()),
             Self::Null(_) => "null".fmt(f),
+            Self::Quote(ref inner) => write!(f, "QUOTE({})", inner.node()),
             Self::Return(ref inner) => (*inner.value()).fmt(f),
+            Self::String(ref inner) => inner.value().fmt(f),
+            Self::Struct(ref inner) => {
+                write!(f, "{} {{", inner.ty().name())?;
+
+                for (index, (field, value)) in
+                    inner.ty().fields().iter().zip(inner.values()).enumerate()
+                {
+                    f.write_str(if index > 0 { ", " } else { " " })?;
+
+                    // Keys read from JSON can be any string, which only reads back quoted.
+                    if Token::from(field.clone()).kind() == TokenKind::Identifier {
+                        write!(f, "{field}: ")?;
+                    } else {
+                        write!(f, "{field:?}: ")?;
+                    }
+
+                    value.fmt_element(f)?;
+                }
+
+                if inner.values().is_empty() {
+                    f.write_str("}")
+                } else {
+                    f.write_str(" }")
+                }
+            }
+            Self::StructType(ref inner) => write!(f, "struct {}", inner.name()),
         }
     }
 }
 
+impl From<Array> for Object {
+    fn from(value: Array) -> Self {
+        Object::Array(value)
+    }
+}
+
 impl From<Boolean> for Object {
     fn from(value: Boolean) -> Self {
         Object::Boolean(value)
     }
 }
 
+impl From<Break> for Object {
+    fn from(value: Break) -> Self {
+        Object::Break(value)
+    }
+}
+
+impl From<Builtin> for Object {
+    fn from(value: Builtin) -> Self {
+        Object::Builtin(value)
+    }
+}
+
+impl From<Constructor> for Object {
+    fn from(value: Constructor) -> Self {
+        Object::Constructor(value)
+    }
+}
+
+impl From<Continue> for Object {
+    fn from(value: Continue) -> Self {
+        Object::Continue(value)
+    }
+}
+
+impl From<Enum> for Object {
+    fn from(value: Enum) -> Self {
+        Object::Enum(value)
+    }
+}
+
+impl From<EnumType> for Object {
+    fn from(value: EnumType) -> Self {
+        Object::EnumType(value)
+    }
+}
+
 impl From<Error> for Object {
     fn from(value: Error) -> Self {
         Object::Error(value)
     }
 }
 
+impl From<Float> for Object {
+    fn from(value: Float) -> Self {
+        Object::Float(value)
+    }
+}
+
 impl From<Function> for Object {
     fn from(value: Function) -> Self {
         Object::Function(value)
@@ -90,14 +284,50 @@ impl From<Integer> for Object {
     }
 }
 
+impl From<Macro> for Object {
+    fn from(value: Macro) -> Self {
+        Object::Macro(value)
+    }
+}
+
+impl From<Module> for Object {
+    fn from(value: Module) -> Self {
+        Object::Module(value)
+    }
+}
+
 impl From<Null> for Object {
     fn from(value: Null) -> Self {
         Object::Null(value)
     }
 }
 
+impl From<Quote> for Object {
+    fn from(value: Quote) -> Self {
+        Object::Quote(value)
+    }
+}
+
 impl From<Return> for Object {
     fn from(value: Return) -> Self {
         Object::Return(value)
     }
 }
+
+impl From<Str> for Object {
+    fn from(value: Str) -> Self {
+        Object::String(value)
+    }
+}
+
+impl From<Struct> for Object {
+    fn from(value: Struct) -> Self {
+        Object::Struct(value)
+    }
+}
+
+impl From<StructType> for Object {
+    fn from(value: StructType) -> Self {
+        Object::StructType(value)
+    }
+}
@@ -0,0 +1,46 @@
+use std::rc::Rc;
+
+use crate::object::Object;
+
+#[derive(Debug)]
+struct Definition {
+    path: String,
+    exports: Vec<(String, Object)>,
+}
+
+/// What `import` binds: the values a file exported, read with `math.add`. Each file is evaluated
+/// once, so importing it again gives the same module.
+#[derive(Debug, Clone)]
+pub struct Module {
+    definition: Rc<Definition>,
+}
+
+impl Module {
+    pub fn new(path: String, exports: Vec<(String, Object)>) -> Self {
+        Self {
+            definition: Rc::new(Definition { path, exports }),
+        }
+    }
+
+    /// The path the module was first imported with.
+    pub fn path(&self) -> &str {
+        &self.definition.path
+    }
+
+    pub fn exports(&self) -> &[(String, Object)] {
+        &self.definition.exports
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Object> {
+        self.exports()
+            .iter()
+            .find(|(export, _)| export == name)
+            .map(|(_, value)| value)
+    }
+}
+
+impl PartialEq for Module {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.definition, &other.definition)
+    }
+}
@@ -0,0 +1,30 @@
+use crate::expression::Expression;
+
+/// An unevaluated piece of AST, produced by `quote(...)` and consumed by macros.
+#[derive(Debug, Clone)]
+pub struct Quote {
+    node: Box<Expression>,
+}
+
+impl Quote {
+    pub fn new(node: Expression) -> Self {
+        Self {
+            node: Box::new(node),
+        }
+    }
+
+    pub fn node(&self) -> &Expression {
+        &self.node
+    }
+
+    pub fn into_node(self) -> Expression {
+        *self.node
+    }
+}
+
+impl PartialEq for Quote {
+    fn eq(&self, other: &Self) -> bool {
+        // The fully parenthesised source is unique to the shape of the tree, and ignores spans.
+        format!("{:#}", self.node) == format!("{:#}", other.node)
+    }
+}
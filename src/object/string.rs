@@ -0,0 +1,19 @@
+use std::rc::Rc;
+
+/// An immutable string. Clones share the text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Str {
+    value: Rc<str>,
+}
+
+impl Str {
+    pub fn new(value: impl Into<Rc<str>>) -> Self {
+        Self {
+            value: value.into(),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
@@ -0,0 +1,110 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::object::{Function, Object};
+
+#[derive(Debug)]
+struct Definition {
+    name: String,
+    fields: Vec<String>,
+    /// Functions added by `impl` blocks, in the order they were defined.
+    methods: RefCell<Vec<(String, Function)>>,
+}
+
+/// What a `struct` declaration evaluates to. Clones are the same type; two declarations are
+/// different types even if they look alike.
+#[derive(Debug, Clone)]
+pub struct StructType {
+    definition: Rc<Definition>,
+}
+
+impl StructType {
+    pub fn new(name: String, fields: Vec<String>) -> Self {
+        Self {
+            definition: Rc::new(Definition {
+                name,
+                fields,
+                methods: RefCell::default(),
+            }),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.definition.name
+    }
+
+    pub fn fields(&self) -> &[String] {
+        &self.definition.fields
+    }
+
+    pub fn field_index(&self, field: &str) -> Option<usize> {
+        self.fields().iter().position(|name| name == field)
+    }
+
+    /// The function named `name` from an `impl` block. It is a method if its first parameter is
+    /// `self`, and an associated function otherwise.
+    pub fn method(&self, name: &str) -> Option<Function> {
+        self.definition
+            .methods
+            .borrow()
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, function)| function.clone())
+    }
+
+    /// Add `function` as `name`, or return `false` if there already is one.
+    pub fn define_method(&self, name: String, function: Function) -> bool {
+        if self.method(&name).is_some() {
+            return false;
+        }
+
+        self.definition.methods.borrow_mut().push((name, function));
+        true
+    }
+}
+
+impl PartialEq for StructType {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.definition, &other.definition)
+    }
+}
+
+/// An instance of a struct, with a value for every field in declaration order. Instances are
+/// values: updating a field makes a new instance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Struct {
+    ty: StructType,
+    values: Rc<[Object]>,
+}
+
+impl Struct {
+    pub fn new(ty: StructType, values: Vec<Object>) -> Self {
+        assert_eq!(ty.fields().len(), values.len(), "a value for every field");
+
+        Self {
+            ty,
+            values: values.into(),
+        }
+    }
+
+    pub const fn ty(&self) -> &StructType {
+        &self.ty
+    }
+
+    pub fn values(&self) -> &[Object] {
+        &self.values
+    }
+
+    pub fn get(&self, field: &str) -> Option<&Object> {
+        self.ty.field_index(field).map(|index| &self.values[index])
+    }
+
+    /// A copy with `field` set to `value`, or `None` if there is no such field.
+    pub fn with(&self, field: &str, value: Object) -> Option<Self> {
+        let index = self.ty.field_index(field)?;
+        let mut values = self.values.to_vec();
+        values[index] = value;
+
+        Some(Self::new(self.ty.clone(), values))
+    }
+}
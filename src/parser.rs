@@ -2,12 +2,12 @@ use std::collections::HashMap;
 use std::{error, fmt};
 
 use crate::expression::{
-    Boolean, Call, Expression, FunctionLiteral, Grouped, Identifier, If, Infix, IntegerLiteral,
-    Prefix,
+    ArrayLiteral, Boolean, Call, Expression, FieldAccess, FloatLiteral, FunctionLiteral, Grouped,
+    Identifier, If, Index, Infix, IntegerLiteral, MacroLiteral, Match, Path, Prefix, StringLiteral,
 };
-use crate::lexer::Tokens;
+use crate::lexer::{LexError, Tokens};
 use crate::statement::Statement;
-use crate::token::{Token, TokenKind};
+use crate::token::{Span, Token, TokenKind};
 
 pub trait Parse<S: Into<Statement> = Self> {
     fn parse(parser: &mut Parser<'_>) -> Result<S, ParserError>;
@@ -28,13 +28,19 @@ lazy_static::lazy_static! {
     pub static ref PREFIX: HashMap<TokenKind, ParsePrefixFn> = HashMap::from([
         (TokenKind::Identifier, Identifier::parse_prefix as ParsePrefixFn),
         (TokenKind::Integer, IntegerLiteral::parse_prefix as ParsePrefixFn),
+        (TokenKind::Float, FloatLiteral::parse_prefix as ParsePrefixFn),
+        (TokenKind::String, StringLiteral::parse_prefix as ParsePrefixFn),
         (TokenKind::Bang, Prefix::parse_prefix as ParsePrefixFn),
         (TokenKind::Minus, Prefix::parse_prefix as ParsePrefixFn),
+        (TokenKind::Tilde, Prefix::parse_prefix as ParsePrefixFn),
         (TokenKind::True, Boolean::parse_prefix as ParsePrefixFn),
         (TokenKind::False, Boolean::parse_prefix as ParsePrefixFn),
         (TokenKind::LParenthesis, Grouped::parse_prefix as ParsePrefixFn),
         (TokenKind::If, If::parse_prefix as ParsePrefixFn),
+        (TokenKind::Match, Match::parse_prefix as ParsePrefixFn),
         (TokenKind::Function, FunctionLiteral::parse_prefix as ParsePrefixFn),
+        (TokenKind::Macro, MacroLiteral::parse_prefix as ParsePrefixFn),
+        (TokenKind::LBracket, ArrayLiteral::parse_prefix as ParsePrefixFn),
     ]);
 
     pub static ref INFIX: HashMap<TokenKind, ParseInfixFn> = HashMap::from([
@@ -46,15 +52,46 @@ lazy_static::lazy_static! {
         (TokenKind::NotEqual, Infix::parse_infix as ParseInfixFn),
         (TokenKind::LessThan, Infix::parse_infix as ParseInfixFn),
         (TokenKind::GreaterThan, Infix::parse_infix as ParseInfixFn),
+        (TokenKind::LessThanOrEqual, Infix::parse_infix as ParseInfixFn),
+        (TokenKind::GreaterThanOrEqual, Infix::parse_infix as ParseInfixFn),
+        (TokenKind::Percent, Infix::parse_infix as ParseInfixFn),
+        (TokenKind::DoubleAsterisk, Infix::parse_infix as ParseInfixFn),
+        (TokenKind::Ampersand, Infix::parse_infix as ParseInfixFn),
+        (TokenKind::Pipe, Infix::parse_infix as ParseInfixFn),
+        (TokenKind::Caret, Infix::parse_infix as ParseInfixFn),
+        (TokenKind::ShiftLeft, Infix::parse_infix as ParseInfixFn),
+        (TokenKind::ShiftRight, Infix::parse_infix as ParseInfixFn),
+        (TokenKind::DoubleAmpersand, Infix::parse_infix as ParseInfixFn),
+        (TokenKind::DoublePipe, Infix::parse_infix as ParseInfixFn),
         (TokenKind::LParenthesis, Call::parse_infix as ParseInfixFn),
+        (TokenKind::LBracket, Index::parse_infix as ParseInfixFn),
+        (TokenKind::Dot, FieldAccess::parse_infix as ParseInfixFn),
+        (TokenKind::DoubleColon, Path::parse_infix as ParseInfixFn),
     ]);
 }
 
+/// How deeply expressions, blocks, patterns and type annotations can nest, so that a hostile
+/// program can't overflow the stack of the parser, or of anything that walks the tree after it.
+pub const MAX_DEPTH: usize = 256;
+
 #[derive(Debug)]
 pub struct Parser<'a> {
     tokens: Tokens<'a>,
     token: Option<Token>,
     peek: Option<Token>,
+    /// Where the most recently consumed token ended; errors at the end of input point here.
+    end: Span,
+    errors: Vec<ParserError>,
+    /// How many loops enclose the current token within the innermost function.
+    loops: usize,
+    /// How many blocks enclose the current token.
+    blocks: usize,
+    /// Whether `Name {` starts a struct literal here. See `without_struct_literals`.
+    struct_literals: bool,
+    /// How many nodes of the tree enclose the current token. See `nested`.
+    depth: usize,
+    /// Whether the input nested too deeply, and the rest of it was skipped.
+    abandoned: bool,
 }
 
 impl<'a> Parser<'a> {
@@ -66,6 +103,13 @@ impl<'a> Parser<'a> {
             tokens,
             token,
             peek,
+            end: Span::default(),
+            errors: Vec::new(),
+            loops: 0,
+            blocks: 0,
+            struct_literals: true,
+            depth: 0,
+            abandoned: false,
         }
     }
 }
@@ -75,7 +119,15 @@ impl Parser<'_> {
         self.token.as_ref()
     }
 
+    pub const fn peek(&self) -> Option<&Token> {
+        self.peek.as_ref()
+    }
+
     pub fn advance(&mut self) {
+        if let Some(ref token) = self.token {
+            self.end = Span::new(token.span().end(), token.span().end());
+        }
+
         self.token = self.peek.take();
         self.peek = self.tokens.next();
     }
@@ -84,29 +136,251 @@ impl Parser<'_> {
         let actual = self.token.as_ref().map(|token| token.kind());
 
         if actual == Some(expected) {
-            let token = self.token.take().unwrap();
+            let token = self.token.clone().unwrap();
             self.advance();
             Ok(token)
+        } else if let Some(TokenKind::Malformed(error)) = actual {
+            let span = self.span();
+            self.advance();
+            Err(ParserError::Lex { error, span })
         } else {
-            Err(ParserError::WrongTokenKind { expected, actual })
+            Err(ParserError::WrongTokenKind {
+                expected,
+                actual,
+                span: self.span(),
+            })
+        }
+    }
+
+    /// The span of the current token, or an empty span just past the last one at the end of input.
+    pub fn span(&self) -> Span {
+        self.token.as_ref().map_or(self.end, Token::span)
+    }
+
+    /// Record an error that was recovered from, so it is still reported with the program.
+    pub fn report(&mut self, err: ParserError) {
+        // After giving up on input that nests too deeply, every enclosing node fails to find
+        // its closing token; those errors would only bury the real one.
+        if !self.abandoned {
+            self.errors.push(err);
+        }
+    }
+
+    /// Run `parse` one level deeper in the tree. Past `MAX_DEPTH` levels this reports
+    /// `ParserError::TooDeep` and skips the rest of the input instead.
+    pub fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, ParserError>,
+    ) -> Result<T, ParserError> {
+        if self.depth == MAX_DEPTH {
+            let err = ParserError::TooDeep { span: self.span() };
+            self.report(err.clone());
+            self.abandoned = true;
+
+            while self.token.is_some() {
+                self.advance();
+            }
+
+            return Err(err);
         }
+
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Whether `break` and `continue` are allowed here.
+    pub const fn in_loop(&self) -> bool {
+        self.loops > 0
+    }
+
+    /// Run `parse` inside the body of a loop.
+    pub fn in_loop_body<T>(&mut self, parse: impl FnOnce(&mut Self) -> T) -> T {
+        self.loops += 1;
+        let result = parse(self);
+        self.loops -= 1;
+        result
+    }
+
+    /// Run `parse` inside the body of a function, where the loops around it can't be broken out
+    /// of.
+    pub fn in_function_body<T>(&mut self, parse: impl FnOnce(&mut Self) -> T) -> T {
+        let loops = std::mem::take(&mut self.loops);
+        let result = parse(self);
+        self.loops = loops;
+        result
+    }
+
+    /// Whether the current token is outside every block, where a file's exports are declared.
+    pub const fn at_top_level(&self) -> bool {
+        self.blocks == 0
+    }
+
+    /// Run `parse` inside a block.
+    pub fn in_block<T>(&mut self, parse: impl FnOnce(&mut Self) -> T) -> T {
+        self.blocks += 1;
+        let result = parse(self);
+        self.blocks -= 1;
+        result
+    }
+
+    pub const fn struct_literals_allowed(&self) -> bool {
+        self.struct_literals
+    }
+
+    /// Run `parse` on the condition of an `if` or `while`, where a `{` after a name opens the
+    /// body rather than a struct literal: `if (ok) { ... }` and `if ok { ... }` mean the same.
+    pub fn without_struct_literals<T>(&mut self, parse: impl FnOnce(&mut Self) -> T) -> T {
+        let struct_literals = std::mem::replace(&mut self.struct_literals, false);
+        let result = parse(self);
+        self.struct_literals = struct_literals;
+        result
+    }
+
+    /// Run `parse` between brackets, where struct literals are unambiguous again.
+    pub fn with_struct_literals<T>(&mut self, parse: impl FnOnce(&mut Self) -> T) -> T {
+        let struct_literals = std::mem::replace(&mut self.struct_literals, true);
+        let result = parse(self);
+        self.struct_literals = struct_literals;
+        result
+    }
+
+    /// Take every error reported so far.
+    pub fn take_errors(&mut self) -> Vec<ParserError> {
+        std::mem::take(&mut self.errors)
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ParserError {
     WrongTokenKind {
         expected: TokenKind,
         actual: Option<TokenKind>,
+        span: Span,
+    },
+    /// The token cannot start an expression.
+    UnexpectedToken {
+        actual: TokenKind,
+        span: Span,
+    },
+    UnexpectedEof {
+        span: Span,
+    },
+    InvalidInteger {
+        literal: String,
+        span: Span,
+    },
+    /// A float literal too large to represent.
+    InvalidFloat {
+        literal: String,
+        span: Span,
+    },
+    /// Source the lexer couldn't read.
+    Lex {
+        error: LexError,
+        span: Span,
+    },
+    /// `break` or `continue` that isn't inside a loop.
+    OutsideLoop {
+        keyword: TokenKind,
+        span: Span,
+    },
+    /// A field named twice in a `struct` declaration or struct literal.
+    DuplicateField {
+        field: String,
+        span: Span,
+    },
+    /// A variant named twice in an `enum` declaration.
+    DuplicateVariant {
+        variant: String,
+        span: Span,
+    },
+    /// A name bound twice by the same pattern.
+    DuplicateBinding {
+        name: String,
+        span: Span,
+    },
+    /// The token cannot start a pattern.
+    InvalidPattern {
+        span: Span,
+    },
+    /// A `...rest` pattern that isn't the last element of an array pattern.
+    MisplacedRest {
+        span: Span,
     },
+    /// `export` inside a block, or before something other than `let`, `struct` or `enum`.
+    InvalidExport {
+        span: Span,
+    },
+    /// The left of `=` is not a name or a field of one.
+    InvalidAssignTarget {
+        span: Span,
+    },
+    /// Expressions, blocks, patterns or annotations nested more than `MAX_DEPTH` deep.
+    TooDeep {
+        span: Span,
+    },
+}
+
+impl ParserError {
+    pub const fn span(&self) -> Span {
+        match *self {
+            Self::WrongTokenKind { span, .. }
+            | Self::UnexpectedToken { span, .. }
+            | Self::UnexpectedEof { span }
+            | Self::InvalidInteger { span, .. }
+            | Self::InvalidFloat { span, .. }
+            | Self::Lex { span, .. }
+            | Self::OutsideLoop { span, .. }
+            | Self::DuplicateField { span, .. }
+            | Self::DuplicateVariant { span, .. }
+            | Self::DuplicateBinding { span, .. }
+            | Self::InvalidPattern { span }
+            | Self::MisplacedRest { span }
+            | Self::InvalidExport { span }
+            | Self::InvalidAssignTarget { span }
+            | Self::TooDeep { span } => span,
+        }
+    }
 }
 
 impl fmt::Display for ParserError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
-            Self::WrongTokenKind { expected, actual } => {
+            Self::WrongTokenKind {
+                expected, actual, ..
+            } => {
                 write!(f, "expected {expected:?}, got {actual:?}")
             }
+            Self::UnexpectedToken { actual, .. } => {
+                write!(f, "expected an expression, got {actual:?}")
+            }
+            Self::UnexpectedEof { .. } => f.write_str("unexpected end of input"),
+            Self::InvalidInteger { ref literal, .. } => {
+                write!(f, "integer literal {literal} is out of range")
+            }
+            Self::InvalidFloat { ref literal, .. } => {
+                write!(f, "float literal {literal} is out of range")
+            }
+            Self::Lex { error, .. } => error.fmt(f),
+            Self::OutsideLoop { keyword, .. } => write!(f, "`{keyword}` outside of a loop"),
+            Self::DuplicateField { ref field, .. } => write!(f, "field {field:?} given twice"),
+            Self::DuplicateVariant { ref variant, .. } => {
+                write!(f, "variant {variant:?} given twice")
+            }
+            Self::DuplicateBinding { ref name, .. } => {
+                write!(f, "{name:?} is bound more than once in the same pattern")
+            }
+            Self::InvalidPattern { .. } => f.write_str("expected a pattern"),
+            Self::MisplacedRest { .. } => {
+                f.write_str("`...` can only come last in an array pattern")
+            }
+            Self::InvalidExport { .. } => {
+                f.write_str("only a top-level `let`, `struct` or `enum` can be exported")
+            }
+            Self::InvalidAssignTarget { .. } => f.write_str("can only assign to a name or a field"),
+            Self::TooDeep { .. } => write!(f, "nested more than {MAX_DEPTH} deep"),
         }
     }
 }
@@ -114,7 +388,21 @@ impl fmt::Display for ParserError {
 impl error::Error for ParserError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match *self {
-            Self::WrongTokenKind { .. } => None,
+            Self::WrongTokenKind { .. }
+            | Self::UnexpectedToken { .. }
+            | Self::UnexpectedEof { .. }
+            | Self::InvalidInteger { .. }
+            | Self::InvalidFloat { .. }
+            | Self::OutsideLoop { .. }
+            | Self::DuplicateField { .. }
+            | Self::DuplicateVariant { .. }
+            | Self::DuplicateBinding { .. }
+            | Self::InvalidPattern { .. }
+            | Self::MisplacedRest { .. }
+            | Self::InvalidExport { .. }
+            | Self::InvalidAssignTarget { .. }
+            | Self::TooDeep { .. } => None,
+            Self::Lex { ref error, .. } => Some(error),
         }
     }
 }
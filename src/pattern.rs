@@ -0,0 +1,678 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression::{Expression, Identifier, qualified};
+use crate::object::{Array, Error, Object};
+use crate::parser::{Parser, ParserError};
+use crate::precedence::Precedence;
+use crate::token::{Span, Token, TokenKind};
+
+/// What a `match` arm compares its value against, or what a `let` or parameter takes apart,
+/// binding names to the parts.
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    /// `_`, which matches anything and binds nothing.
+    Wildcard(Token),
+    /// A name, which matches anything and binds it.
+    Binding(Identifier),
+    /// `1`, `-2.5`, `"a"` or `true`, which matches an equal value.
+    Literal(Box<Expression>),
+    /// `[a, b]`, which matches an array of exactly that length, or `[a, b, ...rest]`, which
+    /// matches one at least that long and matches `rest` against an array of what is left.
+    Array {
+        token: Token,
+        elements: Vec<Pattern>,
+        rest: Option<Box<Pattern>>,
+        rbracket: Token,
+    },
+    /// `Point { x: 0, y }`, which matches a `Point` whose listed fields match, or `{ x, y }`,
+    /// which matches any struct that has them. A field on its own binds its value to its name.
+    /// `m.Point { ... }` names a struct that module `m` exports.
+    Struct {
+        module: Option<Identifier>,
+        name: Option<Identifier>,
+        lbrace: Token,
+        fields: Vec<(Identifier, Pattern)>,
+        rbrace: Token,
+    },
+    /// `Shape::Circle(r)` or `Shape::Empty`, which matches that variant if its values match.
+    /// `m.Shape::Empty` names an enum that module `m` exports.
+    Variant {
+        module: Option<Identifier>,
+        name: Identifier,
+        token: Token,
+        variant: Identifier,
+        arguments: Vec<Pattern>,
+        end: Span,
+    },
+}
+
+/// Why a value doesn't fit a pattern.
+enum Failure {
+    /// The value has another shape, as described.
+    Mismatch(String),
+    /// The pattern itself is wrong, like naming a variant that doesn't exist.
+    Error(Object),
+}
+
+impl Pattern {
+    pub fn parse(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
+        let pattern = Self::parse_inner(parser)?;
+
+        let mut bindings = Vec::new();
+        pattern.collect_bindings(&mut bindings);
+
+        for (index, binding) in bindings.iter().enumerate() {
+            let name = binding.token().literal();
+
+            if bindings[..index]
+                .iter()
+                .any(|other| other.token().literal() == name)
+            {
+                parser.report(ParserError::DuplicateBinding {
+                    name: name.to_owned(),
+                    span: binding.span(),
+                });
+            }
+        }
+
+        Ok(pattern)
+    }
+
+    fn parse_inner(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
+        parser.nested(Self::parse_nested)
+    }
+
+    fn parse_nested(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
+        let Some(token) = parser.token() else {
+            return Err(ParserError::UnexpectedEof {
+                span: parser.span(),
+            });
+        };
+
+        match token.kind() {
+            TokenKind::Identifier if token.literal() == "_" => {
+                let token = parser.expect_token_with_kind(TokenKind::Identifier)?;
+                Ok(Self::Wildcard(token))
+            }
+            TokenKind::Identifier => {
+                let name = parser
+                    .expect_token_with_kind(TokenKind::Identifier)
+                    .map(Identifier::new)?;
+
+                match parser.token().map(Token::kind) {
+                    Some(TokenKind::DoubleColon) => Self::parse_variant(parser, None, name),
+                    Some(TokenKind::LBrace) => Self::parse_struct(parser, None, Some(name)),
+                    Some(TokenKind::Dot) => Self::parse_qualified(parser, name),
+                    _ => Ok(Self::Binding(name)),
+                }
+            }
+            TokenKind::LBrace => Self::parse_struct(parser, None, None),
+            TokenKind::LBracket => Self::parse_array(parser),
+            TokenKind::Integer
+            | TokenKind::Float
+            | TokenKind::String
+            | TokenKind::True
+            | TokenKind::False
+            | TokenKind::Minus => {
+                let span = token.span();
+                let literal = Expression::parse(parser, Precedence::Index)?;
+
+                match literal {
+                    Expression::Prefix(ref inner)
+                        if !matches!(
+                            *inner.right(),
+                            Expression::IntegerLiteral(_) | Expression::FloatLiteral(_)
+                        ) =>
+                    {
+                        Err(ParserError::InvalidPattern { span })
+                    }
+                    _ => Ok(Self::Literal(Box::new(literal))),
+                }
+            }
+            _ => {
+                let span = token.span();
+                parser.advance();
+                Err(ParserError::InvalidPattern { span })
+            }
+        }
+    }
+
+    fn parse_array(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::LBracket)?;
+
+        let mut elements = Vec::new();
+        let mut rest = None;
+        let mut misplaced = false;
+
+        while parser
+            .token()
+            .is_some_and(|token| token.kind() != TokenKind::RBracket)
+        {
+            if parser
+                .token()
+                .is_some_and(|token| token.kind() == TokenKind::Ellipsis)
+            {
+                let ellipsis = parser.expect_token_with_kind(TokenKind::Ellipsis)?;
+                let pattern = Self::parse_inner(parser)?;
+                let span = ellipsis.span().to(pattern.span());
+
+                if rest.is_some() {
+                    parser.report(ParserError::MisplacedRest { span });
+                } else {
+                    rest = Some((span, Box::new(pattern)));
+                }
+            } else {
+                let element = Self::parse_inner(parser)?;
+
+                // Only the first element after the rest is reported, and none are kept.
+                match rest {
+                    Some((span, _)) if !misplaced => {
+                        parser.report(ParserError::MisplacedRest { span });
+                        misplaced = true;
+                    }
+                    Some(_) => {}
+                    None => elements.push(element),
+                }
+            }
+
+            if parser
+                .token()
+                .is_none_or(|token| token.kind() != TokenKind::Comma)
+            {
+                break;
+            }
+
+            parser.advance();
+        }
+
+        let rbracket = parser.expect_token_with_kind(TokenKind::RBracket)?;
+
+        Ok(Self::Array {
+            token,
+            elements,
+            rest: rest.map(|(_, rest)| rest),
+            rbracket,
+        })
+    }
+
+    /// Parse the rest of `module.Name { ... }` or `module.Name::Variant`.
+    fn parse_qualified(parser: &mut Parser<'_>, module: Identifier) -> Result<Self, ParserError> {
+        _ = parser.expect_token_with_kind(TokenKind::Dot)?;
+
+        let name = parser
+            .expect_token_with_kind(TokenKind::Identifier)
+            .map(Identifier::new)?;
+
+        match parser.token().map(Token::kind) {
+            Some(TokenKind::DoubleColon) => Self::parse_variant(parser, Some(module), name),
+            Some(TokenKind::LBrace) => Self::parse_struct(parser, Some(module), Some(name)),
+            _ => Err(ParserError::InvalidPattern {
+                span: module.span().to(name.span()),
+            }),
+        }
+    }
+
+    fn parse_variant(
+        parser: &mut Parser<'_>,
+        module: Option<Identifier>,
+        name: Identifier,
+    ) -> Result<Self, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::DoubleColon)?;
+
+        let variant = parser
+            .expect_token_with_kind(TokenKind::Identifier)
+            .map(Identifier::new)?;
+
+        let mut arguments = Vec::new();
+        let mut end = variant.span();
+
+        if parser
+            .token()
+            .is_some_and(|token| token.kind() == TokenKind::LParenthesis)
+        {
+            parser.advance();
+            arguments = Self::parse_list(parser, TokenKind::RParenthesis)?;
+            end = parser
+                .expect_token_with_kind(TokenKind::RParenthesis)?
+                .span();
+        }
+
+        Ok(Self::Variant {
+            module,
+            name,
+            token,
+            variant,
+            arguments,
+            end,
+        })
+    }
+
+    fn parse_struct(
+        parser: &mut Parser<'_>,
+        module: Option<Identifier>,
+        name: Option<Identifier>,
+    ) -> Result<Self, ParserError> {
+        let lbrace = parser.expect_token_with_kind(TokenKind::LBrace)?;
+
+        let mut fields = Vec::<(Identifier, Pattern)>::new();
+
+        while parser
+            .token()
+            .is_some_and(|token| token.kind() != TokenKind::RBrace)
+        {
+            let field = parser
+                .expect_token_with_kind(TokenKind::Identifier)
+                .map(Identifier::new)?;
+
+            if fields
+                .iter()
+                .any(|(other, _)| other.token().literal() == field.token().literal())
+            {
+                parser.report(ParserError::DuplicateField {
+                    field: field.token().literal().to_owned(),
+                    span: field.span(),
+                });
+            }
+
+            let pattern = if parser
+                .token()
+                .is_some_and(|token| token.kind() == TokenKind::Colon)
+            {
+                parser.advance();
+                Self::parse_inner(parser)?
+            } else {
+                Self::Binding(field.clone())
+            };
+
+            fields.push((field, pattern));
+
+            if parser
+                .token()
+                .is_none_or(|token| token.kind() != TokenKind::Comma)
+            {
+                break;
+            }
+
+            parser.advance();
+        }
+
+        let rbrace = parser.expect_token_with_kind(TokenKind::RBrace)?;
+        Ok(Self::Struct {
+            module,
+            name,
+            lbrace,
+            fields,
+            rbrace,
+        })
+    }
+
+    /// Parse comma-separated patterns up to, but not including, `end`.
+    fn parse_list(parser: &mut Parser<'_>, end: TokenKind) -> Result<Vec<Self>, ParserError> {
+        let mut patterns = Vec::new();
+
+        while parser.token().is_some_and(|token| token.kind() != end) {
+            patterns.push(Self::parse_inner(parser)?);
+
+            if parser
+                .token()
+                .is_none_or(|token| token.kind() != TokenKind::Comma)
+            {
+                break;
+            }
+
+            parser.advance();
+        }
+
+        Ok(patterns)
+    }
+
+    pub fn span(&self) -> Span {
+        match *self {
+            Self::Wildcard(ref token) => token.span(),
+            Self::Binding(ref name) => name.span(),
+            Self::Literal(ref literal) => literal.span(),
+            Self::Array {
+                ref token,
+                ref rbracket,
+                ..
+            } => token.span().to(rbracket.span()),
+            Self::Struct {
+                ref module,
+                ref name,
+                ref lbrace,
+                ref rbrace,
+                ..
+            } => module
+                .as_ref()
+                .or(name.as_ref())
+                .map_or(lbrace.span(), Identifier::span)
+                .to(rbrace.span()),
+            Self::Variant {
+                ref module,
+                ref name,
+                end,
+                ..
+            } => module.as_ref().unwrap_or(name).span().to(end),
+        }
+    }
+
+    /// Whether this matches every value, so any arms after it are only reached through guards.
+    pub const fn is_irrefutable(&self) -> bool {
+        matches!(*self, Self::Wildcard(_) | Self::Binding(_))
+    }
+
+    /// Every name this binds, in source order.
+    pub fn bindings(&self) -> Vec<&Identifier> {
+        let mut bindings = Vec::new();
+        self.collect_bindings(&mut bindings);
+        bindings
+    }
+
+    fn collect_bindings<'a>(&'a self, bindings: &mut Vec<&'a Identifier>) {
+        match *self {
+            Self::Wildcard(_) | Self::Literal(_) => {}
+            Self::Binding(ref name) => bindings.push(name),
+            Self::Array {
+                ref elements,
+                ref rest,
+                ..
+            } => {
+                for element in elements.iter().chain(rest.as_deref()) {
+                    element.collect_bindings(bindings);
+                }
+            }
+            Self::Struct { ref fields, .. } => {
+                for (_, pattern) in fields {
+                    pattern.collect_bindings(bindings);
+                }
+            }
+            Self::Variant { ref arguments, .. } => {
+                for argument in arguments {
+                    argument.collect_bindings(bindings);
+                }
+            }
+        }
+    }
+
+    /// Whether `value` matches, binding names in `env` as it goes. Names may be left bound when
+    /// the match fails part way, so `env` should be a scope of its own. Returns an error object
+    /// if the pattern itself is wrong, like naming a variant that doesn't exist.
+    pub fn matches(&self, value: &Object, env: &mut Environment) -> Result<bool, Object> {
+        match self.bind(value, env) {
+            Ok(()) => Ok(true),
+            Err(Failure::Mismatch(_)) => Ok(false),
+            Err(Failure::Error(err)) => Err(err),
+        }
+    }
+
+    /// Take `value` apart, binding names in `env`. A value that doesn't match is an error saying
+    /// why, like an array of the wrong length or a struct without a field.
+    pub fn destructure(&self, value: &Object, env: &mut Environment) -> Result<(), Object> {
+        self.bind(value, env).map_err(|failure| match failure {
+            Failure::Mismatch(message) => Error::new(message).into(),
+            Failure::Error(err) => err,
+        })
+    }
+
+    fn bind(&self, value: &Object, env: &mut Environment) -> Result<(), Failure> {
+        match *self {
+            Self::Wildcard(_) => Ok(()),
+            Self::Binding(ref name) => {
+                _ = env.set(name.token().literal().to_owned(), value.clone());
+                Ok(())
+            }
+            Self::Literal(ref literal) => match literal.evaluate(env) {
+                Object::Error(err) => Err(Failure::Error(err.into())),
+                evaluated if evaluated == *value => Ok(()),
+                _ => Err(Failure::Mismatch(format!(
+                    "expected {literal}, found {value}"
+                ))),
+            },
+            Self::Array {
+                ref elements,
+                ref rest,
+                ..
+            } => {
+                let Object::Array(ref array) = *value else {
+                    let message = format!("expected an array, found {value}");
+                    return Err(Failure::Mismatch(message));
+                };
+
+                let (expected, found) = (elements.len(), array.elements().len());
+
+                if found != expected && (rest.is_none() || found < expected) {
+                    let message = format!(
+                        "expected an array of {}{expected} element{}, but it has {found}",
+                        if rest.is_some() { "at least " } else { "" },
+                        if expected == 1 { "" } else { "s" },
+                    );
+                    return Err(Failure::Mismatch(message));
+                }
+
+                for (pattern, element) in elements.iter().zip(array.elements()) {
+                    pattern.bind(element, env)?;
+                }
+
+                let Some(ref rest) = *rest else {
+                    return Ok(());
+                };
+
+                if let Err(err) = env.runtime().allocate(1) {
+                    return Err(Failure::Error(err.into()));
+                }
+
+                let remaining = Array::new(array.elements()[expected..].to_vec());
+                rest.bind(&remaining.into(), env)
+            }
+            Self::Struct {
+                ref module,
+                ref name,
+                ref fields,
+                ..
+            } => {
+                let ty = match name
+                    .as_ref()
+                    .map(|name| (name, qualified(module.as_ref(), name, env)))
+                {
+                    None => None,
+                    Some((_, Object::StructType(ty))) => Some(ty),
+                    Some((_, Object::Error(err))) => return Err(Failure::Error(err.into())),
+                    Some((name, _)) => {
+                        let message = format!("{:?} is not a struct", name.token().literal());
+                        return Err(Failure::Error(Error::new(message).into()));
+                    }
+                };
+
+                if let Some(ref ty) = ty {
+                    for (field, _) in fields {
+                        let field = field.token().literal();
+
+                        if ty.field_index(field).is_none() {
+                            let message = format!("struct {} has no field {field:?}", ty.name());
+                            return Err(Failure::Error(Error::new(message).into()));
+                        }
+                    }
+                }
+
+                let instance = match *value {
+                    Object::Struct(ref instance)
+                        if ty.as_ref().is_none_or(|ty| instance.ty() == ty) =>
+                    {
+                        instance
+                    }
+                    _ => {
+                        let message = match ty {
+                            Some(ref ty) => format!("expected a {}, found {value}", ty.name()),
+                            None => format!("expected a struct, found {value}"),
+                        };
+                        return Err(Failure::Mismatch(message));
+                    }
+                };
+
+                for (field, pattern) in fields {
+                    let field = field.token().literal();
+
+                    let Some(value) = instance.get(field) else {
+                        let message =
+                            format!("struct {} has no field {field:?}", instance.ty().name());
+                        return Err(Failure::Mismatch(message));
+                    };
+
+                    pattern.bind(value, env)?;
+                }
+
+                Ok(())
+            }
+            Self::Variant {
+                ref module,
+                ref name,
+                ref variant,
+                ref arguments,
+                ..
+            } => {
+                let ty = match qualified(module.as_ref(), name, env) {
+                    Object::EnumType(ty) => ty,
+                    Object::Error(err) => return Err(Failure::Error(err.into())),
+                    _ => {
+                        let message = format!("{:?} is not an enum", name.token().literal());
+                        return Err(Failure::Error(Error::new(message).into()));
+                    }
+                };
+
+                let variant = variant.token().literal();
+
+                let Some(index) = ty.variant_index(variant) else {
+                    let message = format!("enum {} has no variant {variant:?}", ty.name());
+                    return Err(Failure::Error(Error::new(message).into()));
+                };
+
+                let arity = ty.variants()[index].1;
+
+                if arity != arguments.len() {
+                    let message = format!(
+                        "{}::{variant} holds {arity} values, but the pattern has {}",
+                        ty.name(),
+                        arguments.len()
+                    );
+                    return Err(Failure::Error(Error::new(message).into()));
+                }
+
+                let instance = match *value {
+                    Object::Enum(ref instance)
+                        if *instance.ty() == ty && instance.variant() == variant =>
+                    {
+                        instance
+                    }
+                    _ => {
+                        let message = format!("expected {}::{variant}, found {value}", ty.name());
+                        return Err(Failure::Mismatch(message));
+                    }
+                };
+
+                for (pattern, value) in arguments.iter().zip(instance.payload()) {
+                    pattern.bind(value, env)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Wildcard(_) => f.write_str("_"),
+            Self::Binding(ref name) => name.fmt(f),
+            Self::Literal(ref literal) => literal.fmt(f),
+            Self::Array {
+                ref elements,
+                ref rest,
+                ..
+            } => {
+                f.write_str("[")?;
+                fmt_list(f, elements)?;
+
+                if let Some(ref rest) = *rest {
+                    if !elements.is_empty() {
+                        f.write_str(", ")?;
+                    }
+
+                    write!(f, "...{rest}")?;
+                }
+
+                f.write_str("]")
+            }
+            Self::Struct {
+                ref module,
+                ref name,
+                ref fields,
+                ..
+            } => {
+                if let Some(ref module) = *module {
+                    write!(f, "{module}.")?;
+                }
+
+                if let Some(ref name) = *name {
+                    write!(f, "{name} ")?;
+                }
+
+                f.write_str("{")?;
+
+                for (index, (field, pattern)) in fields.iter().enumerate() {
+                    f.write_str(if index > 0 { ", " } else { " " })?;
+
+                    match *pattern {
+                        Self::Binding(ref binding)
+                            if binding.token().literal() == field.token().literal() =>
+                        {
+                            field.fmt(f)?;
+                        }
+                        _ => write!(f, "{field}: {pattern}")?,
+                    }
+                }
+
+                if fields.is_empty() {
+                    f.write_str("}")
+                } else {
+                    f.write_str(" }")
+                }
+            }
+            Self::Variant {
+                ref module,
+                ref name,
+                ref variant,
+                ref arguments,
+                ..
+            } => {
+                if let Some(ref module) = *module {
+                    write!(f, "{module}.")?;
+                }
+
+                write!(f, "{name}::{variant}")?;
+
+                if arguments.is_empty() {
+                    return Ok(());
+                }
+
+                f.write_str("(")?;
+                fmt_list(f, arguments)?;
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+fn fmt_list(f: &mut fmt::Formatter<'_>, patterns: &[Pattern]) -> fmt::Result {
+    for (index, pattern) in patterns.iter().enumerate() {
+        if index > 0 {
+            f.write_str(", ")?;
+        }
+
+        fmt::Display::fmt(pattern, f)?;
+    }
+
+    Ok(())
+}
@@ -4,25 +4,50 @@ use crate::token::TokenKind;
 
 lazy_static::lazy_static! {
     pub static ref PRECEDENCES: HashMap<TokenKind, Precedence> = HashMap::from([
+        (TokenKind::DoublePipe, Precedence::LogicalOr),
+        (TokenKind::DoubleAmpersand, Precedence::LogicalAnd),
         (TokenKind::Equal, Precedence::Equals),
         (TokenKind::NotEqual, Precedence::Equals),
         (TokenKind::LessThan, Precedence::LessGreater),
         (TokenKind::GreaterThan, Precedence::LessGreater),
+        (TokenKind::LessThanOrEqual, Precedence::LessGreater),
+        (TokenKind::GreaterThanOrEqual, Precedence::LessGreater),
+        (TokenKind::Pipe, Precedence::BitOr),
+        (TokenKind::Caret, Precedence::BitXor),
+        (TokenKind::Ampersand, Precedence::BitAnd),
+        (TokenKind::ShiftLeft, Precedence::Shift),
+        (TokenKind::ShiftRight, Precedence::Shift),
         (TokenKind::Plus, Precedence::Sum),
         (TokenKind::Minus, Precedence::Sum),
         (TokenKind::Slash, Precedence::Product),
         (TokenKind::Asterisk, Precedence::Product),
+        (TokenKind::Percent, Precedence::Product),
+        (TokenKind::DoubleAsterisk, Precedence::Power),
         (TokenKind::LParenthesis, Precedence::Call),
+        (TokenKind::LBracket, Precedence::Index),
+        (TokenKind::Dot, Precedence::Index),
+        (TokenKind::DoubleColon, Precedence::Index),
     ]);
 }
 
+/// Binding power, loosest first. Bitwise operators bind tighter than comparisons, as in Rust, so
+/// `a & mask == 0` means `(a & mask) == 0`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Precedence {
     Lowest,
+    LogicalOr,
+    LogicalAnd,
     Equals,
     LessGreater,
+    BitOr,
+    BitXor,
+    BitAnd,
+    Shift,
     Sum,
     Product,
     Prefix,
+    /// `**` binds tighter than a prefix operator on its left, so `-2 ** 2` is `-(2 ** 2)`.
+    Power,
     Call,
+    Index,
 }
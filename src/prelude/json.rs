@@ -0,0 +1,153 @@
+//! `json_parse` and `json_stringify`, built on `crate::json`.
+//!
+//! Monkey has no hashes, so a JSON object is read into a struct named `Json` with a field for
+//! each member, sorted by key, as in `config.name` or `entries(config)`. Any struct is written
+//! out as an object.
+
+use crate::environment::Environment;
+use crate::json::Json;
+use crate::object::{
+    Array, Boolean, Builtin, Error, Float, Integer, NULL, Object, Str, Struct, StructType,
+};
+use crate::prelude::{expected, new_string};
+
+pub const BUILTINS: &[Builtin] = &[
+    Builtin::new("json_parse", 1, json_parse),
+    Builtin::new("json_stringify", 2, json_stringify),
+];
+
+/// The name of the struct type JSON objects are read into.
+const OBJECT: &str = "Json";
+
+/// The value a JSON document describes.
+fn json_parse(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let Object::String(ref text) = arguments[0] else {
+        return Err(expected("json_parse", "a string", &arguments[0]));
+    };
+
+    let json = Json::parse(text.value()).map_err(|err| Error::new(format!("json_parse: {err}")))?;
+
+    from_json(json, env)
+}
+
+/// A value as JSON, with each element and member on its own line indented by the second
+/// argument's number of spaces, or all on one line if that is zero.
+fn json_stringify(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let indent = match arguments[1] {
+        Object::Integer(ref inner) => usize::try_from(inner.value()).map_err(|_| {
+            Error::new(format!(
+                "json_stringify: the indent can't be negative, found {}",
+                inner.value()
+            ))
+        })?,
+        ref other => return Err(expected("json_stringify", "an integer", other)),
+    };
+
+    let json = to_json(&arguments[0])?;
+    new_string(json.stringify(indent), env)
+}
+
+fn from_json(json: Json, env: &Environment) -> Result<Object, Error> {
+    let runtime = env.runtime();
+
+    let value = match json {
+        Json::Null => NULL,
+        Json::Boolean(value) => Boolean::new(value).into(),
+        Json::Integer(value) => Integer::new(value).into(),
+        Json::Float(value) => Float::new(value).into(),
+        Json::String(value) => Str::new(value).into(),
+        Json::Array(elements) => {
+            runtime.check_size(elements.len())?;
+            runtime.allocate(1)?;
+
+            let elements = elements
+                .into_iter()
+                .map(|element| from_json(element, env))
+                .collect::<Result<_, _>>()?;
+
+            Array::new(elements).into()
+        }
+        Json::Object(members) => {
+            let mut fields = Vec::<String>::new();
+            let mut values = Vec::new();
+
+            for (key, value) in members {
+                let value = from_json(value, env)?;
+
+                // As in JavaScript, a repeated member replaces the earlier one.
+                match fields.iter().position(|field| *field == key) {
+                    Some(index) => values[index] = value,
+                    None => {
+                        fields.push(key);
+                        values.push(value);
+                    }
+                }
+            }
+
+            return object(fields.into_iter().zip(values).collect(), env);
+        }
+    };
+
+    Ok(value)
+}
+
+/// A `Json` struct with `members`, which must have distinct keys.
+pub(super) fn object(
+    mut members: Vec<(String, Object)>,
+    env: &Environment,
+) -> Result<Object, Error> {
+    let runtime = env.runtime();
+
+    // Members are sorted so the same object always has the same type, whatever order its
+    // members were written in.
+    members.sort_by(|(left, _), (right, _)| left.cmp(right));
+    let (fields, values): (Vec<_>, Vec<_>) = members.into_iter().unzip();
+
+    runtime.check_size(fields.len())?;
+    runtime.allocate(1)?;
+
+    Ok(Struct::new(runtime.struct_type(OBJECT, &fields), values).into())
+}
+
+/// Whether `ty` is the type of objects read from JSON, rather than a struct declared in a
+/// program.
+pub(super) fn is_object(ty: &StructType, env: &Environment) -> bool {
+    ty.name() == OBJECT && env.runtime().has_struct_type(ty)
+}
+
+fn to_json(value: &Object) -> Result<Json, Error> {
+    let json = match *value {
+        Object::Null(_) => Json::Null,
+        Object::Boolean(ref inner) => Json::Boolean(inner.value()),
+        Object::Integer(ref inner) => Json::Integer(inner.value()),
+        Object::Float(ref inner) if inner.value().is_finite() => Json::Float(inner.value()),
+        Object::String(ref inner) => Json::String(inner.value().to_owned()),
+        Object::Array(ref inner) => Json::Array(
+            inner
+                .elements()
+                .iter()
+                .map(to_json)
+                .collect::<Result<_, _>>()?,
+        ),
+        Object::Struct(ref inner) => Json::Object(
+            inner
+                .ty()
+                .fields()
+                .iter()
+                .zip(inner.values())
+                .map(|(field, value)| Ok((field.to_owned(), to_json(value)?)))
+                .collect::<Result<_, Error>>()?,
+        ),
+        // Big integers don't fit in a JSON number that other programs can read back exactly.
+        Object::BigInteger(_) => {
+            let message = format!("json_stringify: {value} is too large for JSON");
+            return Err(Error::new(message));
+        }
+        _ => {
+            let message = format!("json_stringify: {value} can't be represented as JSON");
+            return Err(Error::new(message));
+        }
+    };
+
+    Ok(json)
+}
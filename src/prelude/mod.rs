@@ -0,0 +1,109 @@
+//! The functions every program starts with.
+//!
+//! String functions, `len`, `push`, `range`, `map`, `filter`, `reduce`, `sort` and the other hot
+//! paths are builtins written in Rust (see `native`). The rest, like `zip` and `enumerate`, are
+//! written in Monkey in `prelude.monkey`, which is embedded in the binary. Both are bound in a scope around the
+//! program's own, so a program can define its own `map` in place of the prelude's.
+//!
+//! The builtins in `system` reach the file system, environment variables and the process; they
+//! only work as far as the runtime's `Permissions` allow. Those in `json` convert values to and
+//! from JSON.
+
+mod json;
+mod native;
+mod system;
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::lexer::Lexer;
+use crate::object::{Error, Object, Str};
+use crate::parser::Parser;
+use crate::program::Program;
+use crate::runtime::Runtime;
+
+/// Every name the prelude defines, builtins first.
+pub const NAMES: &[&str] = &[
+    "len",
+    "push",
+    "split",
+    "join",
+    "trim",
+    "replace",
+    "upper",
+    "lower",
+    "contains",
+    "starts_with",
+    "range",
+    "reverse",
+    "sort",
+    "map",
+    "filter",
+    "reduce",
+    "keys",
+    "values",
+    "entries",
+    "merge",
+    "read_file",
+    "write_file",
+    "list_dir",
+    "getenv",
+    "args",
+    "exit",
+    "json_parse",
+    "json_stringify",
+    "zip",
+    "enumerate",
+];
+
+const SOURCE: &str = include_str!("prelude.monkey");
+
+/// A new top-level scope in the session of `runtime`, inside one holding the prelude.
+pub fn environment(runtime: Rc<Runtime>) -> Environment {
+    let mut prelude = Environment::with_runtime(Rc::clone(&runtime));
+
+    let builtins = native::BUILTINS
+        .iter()
+        .chain(system::BUILTINS)
+        .chain(json::BUILTINS);
+
+    for builtin in builtins {
+        _ = prelude.set(builtin.name().to_owned(), (*builtin).into());
+    }
+
+    let lexer = Lexer::new(SOURCE.to_owned());
+    let mut parser = Parser::new(lexer.tokens());
+    let program = Program::parse(&mut parser).expect("failed to parse the prelude");
+    assert!(program.errors().is_empty(), "the prelude has syntax errors");
+
+    // A debugger stepping through the program shouldn't stop in the prelude being set up.
+    let hook = runtime.take_hook();
+    let value = program.evaluate(&mut prelude);
+
+    if let Some(hook) = hook {
+        runtime.set_hook(hook);
+    }
+
+    assert!(
+        !matches!(value, Object::Error(_)),
+        "failed to evaluate the prelude: {value}"
+    );
+
+    debug_assert_eq!(prelude.bindings().len(), NAMES.len());
+    Environment::new(HashMap::new(), Some(Box::new(prelude)))
+}
+
+/// A string, as long as the runtime's limits allow one that long.
+fn new_string(value: String, env: &Environment) -> Result<Object, Error> {
+    let runtime = env.runtime();
+    runtime.check_size(value.chars().count())?;
+    runtime.allocate(1)?;
+    Ok(Str::new(value).into())
+}
+
+/// The error for a builtin given the wrong kind of argument.
+fn expected(name: &str, what: &str, value: &Object) -> Error {
+    Error::new(format!("{name}: expected {what}, found {value}"))
+}
@@ -0,0 +1,375 @@
+//! The builtins of the prelude, written in Rust.
+
+use crate::environment::Environment;
+use crate::expression::call_function;
+use crate::object::{Array, Boolean, Builtin, Error, Integer, Object, Str};
+use crate::prelude::{expected, json, new_string};
+
+pub const BUILTINS: &[Builtin] = &[
+    Builtin::new("len", 1, len),
+    Builtin::new("push", 2, push),
+    Builtin::new("split", 2, split),
+    Builtin::new("join", 2, join),
+    Builtin::new("trim", 1, trim),
+    Builtin::new("replace", 3, replace),
+    Builtin::new("upper", 1, upper),
+    Builtin::new("lower", 1, lower),
+    Builtin::new("contains", 2, contains),
+    Builtin::new("starts_with", 2, starts_with),
+    Builtin::new("range", 2, range),
+    Builtin::new("reverse", 1, reverse),
+    Builtin::new("sort", 2, sort),
+    Builtin::new("map", 2, map),
+    Builtin::new("filter", 2, filter),
+    Builtin::new("reduce", 3, reduce),
+    Builtin::new("keys", 1, keys),
+    Builtin::new("values", 1, values),
+    Builtin::new("entries", 1, entries),
+    Builtin::new("merge", 2, merge),
+];
+
+/// The number of characters in a string, or elements in an array.
+fn len(arguments: &[Object], _: &mut Environment) -> Result<Object, Error> {
+    let len = match arguments[0] {
+        Object::String(ref inner) => inner.value().chars().count(),
+        Object::Array(ref inner) => inner.elements().len(),
+        ref other => return Err(expected("len", "a string or an array", other)),
+    };
+
+    Ok(integer(len))
+}
+
+/// A copy of an array with one more element at the end.
+fn push(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let mut elements = array("push", &arguments[0])?.to_vec();
+    elements.push(arguments[1].clone());
+    new_array(elements, env)
+}
+
+/// The pieces of a string between each occurrence of a separator, or its characters if the
+/// separator is empty.
+fn split(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let value = string("split", &arguments[0])?;
+    let separator = string("split", &arguments[1])?;
+
+    let pieces = if separator.is_empty() {
+        value
+            .chars()
+            .map(|c| Str::new(c.to_string()).into())
+            .collect()
+    } else {
+        value
+            .split(separator)
+            .map(|piece| Str::new(piece).into())
+            .collect()
+    };
+
+    new_array(pieces, env)
+}
+
+/// The elements of an array written one after the other, with a separator between each.
+fn join(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let elements = array("join", &arguments[0])?;
+    let separator = string("join", &arguments[1])?;
+
+    let joined = elements
+        .iter()
+        .map(Object::to_string)
+        .collect::<Vec<_>>()
+        .join(separator);
+
+    new_string(joined, env)
+}
+
+fn trim(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let value = string("trim", &arguments[0])?;
+    new_string(value.trim().to_owned(), env)
+}
+
+/// A copy of a string with every occurrence of one string replaced by another.
+fn replace(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let value = string("replace", &arguments[0])?;
+    let from = string("replace", &arguments[1])?;
+    let to = string("replace", &arguments[2])?;
+
+    if from.is_empty() {
+        return Err(Error::new(
+            "replace: cannot replace an empty string".to_owned(),
+        ));
+    }
+
+    new_string(value.replace(from, to), env)
+}
+
+fn upper(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let value = string("upper", &arguments[0])?;
+    new_string(value.to_uppercase(), env)
+}
+
+fn lower(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let value = string("lower", &arguments[0])?;
+    new_string(value.to_lowercase(), env)
+}
+
+/// Whether a string contains another, or an array contains a value.
+fn contains(arguments: &[Object], _: &mut Environment) -> Result<Object, Error> {
+    let found = match arguments[0] {
+        Object::String(ref inner) => inner.value().contains(string("contains", &arguments[1])?),
+        Object::Array(ref inner) => inner.elements().contains(&arguments[1]),
+        ref other => return Err(expected("contains", "a string or an array", other)),
+    };
+
+    Ok(Boolean::new(found).into())
+}
+
+fn starts_with(arguments: &[Object], _: &mut Environment) -> Result<Object, Error> {
+    let value = string("starts_with", &arguments[0])?;
+    let prefix = string("starts_with", &arguments[1])?;
+    Ok(Boolean::new(value.starts_with(prefix)).into())
+}
+
+/// The integers from the first argument up to, but not including, the second.
+fn range(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let start = int("range", &arguments[0])?;
+    let end = int("range", &arguments[1])?;
+
+    // Check the size before building anything, since it could be enormous.
+    let len = usize::try_from(end.saturating_sub(start)).unwrap_or_default();
+    env.runtime().check_size(len)?;
+
+    // Without a size limit, ask for the memory up front so a huge range is an error rather than
+    // an abort.
+    let mut elements = Vec::new();
+    elements
+        .try_reserve_exact(len)
+        .map_err(|_| Error::new(format!("range: not enough memory for {len} elements")))?;
+    elements.extend((start..end).map(|value| Object::from(Integer::new(value))));
+
+    new_array(elements, env)
+}
+
+/// A string or an array, back to front.
+fn reverse(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    match arguments[0] {
+        Object::String(ref inner) => new_string(inner.value().chars().rev().collect(), env),
+        Object::Array(ref inner) => {
+            new_array(inner.elements().iter().rev().cloned().collect(), env)
+        }
+        ref other => Err(expected("reverse", "a string or an array", other)),
+    }
+}
+
+/// A sorted copy of an array. The second argument says whether its first argument goes before
+/// its second; elements it doesn't order keep their order.
+fn sort(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let elements = array("sort", &arguments[0])?.to_vec();
+    let less = &arguments[1];
+
+    let mut is_less = |a: &Object, b: &Object| match call(less, vec![a.clone(), b.clone()], env)? {
+        Object::Boolean(inner) => Ok(inner.value()),
+        other => Err(Error::new(format!(
+            "sort: the comparison must return a boolean, found {other}"
+        ))),
+    };
+
+    let sorted = merge_sort(elements, &mut is_less)?;
+    new_array(sorted, env)
+}
+
+/// A stable sort that stops at the first error, which `slice::sort_by` can't do, and which
+/// tolerates a comparison that isn't a total order.
+fn merge_sort(
+    mut elements: Vec<Object>,
+    is_less: &mut impl FnMut(&Object, &Object) -> Result<bool, Error>,
+) -> Result<Vec<Object>, Error> {
+    if elements.len() <= 1 {
+        return Ok(elements);
+    }
+
+    let right = elements.split_off(elements.len() / 2);
+    let left = merge_sort(elements, is_less)?;
+    let right = merge_sort(right, is_less)?;
+
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+
+    while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+        // Take from the left unless the right goes strictly before it, to keep the sort stable.
+        let next = if is_less(b, a)? {
+            right.next()
+        } else {
+            left.next()
+        };
+        merged.extend(next);
+    }
+
+    merged.extend(left);
+    merged.extend(right);
+    Ok(merged)
+}
+
+/// The result of calling the second argument on each element of an array.
+fn map(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let elements = array("map", &arguments[0])?;
+    let mut results = Vec::with_capacity(elements.len());
+
+    for element in elements {
+        results.push(call(&arguments[1], vec![element.clone()], env)?);
+    }
+
+    new_array(results, env)
+}
+
+/// The elements of an array that the second argument returns a true value for, as `if` decides.
+fn filter(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let elements = array("filter", &arguments[0])?;
+    let mut kept = Vec::new();
+
+    for element in elements {
+        if call(&arguments[1], vec![element.clone()], env)?
+            .as_boolean()
+            .value()
+        {
+            kept.push(element.clone());
+        }
+    }
+
+    new_array(kept, env)
+}
+
+/// Combine the elements of an array one at a time, starting from the second argument:
+/// `reduce([1, 2, 3], 0, fn(sum, x) { sum + x })` is `6`.
+fn reduce(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let elements = array("reduce", &arguments[0])?;
+    let mut result = arguments[1].clone();
+
+    for element in elements {
+        result = call(&arguments[2], vec![result, element.clone()], env)?;
+    }
+
+    Ok(result)
+}
+
+/// Call a function passed to a builtin, failing with whatever error it does.
+fn call(function: &Object, arguments: Vec<Object>, env: &mut Environment) -> Result<Object, Error> {
+    match call_function(function, arguments, env) {
+        Object::Error(err) => Err(err),
+        value => Ok(value),
+    }
+}
+
+/// The names of a struct's fields, or of a module's exports.
+fn keys(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let keys = fields("keys", &arguments[0])?
+        .into_iter()
+        .map(|(key, _)| Str::new(key).into())
+        .collect();
+
+    new_array(keys, env)
+}
+
+/// The values of a struct's fields, or of a module's exports.
+fn values(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let values = fields("values", &arguments[0])?
+        .into_iter()
+        .map(|(_, value)| value)
+        .collect();
+
+    new_array(values, env)
+}
+
+/// A `[name, value]` array for each of a struct's fields, or of a module's exports.
+fn entries(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let mut entries = Vec::new();
+
+    for (key, value) in fields("entries", &arguments[0])? {
+        entries.push(new_array(vec![Str::new(key).into(), value], env)?);
+    }
+
+    new_array(entries, env)
+}
+
+/// A copy of the first struct with the fields of the second, which must all be fields of the
+/// first unless it was read from JSON, in which case any new fields are added.
+fn merge(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let Object::Struct(ref base) = arguments[0] else {
+        return Err(expected("merge", "a struct", &arguments[0]));
+    };
+
+    let Object::Struct(ref update) = arguments[1] else {
+        return Err(expected("merge", "a struct", &arguments[1]));
+    };
+
+    if json::is_object(base.ty(), env) {
+        let mut members = fields("merge", &arguments[0])?;
+
+        for (field, value) in update.ty().fields().iter().zip(update.values()) {
+            match members.iter_mut().find(|(key, _)| key == field) {
+                Some(member) => member.1 = value.clone(),
+                None => members.push((field.clone(), value.clone())),
+            }
+        }
+
+        return json::object(members, env);
+    }
+
+    let mut merged = base.clone();
+
+    for (field, value) in update.ty().fields().iter().zip(update.values()) {
+        merged = merged.with(field, value.clone()).ok_or_else(|| {
+            let name = base.ty().name();
+            Error::new(format!("merge: struct {name} has no field {field:?}"))
+        })?;
+    }
+
+    env.runtime().allocate(1)?;
+    Ok(merged.into())
+}
+
+fn fields(name: &str, value: &Object) -> Result<Vec<(String, Object)>, Error> {
+    match *value {
+        Object::Struct(ref inner) => Ok(inner
+            .ty()
+            .fields()
+            .iter()
+            .cloned()
+            .zip(inner.values().iter().cloned())
+            .collect()),
+        Object::Module(ref inner) => Ok(inner.exports().to_vec()),
+        ref other => Err(expected(name, "a struct or a module", other)),
+    }
+}
+
+fn string<'a>(name: &str, value: &'a Object) -> Result<&'a str, Error> {
+    match *value {
+        Object::String(ref inner) => Ok(inner.value()),
+        ref other => Err(expected(name, "a string", other)),
+    }
+}
+
+fn array<'a>(name: &str, value: &'a Object) -> Result<&'a [Object], Error> {
+    match *value {
+        Object::Array(ref inner) => Ok(inner.elements()),
+        ref other => Err(expected(name, "an array", other)),
+    }
+}
+
+fn int(name: &str, value: &Object) -> Result<i64, Error> {
+    match *value {
+        Object::Integer(ref inner) => Ok(inner.value()),
+        ref other => Err(expected(name, "an integer", other)),
+    }
+}
+
+fn integer(value: usize) -> Object {
+    Integer::new(i64::try_from(value).unwrap_or(i64::MAX)).into()
+}
+
+/// An array, as long as the runtime's limits allow one that large.
+fn new_array(elements: Vec<Object>, env: &Environment) -> Result<Object, Error> {
+    let runtime = env.runtime();
+    runtime.check_size(elements.len())?;
+    runtime.allocate(1)?;
+    Ok(Array::new(elements).into())
+}
@@ -0,0 +1,128 @@
+//! The builtins of the prelude that reach outside the interpreter. Each one fails with a
+//! permission error unless the host granted it the matching `Permissions`.
+
+use std::path::Path;
+use std::{env, fs, io};
+
+use crate::environment::Environment;
+use crate::object::{Array, Builtin, Error, ErrorKind, NULL, Object, Str};
+use crate::prelude::{expected, new_string};
+
+pub const BUILTINS: &[Builtin] = &[
+    Builtin::new("read_file", 1, read_file),
+    Builtin::new("write_file", 2, write_file),
+    Builtin::new("list_dir", 1, list_dir),
+    Builtin::new("getenv", 1, getenv),
+    Builtin::new("args", 0, args),
+    Builtin::new("exit", 1, exit),
+];
+
+/// The contents of a text file.
+fn read_file(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let path = path("read_file", &arguments[0])?;
+    env.runtime().permissions().check_read(path)?;
+
+    let contents = fs::read_to_string(path).map_err(|err| failed("read_file", path, &err))?;
+    new_string(contents, env)
+}
+
+/// Replace the contents of a file, creating it if need be.
+fn write_file(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let path = path("write_file", &arguments[0])?;
+    let Object::String(ref contents) = arguments[1] else {
+        return Err(expected("write_file", "a string", &arguments[1]));
+    };
+
+    env.runtime().permissions().check_write(path)?;
+
+    fs::write(path, contents.value()).map_err(|err| failed("write_file", path, &err))?;
+    Ok(NULL)
+}
+
+/// The names of the entries in a directory, sorted.
+fn list_dir(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let path = path("list_dir", &arguments[0])?;
+    env.runtime().permissions().check_read(path)?;
+
+    let mut names = Vec::new();
+
+    for entry in fs::read_dir(path).map_err(|err| failed("list_dir", path, &err))? {
+        let entry = entry.map_err(|err| failed("list_dir", path, &err))?;
+        names.push(entry.file_name().to_string_lossy().into_owned());
+    }
+
+    names.sort();
+
+    let runtime = env.runtime();
+    runtime.check_size(names.len())?;
+    runtime.allocate(1)?;
+
+    let names = names
+        .into_iter()
+        .map(|name| Str::new(name).into())
+        .collect();
+    Ok(Array::new(names).into())
+}
+
+/// The value of an environment variable, or `null` if it isn't set.
+fn getenv(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let Object::String(ref name) = arguments[0] else {
+        return Err(expected("getenv", "a string", &arguments[0]));
+    };
+
+    env.runtime().permissions().check_env(name.value())?;
+
+    match env::var(name.value()) {
+        Ok(value) => Ok(Str::new(value).into()),
+        Err(_) => Ok(NULL),
+    }
+}
+
+/// The arguments the host passed to the script.
+fn args(_: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    let runtime = env.runtime();
+    runtime
+        .permissions()
+        .check_process("reading the script's arguments")?;
+
+    let args = runtime.args();
+    runtime.check_size(args.len())?;
+    runtime.allocate(1)?;
+
+    let args = args.into_iter().map(|arg| Str::new(arg).into()).collect();
+    Ok(Array::new(args).into())
+}
+
+/// Stop the program with a status code. This unwinds like an error, which the host is expected
+/// to turn into the process's exit status.
+fn exit(arguments: &[Object], env: &mut Environment) -> Result<Object, Error> {
+    env.runtime()
+        .permissions()
+        .check_process("ending the process with `exit`")?;
+
+    let code = match arguments[0] {
+        Object::Integer(ref inner) => u8::try_from(inner.value()).map_err(|_| {
+            Error::new(format!(
+                "exit: the code must be between 0 and 255, found {}",
+                inner.value()
+            ))
+        })?,
+        ref other => return Err(expected("exit", "an integer", other)),
+    };
+
+    Err(Error::with_kind(
+        ErrorKind::Exit(code),
+        format!("exit with code {code}"),
+    ))
+}
+
+fn path<'a>(name: &str, value: &'a Object) -> Result<&'a Path, Error> {
+    match *value {
+        Object::String(ref inner) => Ok(Path::new(inner.value())),
+        ref other => Err(expected(name, "a path", other)),
+    }
+}
+
+fn failed(name: &str, path: &Path, err: &io::Error) -> Error {
+    Error::new(format!("{name}: {:?}: {err}", path.display().to_string()))
+}
@@ -1,50 +1,206 @@
+use std::fmt;
+use std::rc::Rc;
+
 use crate::environment::Environment;
 use crate::evaluator::Evaluate;
-use crate::object::{NULL, Object};
+use crate::object::{Error, NULL, Object};
 use crate::parser::{Parse, Parser, ParserError};
 use crate::statement::Statement;
+use crate::token::Span;
 
 #[derive(Debug, Default)]
 pub struct Program {
     statements: Vec<Statement>,
-    errors: Vec<String>,
+    errors: Vec<ParserError>,
 }
 
 impl Program {
-    pub fn errors(&self) -> &[String] {
+    pub fn statements(&self) -> &[Statement] {
+        &self.statements
+    }
+
+    pub fn statements_mut(&mut self) -> &mut Vec<Statement> {
+        &mut self.statements
+    }
+
+    pub fn errors(&self) -> &[ParserError] {
         &self.errors
     }
 
+    pub fn span(&self) -> Span {
+        match (self.statements.first(), self.statements.last()) {
+            (Some(first), Some(last)) => first.span().to(last.span()),
+            _ => Span::default(),
+        }
+    }
+
     pub fn parse(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
         let mut program = Self::default();
 
         while parser.token().is_some() {
             match Statement::parse(parser) {
                 Ok(statement) => program.statements.push(statement),
-                Err(err) => program.errors.push(err.to_string()),
+                Err(err) => {
+                    parser.report(err);
+                    // Skip the offending token so parsing can resume.
+                    parser.advance();
+                }
             }
-
-            parser.advance();
         }
 
+        program.errors = parser.take_errors();
+
         Ok(program)
     }
 }
 
+/// Prints one statement per line; see `Expression`'s `Display` implementation for the meaning of
+/// the alternate flag.
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, statement) in self.statements.iter().enumerate() {
+            if index > 0 {
+                f.write_str("\n")?;
+            }
+
+            statement.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl Evaluate for Program {
     fn evaluate(&self, env: &mut Environment) -> Object {
+        let runtime = Rc::clone(env.runtime());
+
+        // A program evaluated inside another, e.g. by the debugger, shares its budget.
+        if runtime.depth() == 0 {
+            runtime.start();
+        }
+
+        if let Err(err) = runtime.enter("<program>".to_owned(), env.clone(), self.span()) {
+            return err.into();
+        }
+
         let mut result = NULL;
 
         for statement in &self.statements {
             result = statement.evaluate(env);
 
             match result {
-                Object::Return(inner) => return inner.value().to_owned(),
-                Object::Error(_) => return result,
+                Object::Return(inner) => {
+                    result = inner.value().to_owned();
+                    break;
+                }
+                Object::Error(_) => break,
+                // The parser reports these, but a program can be run in spite of its errors.
+                Object::Break(_) | Object::Continue(_) => {
+                    result = Error::new(format!("`{result}` outside of a loop")).into();
+                    break;
+                }
                 _ => continue,
             }
         }
 
+        runtime.exit();
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+    use crate::lexer::Lexer;
+
+    /// Source covering every kind of node, written the way people write it rather than the way
+    /// it prints.
+    const CORPUS: &[&str] = &[
+        "let x = 1 + 2 * 3 - 4 / 5 % 6;",
+        "2 ** 3 ** 2; (2 ** 3) ** 2; -2 ** 2; (-2) ** 2;",
+        "a & b | c ^ d << 1 >> 2; x & mask == 0; ~x + !y; - -x; !-x; -(x + 1);",
+        "a < b && b <= c || !(c >= d) && d > e; a == b != c;",
+        "f(1)(2); -f(x); xs[0][1]; -xs[i]; !p.x; (-p).x; p.norm(); Point.origin();",
+        "x = x + 1; p.x = 5; xs = push(xs, [1, 2]);",
+        r#"let s = "tab\tquote\"slash\\new\nemoji😀"; let größe = 3;"#,
+        "1.5; 2.5e3; 1e-9; 0.1 + 2.0E+3; 0xff; 0o755; 0b1010; 1_000_000;",
+        "let add = fn(a, b) { return a + b; }; let id = fn(x: int) -> int { x };",
+        "let f = fn([a, b], {x}) { a + b + x }; let [first, ...rest] = xs;",
+        "if (a) { 1 } else { if (b) { 2 } else { 3 } }; if (x > 1) { x };",
+        "while (i < 10) { if (i == 5) { break; } i = i + 1; continue; }",
+        "for (x in [1, 2, 3]) { puts(x); }; while (false) {}; x;",
+        "struct Point { x, y } let p = Point { x: 1, y: 2 }; let q = Point { x: -1, y: p.y };",
+        "impl Point { fn norm(self) { self.x * self.x } fn origin() { Point { x: 0, y: 0 } } }",
+        "enum Shape { Circle(r), Rect(w, h), Empty } let c = Shape::Circle(3); Shape::Empty;",
+        "match (s) { Shape::Circle(r) => r * r, Shape::Rect(w, h) if w == h => w * w, _ => 0 };",
+        "match (v) { [a, b] => a, Point { x, y: 0 } => x, 1 => 2, \"s\" => 3, n => n };",
+        "let Point { x, y } = p; let [[a, b], Point { z }] = nested;",
+        r#"import "lib/math.monkey" as math; export let square = fn(x) { x * x };"#,
+        "export struct Pair { a, b } export enum Option { Some(x), None } math.square(3);",
+        "let p = m.P { x: 1, y: 2 }; let m.P { x } = p;",
+        "match (s) { m.Shape::Circle(r) => r, m.P { x, y: 0 } => x, _ => m.Shape::Empty };",
+        "let unless = macro(c, a) { quote(if (!(unquote(c))) { unquote(a) } else { null }) };",
+    ];
+
+    fn parse_with_errors(source: &str) -> Program {
+        let lexer = Lexer::new(source.to_owned());
+        let mut parser = Parser::new(lexer.tokens());
+        Program::parse(&mut parser).unwrap()
+    }
+
+    fn parse(source: &str) -> Program {
+        let program = parse_with_errors(source);
+        assert!(
+            program.errors().is_empty(),
+            "{source:?}: {:?}",
+            program.errors()
+        );
+        program
+    }
+
+    #[test]
+    fn printing_a_program_gives_source_that_parses_back_to_it() {
+        for source in CORPUS {
+            let program = parse(source);
+            let printed = program.to_string();
+            let parenthesised = format!("{program:#}");
+
+            let reparsed = parse(&printed);
+            assert_eq!(reparsed.to_string(), printed, "{source:?}");
+            assert_eq!(format!("{reparsed:#}"), parenthesised, "{source:?}");
+
+            let reparsed = parse(&parenthesised);
+            assert_eq!(reparsed.to_string(), printed, "{source:?}");
+            assert_eq!(format!("{reparsed:#}"), parenthesised, "{source:?}");
+        }
+    }
+
+    #[test]
+    fn nesting_too_deeply_is_one_error_rather_than_a_stack_overflow() {
+        // Like `main`, run where the deepest tree allowed fits on the stack in a debug build.
+        let test = thread::Builder::new()
+            .stack_size(64 * 1024 * 1024)
+            .spawn(|| {
+                // The `1` inside the parentheses is one more level.
+                let depth = crate::parser::MAX_DEPTH - 1;
+                let fits = format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+                assert_eq!(parse(&fits).to_string(), "1;");
+
+                for source in [
+                    format!("{}1{}", "(".repeat(100_000), ")".repeat(100_000)),
+                    format!("{}1", "-".repeat(100_000)),
+                    "while (true) { ".repeat(100_000),
+                    format!("let {}x = 1;", "[".repeat(100_000)),
+                ] {
+                    let program = parse_with_errors(&source);
+                    let errors = program.errors();
+                    assert_eq!(errors.len(), 1, "{errors:?}");
+                    assert!(matches!(errors[0], ParserError::TooDeep { .. }));
+                }
+            });
+
+        test.unwrap().join().unwrap();
+    }
+}
@@ -0,0 +1,457 @@
+//! Static name resolution: ties every identifier in a program to the `let` or parameter that
+//! binds it, without running anything.
+//!
+//! Scoping mirrors the evaluator. A name used directly in a scope must be bound before it is used,
+//! but a function body only looks names up when it is called, so it may refer to bindings that
+//! appear later in any enclosing function (this is what makes recursion work).
+
+use crate::expression::{Call, Expression, FunctionLiteral, Identifier, MacroLiteral, Match};
+use crate::pattern::Pattern;
+use crate::program::Program;
+use crate::statement::{Block, Enum, For, Import, Let, Struct};
+use crate::token::Span;
+use crate::visitor::{Visitor, walk_block, walk_call, walk_pattern};
+
+/// Names that are always defined, because the evaluator handles them itself.
+pub const BUILTINS: &[&str] = &["quote", "unquote"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingKind {
+    Let,
+    Parameter,
+    /// The variable of a `for` loop.
+    LoopVariable,
+    /// A name bound by a pattern in a `match` arm.
+    Pattern,
+    /// The name of a `struct`.
+    Struct,
+    /// The name of an `enum`.
+    Enum,
+    /// The name an `import` binds a module to.
+    Module,
+}
+
+#[derive(Debug, Clone)]
+pub struct Binding {
+    name: String,
+    span: Span,
+    definition: Span,
+    kind: BindingKind,
+    scope: usize,
+    arity: Option<usize>,
+}
+
+impl Binding {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Where the name itself is written.
+    pub const fn span(&self) -> Span {
+        self.span
+    }
+
+    /// The whole `let` statement, or the name itself for parameters and loop variables.
+    pub const fn definition(&self) -> Span {
+        self.definition
+    }
+
+    pub const fn kind(&self) -> BindingKind {
+        self.kind
+    }
+
+    pub const fn scope(&self) -> usize {
+        self.scope
+    }
+
+    /// The number of parameters, when the binding is a `let` of a function or macro literal.
+    pub const fn arity(&self) -> Option<usize> {
+        self.arity
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Reference {
+    name: String,
+    span: Span,
+    binding: Option<usize>,
+}
+
+impl Reference {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub const fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Index into `Resolution::bindings`, or `None` if the name is not bound anywhere in reach.
+    pub const fn binding(&self) -> Option<usize> {
+        self.binding
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Scope {
+    parent: Option<usize>,
+    span: Span,
+    function: usize,
+}
+
+impl Scope {
+    pub const fn parent(&self) -> Option<usize> {
+        self.parent
+    }
+
+    pub const fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Identifies the function (or the program, for top-level scopes) this scope belongs to.
+    pub const fn function(&self) -> usize {
+        self.function
+    }
+}
+
+/// A call whose callee is a plain identifier.
+#[derive(Debug, Clone)]
+pub struct CallSite {
+    reference: usize,
+    arguments: usize,
+    span: Span,
+}
+
+impl CallSite {
+    /// Index into `Resolution::references` for the callee.
+    pub const fn reference(&self) -> usize {
+        self.reference
+    }
+
+    pub const fn arguments(&self) -> usize {
+        self.arguments
+    }
+
+    pub const fn span(&self) -> Span {
+        self.span
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Resolution {
+    scopes: Vec<Scope>,
+    bindings: Vec<Binding>,
+    references: Vec<Reference>,
+    calls: Vec<CallSite>,
+    duplicate_parameters: Vec<usize>,
+}
+
+impl Resolution {
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    pub fn bindings(&self) -> &[Binding] {
+        &self.bindings
+    }
+
+    pub fn references(&self) -> &[Reference] {
+        &self.references
+    }
+
+    pub fn calls(&self) -> &[CallSite] {
+        &self.calls
+    }
+
+    /// Parameters that repeat an earlier parameter of the same function, as binding indices.
+    pub fn duplicate_parameters(&self) -> &[usize] {
+        &self.duplicate_parameters
+    }
+}
+
+pub fn resolve(program: &Program) -> Resolution {
+    let mut resolver = Resolver::default();
+    resolver.enter(program.span(), true);
+    resolver.visit_program(program);
+    resolver.exit();
+    resolver.finish()
+}
+
+#[derive(Debug, Default)]
+struct Resolver {
+    resolution: Resolution,
+    scope: Option<usize>,
+    functions: usize,
+    function: usize,
+    /// Per reference: the scope it appeared in, and how many bindings existed at that point.
+    pending: Vec<(usize, usize)>,
+}
+
+impl Resolver {
+    fn enter(&mut self, span: Span, is_function: bool) {
+        if is_function {
+            self.functions += 1;
+            self.function = self.functions;
+        }
+
+        self.resolution.scopes.push(Scope {
+            parent: self.scope,
+            span,
+            function: self.function,
+        });
+        self.scope = Some(self.resolution.scopes.len() - 1);
+    }
+
+    fn exit(&mut self) {
+        let scope = &self.resolution.scopes[self.scope.expect("exit without enter")];
+        self.scope = scope.parent;
+
+        if let Some(parent) = self.scope {
+            self.function = self.resolution.scopes[parent].function;
+        }
+    }
+
+    fn declare(
+        &mut self,
+        name: &Identifier,
+        definition: Span,
+        kind: BindingKind,
+        arity: Option<usize>,
+    ) {
+        let scope = self.scope.expect("declare outside of a scope");
+
+        if kind == BindingKind::Parameter
+            && self.resolution.bindings.iter().any(|binding| {
+                binding.scope == scope
+                    && binding.kind == BindingKind::Parameter
+                    && binding.name == name.token().literal()
+            })
+        {
+            self.resolution
+                .duplicate_parameters
+                .push(self.resolution.bindings.len());
+        }
+
+        self.resolution.bindings.push(Binding {
+            name: name.token().literal().to_owned(),
+            span: name.span(),
+            definition,
+            kind,
+            scope,
+            arity,
+        });
+    }
+
+    /// Declare every name `pattern` binds, with `definition` or else the name itself as where it
+    /// is defined, and resolve the names of the structs and enums it takes apart.
+    fn declare_pattern(
+        &mut self,
+        pattern: &Pattern,
+        definition: Option<Span>,
+        kind: BindingKind,
+        arity: Option<usize>,
+    ) {
+        let mut declarations = Declarations {
+            resolver: self,
+            definition,
+            kind,
+            arity,
+        };
+        declarations.visit_pattern(pattern);
+    }
+
+    fn function_scope(&mut self, span: Span, parameters: &[Pattern], body: &Block) {
+        self.enter(span, true);
+
+        for parameter in parameters {
+            self.declare_pattern(parameter, None, BindingKind::Parameter, None);
+        }
+
+        walk_block(self, body);
+        self.exit();
+    }
+
+    fn finish(mut self) -> Resolution {
+        for (index, &(scope, seen)) in self.pending.iter().enumerate() {
+            let binding = self.lookup(&self.resolution.references[index].name, scope, seen);
+            self.resolution.references[index].binding = binding;
+        }
+
+        self.resolution
+    }
+
+    fn lookup(&self, name: &str, scope: usize, seen: usize) -> Option<usize> {
+        let function = self.resolution.scopes[scope].function;
+        let mut current = Some(scope);
+
+        while let Some(index) = current {
+            let same_function = self.resolution.scopes[index].function == function;
+            let found = self
+                .resolution
+                .bindings
+                .iter()
+                .enumerate()
+                .filter(|(id, binding)| {
+                    binding.scope == index && binding.name == name && (!same_function || *id < seen)
+                })
+                .map(|(id, _)| id)
+                .next_back();
+
+            if found.is_some() {
+                return found;
+            }
+
+            current = self.resolution.scopes[index].parent;
+        }
+
+        None
+    }
+}
+
+impl Visitor for Resolver {
+    fn visit_let(&mut self, node: &Let) {
+        self.visit_expression(node.value());
+
+        let arity = match *node.value() {
+            Expression::FunctionLiteral(ref inner) => Some(inner.parameters().len()),
+            Expression::MacroLiteral(ref inner) => Some(inner.parameters().len()),
+            _ => None,
+        };
+
+        // Only a plain name is known to hold the function.
+        let arity = arity.filter(|_| matches!(*node.pattern(), Pattern::Binding(_)));
+        self.declare_pattern(node.pattern(), Some(node.span()), BindingKind::Let, arity);
+    }
+
+    fn visit_struct(&mut self, node: &Struct) {
+        self.declare(node.name(), node.span(), BindingKind::Struct, None);
+    }
+
+    fn visit_enum(&mut self, node: &Enum) {
+        self.declare(node.name(), node.span(), BindingKind::Enum, None);
+    }
+
+    fn visit_import(&mut self, node: &Import) {
+        self.declare(node.name(), node.span(), BindingKind::Module, None);
+    }
+
+    fn visit_for(&mut self, node: &For) {
+        self.visit_expression(node.iterable());
+
+        self.enter(node.span(), false);
+        let variable = node.variable();
+        self.declare(variable, variable.span(), BindingKind::LoopVariable, None);
+        self.visit_block(node.body());
+        self.exit();
+    }
+
+    fn visit_match(&mut self, node: &Match) {
+        self.visit_expression(node.value());
+
+        for arm in node.arms() {
+            self.enter(arm.span(), false);
+            self.declare_pattern(arm.pattern(), None, BindingKind::Pattern, None);
+
+            if let Some(guard) = arm.guard() {
+                self.visit_expression(guard);
+            }
+
+            self.visit_statement(arm.body());
+            self.exit();
+        }
+    }
+
+    fn visit_block(&mut self, node: &Block) {
+        self.enter(node.span(), false);
+        walk_block(self, node);
+        self.exit();
+    }
+
+    fn visit_function_literal(&mut self, node: &FunctionLiteral) {
+        self.function_scope(node.span(), node.parameters(), node.body());
+    }
+
+    fn visit_macro_literal(&mut self, node: &MacroLiteral) {
+        let parameters = node
+            .parameters()
+            .iter()
+            .cloned()
+            .map(Pattern::Binding)
+            .collect::<Vec<_>>();
+        self.function_scope(node.span(), &parameters, node.body());
+    }
+
+    fn visit_identifier(&mut self, node: &Identifier) {
+        let scope = self.scope.expect("reference outside of a scope");
+        self.pending.push((scope, self.resolution.bindings.len()));
+        self.resolution.references.push(Reference {
+            name: node.token().literal().to_owned(),
+            span: node.span(),
+            binding: None,
+        });
+    }
+
+    fn visit_call(&mut self, node: &Call) {
+        let Expression::Identifier(ref callee) = *node.function() else {
+            return walk_call(self, node);
+        };
+
+        // Quoted code is data, not code to run here; only what gets unquoted is evaluated.
+        if callee.token().literal() == "quote" && node.arguments().len() == 1 {
+            return Unquotes(self).visit_expression(&node.arguments()[0]);
+        }
+
+        self.visit_identifier(callee);
+        self.resolution.calls.push(CallSite {
+            reference: self.resolution.references.len() - 1,
+            arguments: node.arguments().len(),
+            span: node.span(),
+        });
+
+        for argument in node.arguments() {
+            self.visit_expression(argument);
+        }
+    }
+}
+
+/// Walks quoted code, handing only the arguments of `unquote(...)` back to the resolver.
+struct Unquotes<'a>(&'a mut Resolver);
+
+impl Visitor for Unquotes<'_> {
+    fn visit_call(&mut self, node: &Call) {
+        match *node.function() {
+            Expression::Identifier(ref callee)
+                if callee.token().literal() == "unquote" && node.arguments().len() == 1 =>
+            {
+                self.0.visit_expression(&node.arguments()[0]);
+            }
+            _ => walk_call(self, node),
+        }
+    }
+}
+
+/// Walks a pattern for the resolver, declaring the names it binds and handing every other name
+/// back as a reference.
+struct Declarations<'a> {
+    resolver: &'a mut Resolver,
+    definition: Option<Span>,
+    kind: BindingKind,
+    arity: Option<usize>,
+}
+
+impl Visitor for Declarations<'_> {
+    fn visit_pattern(&mut self, node: &Pattern) {
+        match *node {
+            Pattern::Binding(ref name) => {
+                let definition = self.definition.unwrap_or_else(|| name.span());
+                self.resolver
+                    .declare(name, definition, self.kind, self.arity);
+            }
+            _ => walk_pattern(self, node),
+        }
+    }
+
+    fn visit_identifier(&mut self, node: &Identifier) {
+        self.resolver.visit_identifier(node);
+    }
+}
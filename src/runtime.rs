@@ -0,0 +1,583 @@
+//! State shared by everything evaluated in one interpreter session.
+//!
+//! Every `Environment` carries a handle to its session's `Runtime`, which tracks the call stack,
+//! enforces the `Limits` of each evaluation, decides what integer `Overflow` does, can be cancelled
+//! through an `Interrupt`, and lets tools such as the debugger observe evaluation through a `Hook`.
+//! It also keeps every module imported so far, so each file is only evaluated once, and holds
+//! the `Permissions` the host granted and the arguments it passed to the script.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::environment::Environment;
+use crate::object::{Error, ErrorKind, Module, StructType};
+use crate::token::Span;
+
+/// The call depth allowed by default, low enough that the evaluator's own recursion fits in a
+/// main thread's stack.
+pub const DEFAULT_MAX_DEPTH: usize = 1000;
+
+/// How many steps run between looks at the clock.
+const DEADLINE_INTERVAL: u64 = 256;
+
+/// What one top-level evaluation may use before it fails with an error of the matching
+/// `ErrorKind`. Only the call depth is limited by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    steps: Option<u64>,
+    depth: usize,
+    allocations: Option<u64>,
+    collection_size: Option<usize>,
+    time: Option<Duration>,
+}
+
+impl Limits {
+    /// Limit the number of expressions evaluated.
+    pub const fn with_steps(mut self, steps: u64) -> Self {
+        self.steps = Some(steps);
+        self
+    }
+
+    /// Limit the number of nested calls.
+    pub const fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Limit the number of functions, scopes and other values created on the heap.
+    pub const fn with_allocations(mut self, allocations: u64) -> Self {
+        self.allocations = Some(allocations);
+        self
+    }
+
+    /// Limit the number of elements in any one collection.
+    pub const fn with_collection_size(mut self, collection_size: usize) -> Self {
+        self.collection_size = Some(collection_size);
+        self
+    }
+
+    /// Limit the wall-clock time spent.
+    pub const fn with_time(mut self, time: Duration) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    pub const fn steps(&self) -> Option<u64> {
+        self.steps
+    }
+
+    pub const fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub const fn allocations(&self) -> Option<u64> {
+        self.allocations
+    }
+
+    pub const fn collection_size(&self) -> Option<usize> {
+        self.collection_size
+    }
+
+    pub const fn time(&self) -> Option<Duration> {
+        self.time
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            steps: None,
+            depth: DEFAULT_MAX_DEPTH,
+            allocations: None,
+            collection_size: None,
+            time: None,
+        }
+    }
+}
+
+/// What integer arithmetic does when a result doesn't fit in 64 bits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Overflow {
+    /// Fail with an "integer overflow" error.
+    #[default]
+    Error,
+    /// Carry on with a `BigInteger`.
+    Promote,
+}
+
+/// Which files or environment variables one kind of access is granted for.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+enum Grant<T> {
+    #[default]
+    Nothing,
+    Only(Vec<T>),
+    Everything,
+}
+
+impl<T> Grant<T> {
+    fn add(&mut self, item: T) {
+        match *self {
+            Self::Nothing => *self = Self::Only(vec![item]),
+            Self::Only(ref mut items) => items.push(item),
+            Self::Everything => {}
+        }
+    }
+
+    fn allows(&self, covers: impl FnMut(&T) -> bool) -> bool {
+        match *self {
+            Self::Nothing => false,
+            Self::Only(ref items) => items.iter().any(covers),
+            Self::Everything => true,
+        }
+    }
+}
+
+/// What a script may do outside the interpreter. Nothing is allowed by default: the host grants
+/// each capability explicitly, as `monkey --allow-read=./data` does.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Permissions {
+    read: Grant<PathBuf>,
+    write: Grant<PathBuf>,
+    env: Grant<String>,
+    process: bool,
+}
+
+impl Permissions {
+    /// Allow reading `path`, and everything in it if it is a directory.
+    pub fn allow_read(mut self, path: impl Into<PathBuf>) -> Self {
+        self.read.add(path.into());
+        self
+    }
+
+    pub fn allow_read_all(mut self) -> Self {
+        self.read = Grant::Everything;
+        self
+    }
+
+    /// Allow writing `path`, and anything in it if it is a directory.
+    pub fn allow_write(mut self, path: impl Into<PathBuf>) -> Self {
+        self.write.add(path.into());
+        self
+    }
+
+    pub fn allow_write_all(mut self) -> Self {
+        self.write = Grant::Everything;
+        self
+    }
+
+    /// Allow reading the environment variable `name`.
+    pub fn allow_env(mut self, name: impl Into<String>) -> Self {
+        self.env.add(name.into());
+        self
+    }
+
+    pub fn allow_env_all(mut self) -> Self {
+        self.env = Grant::Everything;
+        self
+    }
+
+    /// Allow reading the script's arguments and ending the process with `exit`.
+    pub const fn allow_process(mut self) -> Self {
+        self.process = true;
+        self
+    }
+
+    /// Fail unless the file or directory at `path` may be read.
+    pub fn check_read(&self, path: &Path) -> Result<(), Error> {
+        check_path(&self.read, path, "read", "--allow-read")
+    }
+
+    /// Fail unless the file at `path` may be written.
+    pub fn check_write(&self, path: &Path) -> Result<(), Error> {
+        check_path(&self.write, path, "write", "--allow-write")
+    }
+
+    /// Fail unless the environment variable `name` may be read.
+    pub fn check_env(&self, name: &str) -> Result<(), Error> {
+        if self.env.allows(|granted| granted == name) {
+            return Ok(());
+        }
+
+        Err(denied(format!(
+            "access to the environment variable {name:?} (grant it with --allow-env)"
+        )))
+    }
+
+    /// Fail unless the script may do `what` to its own process.
+    pub fn check_process(&self, what: &str) -> Result<(), Error> {
+        if self.process {
+            return Ok(());
+        }
+
+        Err(denied(format!("{what} (grant it with --allow-process)")))
+    }
+}
+
+/// Paths are compared once symbolic links and `..` are resolved, so a grant for `data` doesn't
+/// cover `data/../secret`. A path that can't be resolved is only covered by a grant for
+/// everything, which leaves the file system to say what is wrong with it.
+fn check_path(grant: &Grant<PathBuf>, path: &Path, access: &str, flag: &str) -> Result<(), Error> {
+    if *grant == Grant::Everything {
+        return Ok(());
+    }
+
+    if let Some(resolved) = resolve(path)
+        && grant
+            .allows(|granted| resolve(granted).is_some_and(|granted| resolved.starts_with(granted)))
+    {
+        return Ok(());
+    }
+
+    Err(denied(format!(
+        "{access} access to {:?} (grant it with {flag})",
+        path.display().to_string()
+    )))
+}
+
+/// The canonical form of `path`, or of the directory it would be created in.
+fn resolve(path: &Path) -> Option<PathBuf> {
+    if let Ok(path) = path.canonicalize() {
+        return Some(path);
+    }
+
+    let name = path.file_name()?;
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    parent.canonicalize().ok().map(|parent| parent.join(name))
+}
+
+fn denied(what: String) -> Error {
+    Error::with_kind(
+        ErrorKind::PermissionDenied,
+        format!("permission denied: {what}"),
+    )
+}
+
+/// What the evaluator should do after a hook has looked at a statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    /// Stop evaluating; the running program fails with a "terminated" error.
+    Terminate,
+}
+
+/// Observes evaluation. Installed with `Runtime::set_hook`.
+pub trait Hook {
+    /// Called before each statement runs, with the call stack as it is at that point.
+    ///
+    /// The hook may evaluate code of its own (e.g. in one of the frames); it is not called
+    /// recursively for statements run that way.
+    fn statement(&mut self, runtime: &Runtime, span: Span) -> Control;
+}
+
+/// Cancels a running evaluation from another thread or a signal handler. Cloning it gives
+/// another handle to the same flag.
+///
+/// The evaluation fails with an "interrupted" error at its next step. A request made while
+/// nothing is running is dropped when the next top-level evaluation starts.
+#[derive(Debug, Clone, Default)]
+pub struct Interrupt(Arc<AtomicBool>);
+
+impl Interrupt {
+    pub fn interrupt(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_interrupted(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// The underlying flag, e.g. for `signal_hook::flag::register`.
+    pub fn flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.0)
+    }
+
+    fn clear(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+}
+
+/// One active call, or the program itself at the bottom of the stack.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    name: String,
+    env: Environment,
+    span: Span,
+}
+
+impl Frame {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The environment the frame's statements run in.
+    pub const fn env(&self) -> &Environment {
+        &self.env
+    }
+
+    /// The statement the frame is currently running.
+    pub const fn span(&self) -> Span {
+        self.span
+    }
+}
+
+#[derive(Default)]
+pub struct Runtime {
+    hook: RefCell<Option<Box<dyn Hook>>>,
+    frames: RefCell<Vec<Frame>>,
+    limits: Cell<Limits>,
+    overflow: Cell<Overflow>,
+    steps: Cell<u64>,
+    allocations: Cell<u64>,
+    deadline: Cell<Option<Instant>>,
+    interrupt: Interrupt,
+    /// The files being evaluated, innermost last: the program's own, if it has one, and then
+    /// each module being imported.
+    files: RefCell<Vec<PathBuf>>,
+    /// Every module evaluated so far, by canonical path.
+    modules: RefCell<HashMap<PathBuf, Module>>,
+    permissions: RefCell<Permissions>,
+    /// What the host passed to the script, as returned by `args()`.
+    args: RefCell<Vec<String>>,
+    /// The struct types of values built by builtins, like the objects from `json_parse`.
+    struct_types: RefCell<Vec<StructType>>,
+}
+
+impl Runtime {
+    pub fn set_limits(&self, limits: Limits) {
+        self.limits.set(limits);
+    }
+
+    pub fn limits(&self) -> Limits {
+        self.limits.get()
+    }
+
+    pub fn set_overflow(&self, overflow: Overflow) {
+        self.overflow.set(overflow);
+    }
+
+    pub fn overflow(&self) -> Overflow {
+        self.overflow.get()
+    }
+
+    /// A handle for cancelling whatever this runtime is evaluating.
+    pub fn interrupt_handle(&self) -> Interrupt {
+        self.interrupt.clone()
+    }
+
+    /// Fail if a collection of `len` elements is larger than the limits allow.
+    pub fn check_size(&self, len: usize) -> Result<(), Error> {
+        match self.limits().collection_size {
+            Some(limit) if len > limit => Err(Error::with_kind(
+                ErrorKind::SizeLimit,
+                format!("collection size limit of {limit} exceeded"),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn set_permissions(&self, permissions: Permissions) {
+        *self.permissions.borrow_mut() = permissions;
+    }
+
+    pub fn permissions(&self) -> Permissions {
+        self.permissions.borrow().clone()
+    }
+
+    /// Set the arguments the script gets from `args()`, if it is allowed to.
+    pub fn set_args(&self, args: Vec<String>) {
+        *self.args.borrow_mut() = args;
+    }
+
+    pub fn args(&self) -> Vec<String> {
+        self.args.borrow().clone()
+    }
+
+    /// Set the file the program was read from, which its imports are found relative to.
+    /// Without one, they are found relative to the current directory.
+    pub fn set_file(&self, path: &Path) {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_owned());
+        *self.files.borrow_mut() = vec![path];
+    }
+
+    /// The files being evaluated, outermost first.
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.files.borrow().clone()
+    }
+
+    pub(crate) fn enter_file(&self, path: PathBuf) {
+        self.files.borrow_mut().push(path);
+    }
+
+    pub(crate) fn exit_file(&self) {
+        _ = self.files.borrow_mut().pop();
+    }
+
+    /// The module already evaluated from the file at the canonical `path`.
+    pub(crate) fn module(&self, path: &Path) -> Option<Module> {
+        self.modules.borrow().get(path).cloned()
+    }
+
+    pub(crate) fn add_module(&self, path: PathBuf, module: Module) {
+        _ = self.modules.borrow_mut().insert(path, module);
+    }
+
+    /// The struct type named `name` with exactly `fields`, made the first time it is needed, so
+    /// that values built with it compare equal when their fields do.
+    pub(crate) fn struct_type(&self, name: &str, fields: &[String]) -> StructType {
+        let mut types = self.struct_types.borrow_mut();
+
+        if let Some(ty) = types
+            .iter()
+            .find(|ty| ty.name() == name && ty.fields() == fields)
+        {
+            return ty.clone();
+        }
+
+        let ty = StructType::new(name.to_owned(), fields.to_vec());
+        types.push(ty.clone());
+        ty
+    }
+
+    /// Whether `ty` was made by `struct_type`.
+    pub(crate) fn has_struct_type(&self, ty: &StructType) -> bool {
+        self.struct_types.borrow().contains(ty)
+    }
+
+    pub fn set_hook(&self, hook: Box<dyn Hook>) {
+        *self.hook.borrow_mut() = Some(hook);
+    }
+
+    pub fn take_hook(&self) -> Option<Box<dyn Hook>> {
+        self.hook.borrow_mut().take()
+    }
+
+    /// The call stack, innermost frame last.
+    pub fn frames(&self) -> Vec<Frame> {
+        self.frames.borrow().clone()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.frames.borrow().len()
+    }
+
+    /// Reset the budget for a new top-level evaluation.
+    pub(crate) fn start(&self) {
+        self.steps.set(0);
+        self.allocations.set(0);
+        self.interrupt.clear();
+        self.deadline
+            .set(self.limits().time.map(|time| Instant::now() + time));
+    }
+
+    /// Charge one evaluation step, failing once the step or time limit is used up or the
+    /// evaluation has been interrupted.
+    pub(crate) fn step(&self) -> Result<(), Error> {
+        if self.interrupt.is_interrupted() {
+            let message = "interrupted".to_owned();
+            return Err(Error::with_kind(ErrorKind::Interrupted, message));
+        }
+
+        let steps = self.steps.get() + 1;
+        self.steps.set(steps);
+
+        if let Some(limit) = self.limits().steps
+            && steps > limit
+        {
+            let message = format!("step limit of {limit} exceeded");
+            return Err(Error::with_kind(ErrorKind::StepLimit, message));
+        }
+
+        if steps.is_multiple_of(DEADLINE_INTERVAL)
+            && let Some(deadline) = self.deadline.get()
+            && Instant::now() >= deadline
+        {
+            let time = self.limits().time.unwrap_or_default();
+            let message = format!("time limit of {time:?} exceeded");
+            return Err(Error::with_kind(ErrorKind::Deadline, message));
+        }
+
+        Ok(())
+    }
+
+    /// Charge `count` newly allocated values.
+    pub(crate) fn allocate(&self, count: u64) -> Result<(), Error> {
+        let allocations = self.allocations.get().saturating_add(count);
+        self.allocations.set(allocations);
+
+        match self.limits().allocations {
+            Some(limit) if allocations > limit => Err(Error::with_kind(
+                ErrorKind::AllocationLimit,
+                format!("allocation limit of {limit} exceeded"),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Push a frame, failing if that would nest more calls than the limits allow. The program's
+    /// own frame doesn't count as a call.
+    pub(crate) fn enter(&self, name: String, env: Environment, span: Span) -> Result<(), Error> {
+        let mut frames = self.frames.borrow_mut();
+        let limit = self.limits().depth;
+
+        if frames.len() > limit {
+            let message = format!("call depth limit of {limit} exceeded");
+            return Err(Error::with_kind(ErrorKind::DepthLimit, message));
+        }
+
+        frames.push(Frame { name, env, span });
+        Ok(())
+    }
+
+    pub(crate) fn exit(&self) {
+        _ = self.frames.borrow_mut().pop();
+    }
+
+    /// Record that the innermost frame is about to run the statement at `span`, and let the hook
+    /// see it.
+    pub(crate) fn statement(&self, span: Span) -> Control {
+        if let Some(frame) = self.frames.borrow_mut().last_mut() {
+            frame.span = span;
+        }
+
+        // Take the hook out while it runs, so that code it evaluates doesn't re-enter it.
+        let Some(mut hook) = self.take_hook() else {
+            return Control::Continue;
+        };
+
+        let control = hook.statement(self, span);
+        let mut slot = self.hook.borrow_mut();
+
+        if slot.is_none() {
+            *slot = Some(hook);
+        }
+
+        control
+    }
+}
+
+impl fmt::Debug for Runtime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Runtime")
+            .field("hook", &self.hook.borrow().is_some())
+            .field("frames", &self.frames.borrow().len())
+            .field("limits", &self.limits.get())
+            .field("overflow", &self.overflow.get())
+            .field("steps", &self.steps.get())
+            .field("allocations", &self.allocations.get())
+            .field("files", &self.files.borrow())
+            .field("modules", &self.modules.borrow().len())
+            .field("permissions", &self.permissions.borrow())
+            .field("args", &self.args.borrow())
+            .field("struct_types", &self.struct_types.borrow().len())
+            .finish()
+    }
+}
@@ -0,0 +1,181 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression::{self, Identifier};
+use crate::object::{Error, Object};
+use crate::parser::{Parse, Parser, ParserError};
+use crate::precedence::Precedence;
+use crate::statement::{Expression, Statement};
+use crate::token::{Span, Token, TokenKind};
+
+/// `name = value;` or `name.field = value;`, rebinding a name that is already bound. Structs are
+/// values, so assigning to a field rebinds the name to an updated copy.
+#[derive(Debug, Clone)]
+pub struct Assign {
+    name: Identifier,
+    fields: Vec<Identifier>,
+    token: Token,
+    value: expression::Expression,
+}
+
+impl Assign {
+    pub fn new(
+        name: Identifier,
+        fields: Vec<Identifier>,
+        token: Token,
+        value: expression::Expression,
+    ) -> Self {
+        Self {
+            name,
+            fields,
+            token,
+            value,
+        }
+    }
+
+    pub const fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    /// The path of fields after the name, empty when assigning to the name itself.
+    pub fn fields(&self) -> &[Identifier] {
+        &self.fields
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub const fn value(&self) -> &expression::Expression {
+        &self.value
+    }
+
+    pub const fn name_mut(&mut self) -> &mut Identifier {
+        &mut self.name
+    }
+
+    pub const fn value_mut(&mut self) -> &mut expression::Expression {
+        &mut self.value
+    }
+
+    pub fn span(&self) -> Span {
+        self.name.span().to(self.value.span())
+    }
+
+    /// Split `a.b.c` into `a` and `[b, c]`, or `None` if `target` can't be assigned to.
+    fn target(target: expression::Expression) -> Option<(Identifier, Vec<Identifier>)> {
+        match target {
+            expression::Expression::Identifier(name) => Some((name, Vec::new())),
+            expression::Expression::FieldAccess(access) => {
+                let field = access.field().clone();
+                let (name, mut fields) = Self::target(access.left().clone())?;
+                fields.push(field);
+                Some((name, fields))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parses an expression statement, or an assignment if the expression is followed by `=`.
+impl Parse<Statement> for Assign {
+    fn parse(parser: &mut Parser<'_>) -> Result<Statement, ParserError> {
+        let target = expression::Expression::parse(parser, Precedence::Lowest)?;
+
+        let statement = if parser
+            .token()
+            .is_some_and(|token| token.kind() == TokenKind::Assign)
+        {
+            let span = target.span();
+            let (name, fields) =
+                Self::target(target).ok_or(ParserError::InvalidAssignTarget { span })?;
+
+            let token = parser.expect_token_with_kind(TokenKind::Assign)?;
+            let value = expression::Expression::parse(parser, Precedence::Lowest)?;
+            Self::new(name, fields, token, value).into()
+        } else {
+            Expression::new(target).into()
+        };
+
+        if parser
+            .token()
+            .is_some_and(|token| token.kind() == TokenKind::Semicolon)
+        {
+            parser.advance();
+        }
+
+        Ok(statement)
+    }
+}
+
+/// `current` with the field at the end of `fields` set to `value`.
+fn update(current: Object, fields: &[Identifier], value: Object) -> Object {
+    let Some((field, rest)) = fields.split_first() else {
+        return value;
+    };
+
+    let field = field.token().literal();
+
+    let Object::Struct(ref inner) = current else {
+        return Error::new(format!("cannot assign to field {field:?} of {current}")).into();
+    };
+
+    let Some(old) = inner.get(field) else {
+        let message = format!("struct {} has no field {field:?}", inner.ty().name());
+        return Error::new(message).into();
+    };
+
+    let new = update(old.clone(), rest, value);
+
+    if matches!(new, Object::Error(_)) {
+        return new;
+    }
+
+    inner.with(field, new).unwrap().into()
+}
+
+impl Evaluate for Assign {
+    fn evaluate(&self, env: &mut Environment) -> Object {
+        let value = self.value.evaluate(env);
+
+        if matches!(value, Object::Error(_)) {
+            return value;
+        }
+
+        let identifier = self.name.token().literal();
+
+        let Some(current) = env.get(identifier) else {
+            return Error::new(format!("identifier {identifier:?} is not defined")).into();
+        };
+
+        let updated = update(current, &self.fields, value.clone());
+
+        if matches!(updated, Object::Error(_)) {
+            return updated;
+        }
+
+        if !self.fields.is_empty()
+            && let Err(err) = env.runtime().allocate(1)
+        {
+            return err.into();
+        }
+
+        _ = env.assign(identifier, updated);
+        value
+    }
+}
+
+impl fmt::Display for Assign {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name.fmt(f)?;
+
+        for field in &self.fields {
+            write!(f, ".{field}")?;
+        }
+
+        f.write_str(" = ")?;
+        self.value.fmt(f)?;
+        f.write_str(";")
+    }
+}
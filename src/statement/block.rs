@@ -1,25 +1,58 @@
+use std::fmt;
+
 use crate::environment::Environment;
 use crate::evaluator::Evaluate;
 use crate::object::{NULL, Object};
 use crate::parser::{Parse, Parser, ParserError};
 use crate::statement::Statement;
-use crate::token::TokenKind;
+use crate::token::{Span, Token, TokenKind};
 
 #[derive(Debug, Clone)]
 pub struct Block {
+    token: Token,
     statements: Vec<Statement>,
+    rbrace: Token,
 }
 
 impl Block {
-    pub fn new(statements: Vec<Statement>) -> Self {
-        Self { statements }
+    pub fn new(token: Token, statements: Vec<Statement>, rbrace: Token) -> Self {
+        Self {
+            token,
+            statements,
+            rbrace,
+        }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn statements(&self) -> &[Statement] {
+        &self.statements
+    }
+
+    pub fn statements_mut(&mut self) -> &mut Vec<Statement> {
+        &mut self.statements
+    }
+
+    pub const fn span(&self) -> Span {
+        self.token.span().to(self.rbrace.span())
     }
 }
 
 impl Parse for Block {
     fn parse(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
-        _ = parser.expect_token_with_kind(TokenKind::LBrace)?;
+        let token = parser.expect_token_with_kind(TokenKind::LBrace)?;
+        parser.nested(|parser| {
+            parser.with_struct_literals(|parser| {
+                parser.in_block(|parser| Self::parse_body(parser, token))
+            })
+        })
+    }
+}
 
+impl Block {
+    fn parse_body(parser: &mut Parser<'_>, token: Token) -> Result<Self, ParserError> {
         let mut statements = Vec::new();
 
         while parser
@@ -28,17 +61,18 @@ impl Parse for Block {
         {
             match Statement::parse(parser) {
                 Ok(statement) => statements.push(statement),
-                Err(err) => tracing::error!("{err}"), // TODO: The book doesn't handle errors here.
+                Err(err) => {
+                    parser.report(err);
+                    parser.advance();
+                }
             }
-
-            // parser.advance();
         }
 
         // TODO: Better error message would be nice. Forgetting to close the curly braces should
         // result in a nice syntax error.
-        _ = parser.expect_token_with_kind(TokenKind::RBrace)?;
+        let rbrace = parser.expect_token_with_kind(TokenKind::RBrace)?;
 
-        Ok(Self::new(statements))
+        Ok(Self::new(token, statements, rbrace))
     }
 }
 
@@ -49,7 +83,10 @@ impl Evaluate for Block {
         for statement in &self.statements {
             value = statement.evaluate(env);
 
-            if matches!(value, Object::Return(_) | Object::Error(_)) {
+            if matches!(
+                value,
+                Object::Return(_) | Object::Error(_) | Object::Break(_) | Object::Continue(_)
+            ) {
                 break;
             }
         }
@@ -57,3 +94,20 @@ impl Evaluate for Block {
         value
     }
 }
+
+impl fmt::Display for Block {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.statements.is_empty() {
+            return f.write_str("{}");
+        }
+
+        f.write_str("{ ")?;
+
+        for statement in &self.statements {
+            statement.fmt(f)?;
+            f.write_str(" ")?;
+        }
+
+        f.write_str("}")
+    }
+}
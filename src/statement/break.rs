@@ -0,0 +1,69 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::object::{self, Object};
+use crate::parser::{Parse, Parser, ParserError};
+use crate::token::{Span, Token, TokenKind};
+
+#[derive(Debug, Clone)]
+pub struct Break {
+    token: Token,
+}
+
+impl Break {
+    pub fn new(token: Token) -> Self {
+        Self { token }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub const fn span(&self) -> Span {
+        self.token.span()
+    }
+}
+
+impl Parse for Break {
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
+        let token = parse_loop_control(parser, TokenKind::Break)?;
+        Ok(Self::new(token))
+    }
+}
+
+impl Evaluate for Break {
+    fn evaluate(&self, _: &mut Environment) -> Object {
+        object::Break::new().into()
+    }
+}
+
+impl fmt::Display for Break {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("break;")
+    }
+}
+
+/// Parse `break` or `continue`, reporting it if there is no loop around it to apply to.
+pub(super) fn parse_loop_control(
+    parser: &mut Parser<'_>,
+    keyword: TokenKind,
+) -> Result<Token, ParserError> {
+    let token = parser.expect_token_with_kind(keyword)?;
+
+    if !parser.in_loop() {
+        parser.report(ParserError::OutsideLoop {
+            keyword,
+            span: token.span(),
+        });
+    }
+
+    if parser
+        .token()
+        .is_some_and(|token| token.kind() == TokenKind::Semicolon)
+    {
+        parser.advance();
+    }
+
+    Ok(token)
+}
@@ -0,0 +1,46 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::object::{self, Object};
+use crate::parser::{Parse, Parser, ParserError};
+use crate::statement::r#break::parse_loop_control;
+use crate::token::{Span, Token, TokenKind};
+
+#[derive(Debug, Clone)]
+pub struct Continue {
+    token: Token,
+}
+
+impl Continue {
+    pub fn new(token: Token) -> Self {
+        Self { token }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub const fn span(&self) -> Span {
+        self.token.span()
+    }
+}
+
+impl Parse for Continue {
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
+        let token = parse_loop_control(parser, TokenKind::Continue)?;
+        Ok(Self::new(token))
+    }
+}
+
+impl Evaluate for Continue {
+    fn evaluate(&self, _: &mut Environment) -> Object {
+        object::Continue::new().into()
+    }
+}
+
+impl fmt::Display for Continue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("continue;")
+    }
+}
@@ -0,0 +1,229 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression::Identifier;
+use crate::object::{EnumType, Error, Object};
+use crate::parser::{Parse, Parser, ParserError};
+use crate::token::{Span, Token, TokenKind};
+
+/// `Name` or `Name(field, ...)` in an `enum` declaration. The field names only document what the
+/// values are; variants are built and matched by position.
+#[derive(Debug, Clone)]
+pub struct Variant {
+    name: Identifier,
+    fields: Vec<Identifier>,
+    end: Span,
+}
+
+impl Variant {
+    pub fn new(name: Identifier, fields: Vec<Identifier>, end: Span) -> Self {
+        Self { name, fields, end }
+    }
+
+    pub const fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn fields(&self) -> &[Identifier] {
+        &self.fields
+    }
+
+    pub const fn span(&self) -> Span {
+        self.name.span().to(self.end)
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name.fmt(f)?;
+
+        if self.fields.is_empty() {
+            return Ok(());
+        }
+
+        f.write_str("(")?;
+
+        for (index, field) in self.fields.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+
+            field.fmt(f)?;
+        }
+
+        f.write_str(")")
+    }
+}
+
+/// `enum Name { Variant, Variant(field, ...), ... }`, binding `Name` to a new enum type.
+#[derive(Debug, Clone)]
+pub struct Enum {
+    token: Token,
+    name: Identifier,
+    variants: Vec<Variant>,
+    rbrace: Token,
+}
+
+impl Enum {
+    pub fn new(token: Token, name: Identifier, variants: Vec<Variant>, rbrace: Token) -> Self {
+        Self {
+            token,
+            name,
+            variants,
+            rbrace,
+        }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub const fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn variants(&self) -> &[Variant] {
+        &self.variants
+    }
+
+    pub const fn name_mut(&mut self) -> &mut Identifier {
+        &mut self.name
+    }
+
+    pub const fn span(&self) -> Span {
+        self.token.span().to(self.rbrace.span())
+    }
+}
+
+impl Parse for Enum {
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::Enum)?;
+
+        let name = parser
+            .expect_token_with_kind(TokenKind::Identifier)
+            .map(Identifier::new)?;
+
+        _ = parser.expect_token_with_kind(TokenKind::LBrace)?;
+
+        let mut variants = Vec::<Variant>::new();
+
+        while parser
+            .token()
+            .is_some_and(|token| token.kind() != TokenKind::RBrace)
+        {
+            let name = parser
+                .expect_token_with_kind(TokenKind::Identifier)
+                .map(Identifier::new)?;
+
+            if variants
+                .iter()
+                .any(|other| other.name.token().literal() == name.token().literal())
+            {
+                parser.report(ParserError::DuplicateVariant {
+                    variant: name.token().literal().to_owned(),
+                    span: name.span(),
+                });
+            }
+
+            let mut fields = Vec::new();
+            let mut end = name.span();
+
+            if parser
+                .token()
+                .is_some_and(|token| token.kind() == TokenKind::LParenthesis)
+            {
+                parser.advance();
+
+                while parser
+                    .token()
+                    .is_some_and(|token| token.kind() != TokenKind::RParenthesis)
+                {
+                    fields.push(
+                        parser
+                            .expect_token_with_kind(TokenKind::Identifier)
+                            .map(Identifier::new)?,
+                    );
+
+                    if parser
+                        .token()
+                        .is_none_or(|token| token.kind() != TokenKind::Comma)
+                    {
+                        break;
+                    }
+
+                    parser.advance();
+                }
+
+                end = parser
+                    .expect_token_with_kind(TokenKind::RParenthesis)?
+                    .span();
+            }
+
+            variants.push(Variant::new(name, fields, end));
+
+            if parser
+                .token()
+                .is_none_or(|token| token.kind() != TokenKind::Comma)
+            {
+                break;
+            }
+
+            parser.advance();
+        }
+
+        let rbrace = parser.expect_token_with_kind(TokenKind::RBrace)?;
+
+        if parser
+            .token()
+            .is_some_and(|token| token.kind() == TokenKind::Semicolon)
+        {
+            parser.advance();
+        }
+
+        Ok(Self::new(token, name, variants, rbrace))
+    }
+}
+
+impl Evaluate for Enum {
+    fn evaluate(&self, env: &mut Environment) -> Object {
+        let name = self.name.token().literal();
+        let variants = self
+            .variants
+            .iter()
+            .map(|variant| {
+                (
+                    variant.name.token().literal().to_owned(),
+                    variant.fields.len(),
+                )
+            })
+            .collect();
+
+        let value = Object::from(EnumType::new(name.to_owned(), variants));
+
+        match env.set(name.to_owned(), value.clone()) {
+            Some(_) => {
+                let message = format!("variable named {name:?} already exists");
+                Error::new(message).into()
+            }
+            None => value,
+        }
+    }
+}
+
+impl fmt::Display for Enum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "enum {} {{", self.name)?;
+
+        for (index, variant) in self.variants.iter().enumerate() {
+            f.write_str(if index > 0 { ", " } else { " " })?;
+            variant.fmt(f)?;
+        }
+
+        if self.variants.is_empty() {
+            f.write_str("}")
+        } else {
+            f.write_str(" }")
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression::Identifier;
+use crate::object::Object;
+use crate::parser::{Parse, Parser, ParserError};
+use crate::statement::Statement;
+use crate::token::{Span, Token, TokenKind};
+
+/// `export let ...`, `export struct ...` or `export enum ...` at the top level of a file, making
+/// what it binds available to files that import this one.
+#[derive(Debug, Clone)]
+pub struct Export {
+    token: Token,
+    statement: Box<Statement>,
+}
+
+impl Export {
+    pub fn new(token: Token, statement: Statement) -> Self {
+        Self {
+            token,
+            statement: Box::new(statement),
+        }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn statement(&self) -> &Statement {
+        &self.statement
+    }
+
+    pub fn statement_mut(&mut self) -> &mut Statement {
+        &mut self.statement
+    }
+
+    /// The names being exported, in source order.
+    pub fn names(&self) -> Vec<&Identifier> {
+        match *self.statement {
+            Statement::Let(ref inner) => inner.pattern().bindings(),
+            Statement::Struct(ref inner) => vec![inner.name()],
+            Statement::Enum(ref inner) => vec![inner.name()],
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        self.token.span().to(self.statement.span())
+    }
+}
+
+impl Parse for Export {
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::Export)?;
+
+        if !parser.at_top_level() {
+            parser.report(ParserError::InvalidExport { span: token.span() });
+        }
+
+        match parser.token().map(Token::kind) {
+            Some(TokenKind::Let | TokenKind::Struct | TokenKind::Enum) => {}
+            Some(_) => {
+                return Err(ParserError::InvalidExport {
+                    span: parser.span(),
+                });
+            }
+            None => {
+                return Err(ParserError::UnexpectedEof {
+                    span: parser.span(),
+                });
+            }
+        }
+
+        let statement = Statement::parse(parser)?;
+        Ok(Self::new(token, statement))
+    }
+}
+
+impl Evaluate for Export {
+    fn evaluate(&self, env: &mut Environment) -> Object {
+        self.statement.evaluate(env)
+    }
+}
+
+impl fmt::Display for Export {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "export {}", self.statement)
+    }
+}
@@ -1,10 +1,12 @@
+use std::fmt;
+
 use crate::environment::Environment;
 use crate::evaluator::Evaluate;
 use crate::expression;
 use crate::object::Object;
 use crate::parser::{Parse, Parser, ParserError};
 use crate::precedence::Precedence;
-use crate::token::TokenKind;
+use crate::token::{Span, TokenKind};
 
 #[derive(Debug, Clone)]
 pub struct Expression {
@@ -15,6 +17,18 @@ impl Expression {
     pub fn new(expression: expression::Expression) -> Self {
         Self { expression }
     }
+
+    pub const fn expression(&self) -> &expression::Expression {
+        &self.expression
+    }
+
+    pub const fn expression_mut(&mut self) -> &mut expression::Expression {
+        &mut self.expression
+    }
+
+    pub fn span(&self) -> Span {
+        self.expression.span()
+    }
 }
 
 impl Parse for Expression {
@@ -37,3 +51,10 @@ impl Evaluate for Expression {
         self.expression.evaluate(env)
     }
 }
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.expression.fmt(f)?;
+        f.write_str(";")
+    }
+}
@@ -0,0 +1,126 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression::{self, Identifier};
+use crate::object::{Error, NULL, Object};
+use crate::parser::{Parse, Parser, ParserError};
+use crate::precedence::Precedence;
+use crate::statement::{Block, run_iteration};
+use crate::token::{Span, Token, TokenKind};
+
+/// `for (variable in iterable) { ... }`.
+#[derive(Debug, Clone)]
+pub struct For {
+    token: Token,
+    variable: Identifier,
+    iterable: expression::Expression,
+    body: Block,
+}
+
+impl For {
+    pub fn new(
+        token: Token,
+        variable: Identifier,
+        iterable: expression::Expression,
+        body: Block,
+    ) -> Self {
+        Self {
+            token,
+            variable,
+            iterable,
+            body,
+        }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub const fn variable(&self) -> &Identifier {
+        &self.variable
+    }
+
+    pub const fn iterable(&self) -> &expression::Expression {
+        &self.iterable
+    }
+
+    pub const fn body(&self) -> &Block {
+        &self.body
+    }
+
+    pub const fn variable_mut(&mut self) -> &mut Identifier {
+        &mut self.variable
+    }
+
+    pub const fn iterable_mut(&mut self) -> &mut expression::Expression {
+        &mut self.iterable
+    }
+
+    pub const fn body_mut(&mut self) -> &mut Block {
+        &mut self.body
+    }
+
+    pub const fn span(&self) -> Span {
+        self.token.span().to(self.body.span())
+    }
+}
+
+impl Parse for For {
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::For)?;
+        _ = parser.expect_token_with_kind(TokenKind::LParenthesis)?;
+
+        let variable = parser
+            .expect_token_with_kind(TokenKind::Identifier)
+            .map(Identifier::new)?;
+
+        _ = parser.expect_token_with_kind(TokenKind::In)?;
+        let iterable = expression::Expression::parse(parser, Precedence::Lowest)?;
+        _ = parser.expect_token_with_kind(TokenKind::RParenthesis)?;
+
+        let body = parser.in_loop_body(Block::parse)?;
+
+        if parser
+            .token()
+            .is_some_and(|token| token.kind() == TokenKind::Semicolon)
+        {
+            parser.advance();
+        }
+
+        Ok(Self::new(token, variable, iterable, body))
+    }
+}
+
+impl Evaluate for For {
+    fn evaluate(&self, env: &mut Environment) -> Object {
+        let iterable = self.iterable.evaluate(env);
+
+        let array = match iterable {
+            Object::Error(_) => return iterable,
+            Object::Array(ref inner) => inner,
+            _ => return Error::new(format!("cannot iterate over {iterable}")).into(),
+        };
+
+        let name = self.variable.token().literal();
+
+        for element in array.elements() {
+            let binding = Some((name, element.to_owned()));
+
+            if let Some(value) = run_iteration(&self.body, env, binding) {
+                return value;
+            }
+        }
+
+        NULL
+    }
+}
+
+impl fmt::Display for For {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "for ({} in ", self.variable)?;
+        self.iterable.fmt(f)?;
+        f.write_str(") ")?;
+        self.body.fmt(f)
+    }
+}
@@ -0,0 +1,172 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression::{FunctionLiteral, Identifier};
+use crate::object::{Error, NULL, Object};
+use crate::parser::{Parse, Parser, ParserError};
+use crate::token::{Span, Token, TokenKind};
+
+/// `fn name(...) { ... }` inside an `impl` block.
+#[derive(Debug, Clone)]
+pub struct Method {
+    name: Identifier,
+    function: FunctionLiteral,
+}
+
+impl Method {
+    pub const fn new(name: Identifier, function: FunctionLiteral) -> Self {
+        Self { name, function }
+    }
+
+    pub const fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub const fn function(&self) -> &FunctionLiteral {
+        &self.function
+    }
+
+    pub const fn function_mut(&mut self) -> &mut FunctionLiteral {
+        &mut self.function
+    }
+
+    pub fn span(&self) -> Span {
+        self.function.span()
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "fn {}", self.name)?;
+        self.function.fmt_signature_and_body(f)
+    }
+}
+
+/// `impl Name { fn ... }`, adding methods and associated functions to the struct bound to `Name`.
+#[derive(Debug, Clone)]
+pub struct Impl {
+    token: Token,
+    name: Identifier,
+    methods: Vec<Method>,
+    rbrace: Token,
+}
+
+impl Impl {
+    pub fn new(token: Token, name: Identifier, methods: Vec<Method>, rbrace: Token) -> Self {
+        Self {
+            token,
+            name,
+            methods,
+            rbrace,
+        }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub const fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn methods(&self) -> &[Method] {
+        &self.methods
+    }
+
+    pub const fn name_mut(&mut self) -> &mut Identifier {
+        &mut self.name
+    }
+
+    pub fn methods_mut(&mut self) -> &mut [Method] {
+        &mut self.methods
+    }
+
+    pub const fn span(&self) -> Span {
+        self.token.span().to(self.rbrace.span())
+    }
+}
+
+impl Parse for Impl {
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::Impl)?;
+
+        let name = parser
+            .expect_token_with_kind(TokenKind::Identifier)
+            .map(Identifier::new)?;
+
+        _ = parser.expect_token_with_kind(TokenKind::LBrace)?;
+
+        let mut methods = Vec::new();
+
+        while parser
+            .token()
+            .is_some_and(|token| token.kind() != TokenKind::RBrace)
+        {
+            let token = parser.expect_token_with_kind(TokenKind::Function)?;
+
+            let name = parser
+                .expect_token_with_kind(TokenKind::Identifier)
+                .map(Identifier::new)?;
+
+            let function = FunctionLiteral::parse_signature_and_body(parser, token)?;
+            methods.push(Method::new(name, function));
+        }
+
+        let rbrace = parser.expect_token_with_kind(TokenKind::RBrace)?;
+
+        if parser
+            .token()
+            .is_some_and(|token| token.kind() == TokenKind::Semicolon)
+        {
+            parser.advance();
+        }
+
+        Ok(Self::new(token, name, methods, rbrace))
+    }
+}
+
+impl Evaluate for Impl {
+    fn evaluate(&self, env: &mut Environment) -> Object {
+        let ty = match self.name.evaluate(env) {
+            Object::StructType(ty) => ty,
+            Object::Error(err) => return err.into(),
+            _ => {
+                let message = format!("{:?} is not a struct", self.name.token().literal());
+                return Error::new(message).into();
+            }
+        };
+
+        for method in &self.methods {
+            let function = match method.function.evaluate(env) {
+                Object::Function(function) => function,
+                value => return value,
+            };
+
+            let name = method.name.token().literal();
+
+            if !ty.define_method(name.to_owned(), function) {
+                let message = format!("struct {} already has a method {name:?}", ty.name());
+                return Error::new(message).into();
+            }
+        }
+
+        NULL
+    }
+}
+
+impl fmt::Display for Impl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "impl {} {{", self.name)?;
+
+        for method in &self.methods {
+            write!(f, " {method}")?;
+        }
+
+        if self.methods.is_empty() {
+            f.write_str("}")
+        } else {
+            f.write_str(" }")
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression::{Identifier, StringLiteral};
+use crate::lexer;
+use crate::module;
+use crate::object::{Error, Object};
+use crate::parser::{Parse, Parser, ParserError};
+use crate::token::{Span, Token, TokenKind};
+
+/// `import "lib/math.monkey" as math;`, binding `math` to what that file exports.
+#[derive(Debug, Clone)]
+pub struct Import {
+    token: Token,
+    path: StringLiteral,
+    name: Identifier,
+}
+
+impl Import {
+    pub fn new(token: Token, path: StringLiteral, name: Identifier) -> Self {
+        Self { token, path, name }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub const fn path(&self) -> &StringLiteral {
+        &self.path
+    }
+
+    pub const fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub const fn name_mut(&mut self) -> &mut Identifier {
+        &mut self.name
+    }
+
+    pub fn span(&self) -> Span {
+        self.token.span().to(self.name.span())
+    }
+}
+
+impl Parse for Import {
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::Import)?;
+
+        let path = parser.expect_token_with_kind(TokenKind::String)?;
+        let value = lexer::unescape(path.literal()).map_err(|error| ParserError::Lex {
+            error,
+            span: path.span(),
+        })?;
+
+        _ = parser.expect_token_with_kind(TokenKind::As)?;
+
+        let name = parser
+            .expect_token_with_kind(TokenKind::Identifier)
+            .map(Identifier::new)?;
+
+        if parser
+            .token()
+            .is_some_and(|token| token.kind() == TokenKind::Semicolon)
+        {
+            parser.advance();
+        }
+
+        Ok(Self::new(token, StringLiteral::new(path, value), name))
+    }
+}
+
+impl Evaluate for Import {
+    fn evaluate(&self, env: &mut Environment) -> Object {
+        let module = match module::import(self.path.value(), env) {
+            Ok(module) => module,
+            Err(err) => return err.into(),
+        };
+
+        let name = self.name.token().literal();
+
+        if env.contains(name) {
+            return Error::new(format!("variable named {name:?} already exists")).into();
+        }
+
+        _ = env.set(name.to_owned(), module.clone().into());
+        module.into()
+    }
+}
+
+impl fmt::Display for Import {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "import {} as {};", self.path, self.name)
+    }
+}
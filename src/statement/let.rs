@@ -1,40 +1,88 @@
+use std::fmt;
+
+use crate::annotation::Annotation;
 use crate::environment::Environment;
 use crate::evaluator::Evaluate;
-use crate::expression::{self, Identifier};
+use crate::expression;
 use crate::object::{Error, Object};
 use crate::parser::{Parse, Parser, ParserError};
+use crate::pattern::Pattern;
 use crate::precedence::Precedence;
-use crate::token::TokenKind;
+use crate::token::{Span, Token, TokenKind};
 
 #[derive(Debug, Clone)]
 pub struct Let {
-    name: Identifier,
+    token: Token,
+    pattern: Pattern,
+    annotation: Option<Annotation>,
     value: expression::Expression,
 }
 
 impl Let {
-    pub fn new(name: Identifier, value: expression::Expression) -> Self {
-        Self { name, value }
+    pub fn new(
+        token: Token,
+        pattern: Pattern,
+        annotation: Option<Annotation>,
+        value: expression::Expression,
+    ) -> Self {
+        Self {
+            token,
+            pattern,
+            annotation,
+            value,
+        }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    /// The name being bound, or the pattern taking the value apart.
+    pub const fn pattern(&self) -> &Pattern {
+        &self.pattern
+    }
+
+    pub const fn annotation(&self) -> Option<&Annotation> {
+        self.annotation.as_ref()
+    }
+
+    pub const fn value(&self) -> &expression::Expression {
+        &self.value
+    }
+
+    pub const fn pattern_mut(&mut self) -> &mut Pattern {
+        &mut self.pattern
+    }
+
+    pub const fn value_mut(&mut self) -> &mut expression::Expression {
+        &mut self.value
     }
 
-    pub const fn name(&self) -> &Identifier {
-        &self.name
+    pub fn span(&self) -> Span {
+        self.token.span().to(self.value.span())
     }
 }
 
 impl Parse for Let {
     fn parse(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
-        _ = parser.expect_token_with_kind(TokenKind::Let)?;
+        let token = parser.expect_token_with_kind(TokenKind::Let)?;
 
-        let name = parser
-            .expect_token_with_kind(TokenKind::Identifier)
-            .map(Identifier::new)?;
+        let pattern = Pattern::parse(parser)?;
+
+        let annotation = Annotation::parse_optional(parser, TokenKind::Colon)?;
 
         _ = parser.expect_token_with_kind(TokenKind::Assign)?;
 
         let value = expression::Expression::parse(parser, Precedence::Lowest)?;
 
-        Ok(Self::new(name, value))
+        if parser
+            .token()
+            .is_some_and(|token| token.kind() == TokenKind::Semicolon)
+        {
+            parser.advance();
+        }
+
+        Ok(Self::new(token, pattern, annotation, value))
     }
 }
 
@@ -46,16 +94,38 @@ impl Evaluate for Let {
             return value;
         }
 
-        let identifier = self.name().token().literal();
-        match env.store_mut().insert(identifier.to_owned(), value.clone()) {
-            Some(_) => {
-                let message = format!("variable named {identifier:?} already exists");
-                Error::new(message).into()
+        for name in self.pattern.bindings() {
+            let name = name.token().literal();
+
+            if env.contains(name) {
+                let message = format!("variable named {name:?} already exists");
+                return Error::new(message).into();
             }
-            None => match value {
-                Object::Return(inner) => inner.value().to_owned(),
-                _ => value,
-            },
         }
+
+        if let Err(err) = self.pattern.destructure(&value, env) {
+            return err;
+        }
+
+        match value {
+            Object::Return(inner) => inner.value().to_owned(),
+            _ => value,
+        }
+    }
+}
+
+impl fmt::Display for Let {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("let ")?;
+        self.pattern.fmt(f)?;
+
+        if let Some(ref annotation) = self.annotation {
+            f.write_str(": ")?;
+            annotation.fmt(f)?;
+        }
+
+        f.write_str(" = ")?;
+        self.value.fmt(f)?;
+        f.write_str(";")
     }
 }
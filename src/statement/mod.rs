@@ -1,18 +1,42 @@
+mod assign;
 mod block;
+mod r#break;
+mod r#continue;
+mod r#enum;
+mod export;
 mod expression;
+mod r#for;
+mod r#impl;
+mod import;
 mod r#let;
 mod r#return;
+mod r#struct;
+mod r#while;
+
+use std::collections::HashMap;
+use std::fmt;
 
 use crate::environment::Environment;
 use crate::evaluator::Evaluate;
-use crate::object::Object;
+use crate::object::{Error, NULL, Object};
 use crate::parser::{Parse, Parser, ParserError};
-use crate::token::TokenKind;
+use crate::runtime::Control;
+use crate::token::{Span, TokenKind};
 
+pub use assign::Assign;
 pub use block::Block;
+pub use r#break::Break;
+pub use r#continue::Continue;
+pub use r#enum::{Enum, Variant};
+pub use export::Export;
 pub use expression::Expression;
+pub use r#for::For;
+pub use r#impl::{Impl, Method};
+pub use import::Import;
 pub use r#let::Let;
 pub use r#return::Return;
+pub use r#struct::Struct;
+pub use r#while::While;
 
 #[derive(Debug, Clone)]
 pub enum Statement {
@@ -20,6 +44,37 @@ pub enum Statement {
     Return(Return),
     Expression(self::Expression),
     Block(Block),
+    While(While),
+    For(For),
+    Break(Break),
+    Continue(Continue),
+    Assign(Assign),
+    Struct(Struct),
+    Impl(Impl),
+    Enum(Enum),
+    Import(Import),
+    Export(Export),
+}
+
+impl Statement {
+    pub fn span(&self) -> Span {
+        match *self {
+            Self::Let(ref inner) => inner.span(),
+            Self::Return(ref inner) => inner.span(),
+            Self::Expression(ref inner) => inner.span(),
+            Self::Block(ref inner) => inner.span(),
+            Self::While(ref inner) => inner.span(),
+            Self::For(ref inner) => inner.span(),
+            Self::Break(ref inner) => inner.span(),
+            Self::Continue(ref inner) => inner.span(),
+            Self::Assign(ref inner) => inner.span(),
+            Self::Struct(ref inner) => inner.span(),
+            Self::Impl(ref inner) => inner.span(),
+            Self::Enum(ref inner) => inner.span(),
+            Self::Import(ref inner) => inner.span(),
+            Self::Export(ref inner) => inner.span(),
+        }
+    }
 }
 
 impl Parse for Statement {
@@ -30,6 +85,22 @@ impl Parse for Statement {
         match token.kind() {
             TokenKind::Let => Let::parse(parser).map(Statement::from),
             TokenKind::Return => Return::parse(parser).map(Statement::from),
+            TokenKind::While => While::parse(parser).map(Statement::from),
+            TokenKind::For => For::parse(parser).map(Statement::from),
+            TokenKind::Break => Break::parse(parser).map(Statement::from),
+            TokenKind::Continue => Continue::parse(parser).map(Statement::from),
+            TokenKind::Struct => Struct::parse(parser).map(Statement::from),
+            TokenKind::Impl => Impl::parse(parser).map(Statement::from),
+            TokenKind::Enum => Enum::parse(parser).map(Statement::from),
+            TokenKind::Import => Import::parse(parser).map(Statement::from),
+            TokenKind::Export => Export::parse(parser).map(Statement::from),
+            TokenKind::Identifier
+                if parser.peek().is_some_and(|token| {
+                    matches!(token.kind(), TokenKind::Assign | TokenKind::Dot)
+                }) =>
+            {
+                Assign::parse(parser)
+            }
             _ => self::Expression::parse(parser).map(Statement::from),
         }
     }
@@ -41,13 +112,75 @@ impl Evaluate for Statement {
             Self::Let(ref inner) => inner,
             Self::Return(ref inner) => inner,
             Self::Expression(ref inner) => inner,
-            Self::Block(ref inner) => inner,
+            Self::While(ref inner) => inner,
+            Self::For(ref inner) => inner,
+            Self::Break(ref inner) => inner,
+            Self::Continue(ref inner) => inner,
+            Self::Assign(ref inner) => inner,
+            Self::Struct(ref inner) => inner,
+            Self::Impl(ref inner) => inner,
+            Self::Enum(ref inner) => inner,
+            Self::Import(ref inner) => inner,
+            // The statement it exports is announced to the hook on its own.
+            Self::Export(ref inner) => return inner.evaluate(env),
+            Self::Block(ref inner) => return inner.evaluate(env),
         };
 
+        if env.runtime().statement(self.span()) == Control::Terminate {
+            return Error::new("terminated".to_owned()).into();
+        }
+
         inner.evaluate(env)
     }
 }
 
+/// Run one pass of a loop's `body` in a fresh scope, so its `let`s start over every time, with
+/// `binding` bound in it. Returns what the loop should evaluate to if it has to stop here.
+fn run_iteration(
+    body: &Block,
+    env: &Environment,
+    binding: Option<(&str, Object)>,
+) -> Option<Object> {
+    if let Err(err) = env.runtime().allocate(1) {
+        return Some(err.into());
+    }
+
+    let mut scope = Environment::new(HashMap::new(), Some(Box::new(env.clone())));
+
+    if let Some((name, value)) = binding {
+        _ = scope.set(name.to_owned(), value);
+    }
+
+    match body.evaluate(&mut scope) {
+        Object::Break(_) => Some(NULL),
+        value @ (Object::Return(_) | Object::Error(_)) => Some(value),
+        _ => None,
+    }
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner: &dyn fmt::Display = match *self {
+            Self::Let(ref inner) => inner,
+            Self::Return(ref inner) => inner,
+            Self::Expression(ref inner) => inner,
+            Self::Block(ref inner) => inner,
+            Self::While(ref inner) => inner,
+            Self::For(ref inner) => inner,
+            Self::Break(ref inner) => inner,
+            Self::Continue(ref inner) => inner,
+            Self::Assign(ref inner) => inner,
+            Self::Struct(ref inner) => inner,
+            Self::Impl(ref inner) => inner,
+            Self::Enum(ref inner) => inner,
+            Self::Import(ref inner) => inner,
+            Self::Export(ref inner) => inner,
+        };
+
+        inner.fmt(f)
+    }
+}
+
 impl From<Let> for Statement {
     fn from(value: Let) -> Self {
         Statement::Let(value)
@@ -71,3 +204,63 @@ impl From<Block> for Statement {
         Statement::Block(value)
     }
 }
+
+impl From<While> for Statement {
+    fn from(value: While) -> Self {
+        Statement::While(value)
+    }
+}
+
+impl From<For> for Statement {
+    fn from(value: For) -> Self {
+        Statement::For(value)
+    }
+}
+
+impl From<Break> for Statement {
+    fn from(value: Break) -> Self {
+        Statement::Break(value)
+    }
+}
+
+impl From<Continue> for Statement {
+    fn from(value: Continue) -> Self {
+        Statement::Continue(value)
+    }
+}
+
+impl From<Assign> for Statement {
+    fn from(value: Assign) -> Self {
+        Statement::Assign(value)
+    }
+}
+
+impl From<Struct> for Statement {
+    fn from(value: Struct) -> Self {
+        Statement::Struct(value)
+    }
+}
+
+impl From<Impl> for Statement {
+    fn from(value: Impl) -> Self {
+        Statement::Impl(value)
+    }
+}
+
+impl From<Enum> for Statement {
+    fn from(value: Enum) -> Self {
+        Statement::Enum(value)
+    }
+}
+
+impl From<Import> for Statement {
+    fn from(value: Import) -> Self {
+        Statement::Import(value)
+    }
+}
+
+impl From<Export> for Statement {
+    fn from(value: Export) -> Self {
+        Statement::Export(value)
+    }
+}
@@ -1,25 +1,44 @@
+use std::fmt;
+
 use crate::environment::Environment;
 use crate::evaluator::Evaluate;
 use crate::object::Object;
 use crate::parser::{Parse, Parser, ParserError};
 use crate::precedence::Precedence;
-use crate::token::TokenKind;
+use crate::token::{Span, Token, TokenKind};
 use crate::{expression, object};
 
 #[derive(Debug, Clone)]
 pub struct Return {
+    token: Token,
     value: expression::Expression,
 }
 
 impl Return {
-    pub fn new(value: expression::Expression) -> Self {
-        Self { value }
+    pub fn new(token: Token, value: expression::Expression) -> Self {
+        Self { token, value }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub const fn value(&self) -> &expression::Expression {
+        &self.value
+    }
+
+    pub const fn value_mut(&mut self) -> &mut expression::Expression {
+        &mut self.value
+    }
+
+    pub fn span(&self) -> Span {
+        self.token.span().to(self.value.span())
     }
 }
 
 impl Parse for Return {
     fn parse(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
-        _ = parser.expect_token_with_kind(TokenKind::Return)?;
+        let token = parser.expect_token_with_kind(TokenKind::Return)?;
         let value = expression::Expression::parse(parser, Precedence::Lowest)?;
 
         if parser
@@ -29,7 +48,7 @@ impl Parse for Return {
             parser.advance();
         }
 
-        Ok(Self::new(value))
+        Ok(Self::new(token, value))
     }
 }
 
@@ -44,3 +63,11 @@ impl Evaluate for Return {
         object::Return::new(Box::new(value)).into()
     }
 }
+
+impl fmt::Display for Return {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("return ")?;
+        self.value.fmt(f)?;
+        f.write_str(";")
+    }
+}
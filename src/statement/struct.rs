@@ -0,0 +1,141 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression::Identifier;
+use crate::object::{Error, Object, StructType};
+use crate::parser::{Parse, Parser, ParserError};
+use crate::token::{Span, Token, TokenKind};
+
+/// `struct Name { field, ... }`, binding `Name` to a new struct type.
+#[derive(Debug, Clone)]
+pub struct Struct {
+    token: Token,
+    name: Identifier,
+    fields: Vec<Identifier>,
+    rbrace: Token,
+}
+
+impl Struct {
+    pub fn new(token: Token, name: Identifier, fields: Vec<Identifier>, rbrace: Token) -> Self {
+        Self {
+            token,
+            name,
+            fields,
+            rbrace,
+        }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub const fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn fields(&self) -> &[Identifier] {
+        &self.fields
+    }
+
+    pub const fn name_mut(&mut self) -> &mut Identifier {
+        &mut self.name
+    }
+
+    pub const fn span(&self) -> Span {
+        self.token.span().to(self.rbrace.span())
+    }
+}
+
+impl Parse for Struct {
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::Struct)?;
+
+        let name = parser
+            .expect_token_with_kind(TokenKind::Identifier)
+            .map(Identifier::new)?;
+
+        _ = parser.expect_token_with_kind(TokenKind::LBrace)?;
+
+        let mut fields = Vec::<Identifier>::new();
+
+        while parser
+            .token()
+            .is_some_and(|token| token.kind() != TokenKind::RBrace)
+        {
+            let field = parser
+                .expect_token_with_kind(TokenKind::Identifier)
+                .map(Identifier::new)?;
+
+            if fields
+                .iter()
+                .any(|other| other.token().literal() == field.token().literal())
+            {
+                parser.report(ParserError::DuplicateField {
+                    field: field.token().literal().to_owned(),
+                    span: field.span(),
+                });
+            }
+
+            fields.push(field);
+
+            if parser
+                .token()
+                .is_none_or(|token| token.kind() != TokenKind::Comma)
+            {
+                break;
+            }
+
+            parser.advance();
+        }
+
+        let rbrace = parser.expect_token_with_kind(TokenKind::RBrace)?;
+
+        if parser
+            .token()
+            .is_some_and(|token| token.kind() == TokenKind::Semicolon)
+        {
+            parser.advance();
+        }
+
+        Ok(Self::new(token, name, fields, rbrace))
+    }
+}
+
+impl Evaluate for Struct {
+    fn evaluate(&self, env: &mut Environment) -> Object {
+        let name = self.name.token().literal();
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| field.token().literal().to_owned())
+            .collect();
+
+        let value = Object::from(StructType::new(name.to_owned(), fields));
+
+        match env.set(name.to_owned(), value.clone()) {
+            Some(_) => {
+                let message = format!("variable named {name:?} already exists");
+                Error::new(message).into()
+            }
+            None => value,
+        }
+    }
+}
+
+impl fmt::Display for Struct {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "struct {} {{", self.name)?;
+
+        for (index, field) in self.fields.iter().enumerate() {
+            f.write_str(if index > 0 { ", " } else { " " })?;
+            field.fmt(f)?;
+        }
+
+        if self.fields.is_empty() {
+            f.write_str("}")
+        } else {
+            f.write_str(" }")
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::fmt;
+
+use crate::environment::Environment;
+use crate::evaluator::Evaluate;
+use crate::expression;
+use crate::object::{NULL, Object};
+use crate::parser::{Parse, Parser, ParserError};
+use crate::precedence::Precedence;
+use crate::statement::{Block, run_iteration};
+use crate::token::{Span, Token, TokenKind};
+
+#[derive(Debug, Clone)]
+pub struct While {
+    token: Token,
+    condition: expression::Expression,
+    body: Block,
+}
+
+impl While {
+    pub fn new(token: Token, condition: expression::Expression, body: Block) -> Self {
+        Self {
+            token,
+            condition,
+            body,
+        }
+    }
+
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub const fn condition(&self) -> &expression::Expression {
+        &self.condition
+    }
+
+    pub const fn body(&self) -> &Block {
+        &self.body
+    }
+
+    pub const fn condition_mut(&mut self) -> &mut expression::Expression {
+        &mut self.condition
+    }
+
+    pub const fn body_mut(&mut self) -> &mut Block {
+        &mut self.body
+    }
+
+    pub const fn span(&self) -> Span {
+        self.token.span().to(self.body.span())
+    }
+}
+
+impl Parse for While {
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParserError> {
+        let token = parser.expect_token_with_kind(TokenKind::While)?;
+        let condition = parser.without_struct_literals(|parser| {
+            expression::Expression::parse(parser, Precedence::Lowest)
+        })?;
+        let body = parser.in_loop_body(Block::parse)?;
+
+        if parser
+            .token()
+            .is_some_and(|token| token.kind() == TokenKind::Semicolon)
+        {
+            parser.advance();
+        }
+
+        Ok(Self::new(token, condition, body))
+    }
+}
+
+impl Evaluate for While {
+    fn evaluate(&self, env: &mut Environment) -> Object {
+        loop {
+            let condition = self.condition.evaluate(env);
+
+            if matches!(condition, Object::Error(_)) {
+                return condition;
+            }
+
+            if !condition.as_boolean().value() {
+                return NULL;
+            }
+
+            if let Some(value) = run_iteration(&self.body, env, None) {
+                return value;
+            }
+        }
+    }
+}
+
+impl fmt::Display for While {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("while ")?;
+
+        // In alternate mode operators already bring their own parentheses.
+        if f.alternate() && self.condition.precedence() < Precedence::Call {
+            self.condition.fmt(f)?;
+        } else {
+            f.write_str("(")?;
+            self.condition.fmt(f)?;
+            f.write_str(")")?;
+        }
+
+        f.write_str(" ")?;
+        self.body.fmt(f)
+    }
+}
@@ -1,17 +1,42 @@
 use std::fmt;
 
+use unicode_normalization::is_nfc;
+use unicode_xid::UnicodeXID;
+
+use crate::lexer::{self, LexError};
+
+/// Words the lexer reserves, in the order editors should offer them.
+pub const KEYWORDS: &[&str] = &[
+    "let", "fn", "if", "else", "return", "true", "false", "macro", "while", "for", "in", "break",
+    "continue", "struct", "impl", "enum", "match", "import", "export", "as",
+];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TokenKind {
     Illegal,
+    /// Source the lexer can't read, and why.
+    Malformed(LexError),
     // EndOfFile,
     Identifier,
     Integer,
+    Float,
+    String,
     Assign,
     Plus,
     Minus,
     Bang,
     Asterisk,
     Slash,
+    Percent,
+    DoubleAsterisk,
+    Ampersand,
+    DoubleAmpersand,
+    Pipe,
+    DoublePipe,
+    Caret,
+    Tilde,
+    ShiftLeft,
+    ShiftRight,
     LessThan,
     GreaterThan,
     Equal,
@@ -19,11 +44,17 @@ pub enum TokenKind {
     LessThanOrEqual,
     GreaterThanOrEqual,
     Comma,
+    Colon,
+    DoubleColon,
+    Arrow,
+    FatArrow,
     Semicolon,
     LParenthesis,
     RParenthesis,
     LBrace,
     RBrace,
+    LBracket,
+    RBracket,
     Function,
     Let,
     True,
@@ -31,17 +62,93 @@ pub enum TokenKind {
     If,
     Else,
     Return,
+    Macro,
+    While,
+    For,
+    In,
+    Break,
+    Continue,
+    Struct,
+    Impl,
+    Enum,
+    Match,
+    Import,
+    Export,
+    As,
+    Dot,
+    Ellipsis,
+}
+
+/// A location in the source, counted in characters. Both `line` and `column` start at 1.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    line: usize,
+    column: usize,
+}
+
+impl Position {
+    pub const fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+}
+
+/// The region of source a token or node was read from. `end` is exclusive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    start: Position,
+    end: Position,
+}
+
+impl Span {
+    pub const fn new(start: Position, end: Position) -> Self {
+        Self { start, end }
+    }
+
+    pub const fn start(&self) -> Position {
+        self.start
+    }
+
+    pub const fn end(&self) -> Position {
+        self.end
+    }
+
+    /// A span starting where `self` starts and ending where `other` ends.
+    pub const fn to(self, other: Span) -> Self {
+        Self::new(self.start, other.end)
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        self.start <= position && position < self.end
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Token {
     kind: TokenKind,
     literal: String,
+    span: Span,
 }
 
 impl Token {
     pub fn new(kind: TokenKind, literal: String) -> Self {
-        Self { kind, literal }
+        Self {
+            kind,
+            literal,
+            span: Span::default(),
+        }
+    }
+
+    pub const fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
     }
 
     pub const fn kind(&self) -> TokenKind {
@@ -51,6 +158,10 @@ impl Token {
     pub fn literal(&self) -> &str {
         &self.literal
     }
+
+    pub const fn span(&self) -> Span {
+        self.span
+    }
 }
 
 impl From<String> for Token {
@@ -63,6 +174,16 @@ impl From<String> for Token {
             "!" => TokenKind::Bang,
             "*" => TokenKind::Asterisk,
             "/" => TokenKind::Slash,
+            "%" => TokenKind::Percent,
+            "**" => TokenKind::DoubleAsterisk,
+            "&" => TokenKind::Ampersand,
+            "&&" => TokenKind::DoubleAmpersand,
+            "|" => TokenKind::Pipe,
+            "||" => TokenKind::DoublePipe,
+            "^" => TokenKind::Caret,
+            "~" => TokenKind::Tilde,
+            "<<" => TokenKind::ShiftLeft,
+            ">>" => TokenKind::ShiftRight,
             "<" => TokenKind::LessThan,
             ">" => TokenKind::GreaterThan,
             "==" => TokenKind::Equal,
@@ -70,11 +191,17 @@ impl From<String> for Token {
             "<=" => TokenKind::LessThanOrEqual,
             ">=" => TokenKind::GreaterThanOrEqual,
             "," => TokenKind::Comma,
+            ":" => TokenKind::Colon,
+            "::" => TokenKind::DoubleColon,
+            "->" => TokenKind::Arrow,
+            "=>" => TokenKind::FatArrow,
             ";" => TokenKind::Semicolon,
             "(" => TokenKind::LParenthesis,
             ")" => TokenKind::RParenthesis,
             "{" => TokenKind::LBrace,
             "}" => TokenKind::RBrace,
+            "[" => TokenKind::LBracket,
+            "]" => TokenKind::RBracket,
             "fn" => TokenKind::Function,
             "let" => TokenKind::Let,
             "true" => TokenKind::True,
@@ -82,7 +209,23 @@ impl From<String> for Token {
             "if" => TokenKind::If,
             "else" => TokenKind::Else,
             "return" => TokenKind::Return,
+            "macro" => TokenKind::Macro,
+            "while" => TokenKind::While,
+            "for" => TokenKind::For,
+            "in" => TokenKind::In,
+            "break" => TokenKind::Break,
+            "continue" => TokenKind::Continue,
+            "struct" => TokenKind::Struct,
+            "impl" => TokenKind::Impl,
+            "enum" => TokenKind::Enum,
+            "match" => TokenKind::Match,
+            "import" => TokenKind::Import,
+            "export" => TokenKind::Export,
+            "as" => TokenKind::As,
+            "." => TokenKind::Dot,
+            "..." => TokenKind::Ellipsis,
             _ if is_valid_integer(&value) => TokenKind::Integer,
+            _ if is_valid_float(&value) => TokenKind::Float,
             _ if is_valid_identifier(&value) => TokenKind::Identifier,
             _ => TokenKind::Illegal,
         };
@@ -91,16 +234,31 @@ impl From<String> for Token {
     }
 }
 
+/// Decimal digits, or `0x`, `0o` or `0b` followed by digits in that radix, with single `_`
+/// separators between digits (`1_000_000`, `0xff_ff`).
 fn is_valid_integer(value: &str) -> bool {
-    value.chars().all(|c| c.is_ascii_digit())
+    let (radix, digits) = lexer::split_radix(value);
+
+    !digits.is_empty()
+        && !digits.starts_with('_')
+        && !digits.ends_with('_')
+        && !digits.contains("__")
+        && digits.chars().all(|c| c == '_' || c.is_digit(radix))
+}
+
+/// Digits with a fraction (`1.5`), an exponent (`1e-9`), or both.
+fn is_valid_float(value: &str) -> bool {
+    value.starts_with(|c: char| c.is_ascii_digit())
+        && value.contains(['.', 'e', 'E'])
+        && value.replace('_', "").parse::<f64>().is_ok()
 }
 
+/// `_` or an XID_Start character, then XID_Continue characters, in NFC.
 fn is_valid_identifier(value: &str) -> bool {
     let mut chars = value.chars();
-    chars
-        .next()
-        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
-        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    chars.next().is_some_and(|c| c.is_xid_start() || c == '_')
+        && chars.all(|c| c.is_xid_continue())
+        && is_nfc(value)
 }
 
 impl From<char> for Token {
@@ -109,19 +267,38 @@ impl From<char> for Token {
     }
 }
 
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
 impl fmt::Display for TokenKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             Self::Illegal => "ILLEGAL".fmt(f),
+            Self::Malformed(_) => "MALFORMED".fmt(f),
             // Self::EndOfFile => "EOF".fmt(f),
             Self::Identifier => "IDENTIFIER".fmt(f),
             Self::Integer => "INTEGER".fmt(f),
+            Self::Float => "FLOAT".fmt(f),
+            Self::String => "STRING".fmt(f),
             Self::Assign => "=".fmt(f),
             Self::Plus => "+".fmt(f),
             Self::Minus => "-".fmt(f),
             Self::Bang => "!".fmt(f),
             Self::Asterisk => "*".fmt(f),
             Self::Slash => "/".fmt(f),
+            Self::Percent => "%".fmt(f),
+            Self::DoubleAsterisk => "**".fmt(f),
+            Self::Ampersand => "&".fmt(f),
+            Self::DoubleAmpersand => "&&".fmt(f),
+            Self::Pipe => "|".fmt(f),
+            Self::DoublePipe => "||".fmt(f),
+            Self::Caret => "^".fmt(f),
+            Self::Tilde => "~".fmt(f),
+            Self::ShiftLeft => "<<".fmt(f),
+            Self::ShiftRight => ">>".fmt(f),
             Self::LessThan => "<".fmt(f),
             Self::GreaterThan => ">".fmt(f),
             Self::Equal => "==".fmt(f),
@@ -129,11 +306,17 @@ impl fmt::Display for TokenKind {
             Self::LessThanOrEqual => "<=".fmt(f),
             Self::GreaterThanOrEqual => ">=".fmt(f),
             Self::Comma => ",".fmt(f),
+            Self::Colon => ":".fmt(f),
+            Self::DoubleColon => "::".fmt(f),
+            Self::Arrow => "->".fmt(f),
+            Self::FatArrow => "=>".fmt(f),
             Self::Semicolon => ";".fmt(f),
             Self::LParenthesis => "(".fmt(f),
             Self::RParenthesis => ")".fmt(f),
             Self::LBrace => "{".fmt(f),
             Self::RBrace => "}".fmt(f),
+            Self::LBracket => "[".fmt(f),
+            Self::RBracket => "]".fmt(f),
             Self::Function => "fn".fmt(f),
             Self::Let => "let".fmt(f),
             Self::True => "true".fmt(f),
@@ -141,6 +324,21 @@ impl fmt::Display for TokenKind {
             Self::If => "if".fmt(f),
             Self::Else => "else".fmt(f),
             Self::Return => "return".fmt(f),
+            Self::Macro => "macro".fmt(f),
+            Self::While => "while".fmt(f),
+            Self::For => "for".fmt(f),
+            Self::In => "in".fmt(f),
+            Self::Break => "break".fmt(f),
+            Self::Continue => "continue".fmt(f),
+            Self::Struct => "struct".fmt(f),
+            Self::Impl => "impl".fmt(f),
+            Self::Enum => "enum".fmt(f),
+            Self::Match => "match".fmt(f),
+            Self::Import => "import".fmt(f),
+            Self::Export => "export".fmt(f),
+            Self::As => "as".fmt(f),
+            Self::Dot => ".".fmt(f),
+            Self::Ellipsis => "...".fmt(f),
         }
     }
 }
@@ -0,0 +1,914 @@
+//! Hindley–Milner type inference (algorithm W with a mutable substitution).
+//!
+//! Every `let` is generalized, so a function like `let id = fn(x) { x };` can be used at several
+//! types. Scoping follows the evaluator: blocks share their function's scope, and a name that
+//! cannot be found is given a fresh type rather than reported again here, since the resolver
+//! already does that.
+
+use crate::annotation::Annotation;
+use crate::diagnostic::Diagnostic;
+use crate::expression::{
+    Call, Expression, FunctionLiteral, Identifier, Index, Infix, Match, Path, Prefix,
+};
+use crate::pattern::Pattern;
+use crate::prelude;
+use crate::program::Program;
+use crate::statement::{Block, For, Let, Statement};
+use crate::token::{Span, TokenKind};
+use crate::types::Type;
+
+/// The type inferred for one `let` binding.
+#[derive(Debug, Clone)]
+pub struct TypedBinding {
+    name: String,
+    span: Span,
+    ty: Type,
+}
+
+impl TypedBinding {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub const fn span(&self) -> Span {
+        self.span
+    }
+
+    pub const fn ty(&self) -> &Type {
+        &self.ty
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Inference {
+    bindings: Vec<TypedBinding>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Inference {
+    /// Every `let` in the program, in source order.
+    pub fn bindings(&self) -> &[TypedBinding] {
+        &self.bindings
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
+
+pub fn infer(program: &Program) -> Inference {
+    let mut inferer = Inferer::default();
+    let result = inferer.fresh();
+    inferer.returns.push(result);
+    inferer.define_prelude();
+
+    for statement in program.statements() {
+        _ = inferer.infer_statement(statement);
+
+        if jumps(statement) {
+            break;
+        }
+    }
+
+    let bindings = inferer
+        .bindings
+        .iter()
+        .map(|(name, span, ty)| TypedBinding {
+            name: name.to_owned(),
+            span: *span,
+            ty: inferer.resolve(ty),
+        })
+        .collect();
+
+    Inference {
+        bindings,
+        diagnostics: inferer.diagnostics,
+    }
+}
+
+/// Whether control never reaches the statement after this one.
+const fn jumps(statement: &Statement) -> bool {
+    matches!(
+        *statement,
+        Statement::Return(_) | Statement::Break(_) | Statement::Continue(_)
+    )
+}
+
+/// A type with some of its variables universally quantified.
+#[derive(Debug, Clone)]
+struct Scheme {
+    variables: Vec<usize>,
+    ty: Type,
+}
+
+impl Scheme {
+    const fn monomorphic(ty: Type) -> Self {
+        Self {
+            variables: Vec::new(),
+            ty,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inferer {
+    /// What each type variable has been solved to, if anything.
+    substitution: Vec<Option<Type>>,
+    scope: Vec<(String, Scheme)>,
+    /// The result type of each function being inferred, innermost last.
+    returns: Vec<Type>,
+    bindings: Vec<(String, Span, Type)>,
+    /// Names declared with `struct` or `enum`, which can be used in annotations.
+    named: Vec<String>,
+    /// The variants of each `enum`, with how many values they carry.
+    enums: Vec<(String, Vec<(String, usize)>)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Inferer {
+    fn fresh(&mut self) -> Type {
+        self.substitution.push(None);
+        Type::Variable(self.substitution.len() - 1)
+    }
+
+    /// Apply the substitution all the way down.
+    fn resolve(&self, ty: &Type) -> Type {
+        match *ty {
+            Type::Variable(id) => match self.substitution[id] {
+                Some(ref inner) => self.resolve(inner),
+                None => ty.clone(),
+            },
+            Type::Function(ref parameters, ref result) => Type::Function(
+                parameters.iter().map(|ty| self.resolve(ty)).collect(),
+                Box::new(self.resolve(result)),
+            ),
+            Type::Array(ref element) => Type::Array(Box::new(self.resolve(element))),
+            _ => ty.clone(),
+        }
+    }
+
+    /// Make `found` equal to `expected`, reporting a mismatch at `span` if they cannot be.
+    fn unify(&mut self, expected: &Type, found: &Type, span: Span) {
+        if !self.unify_inner(expected, found) {
+            let expected = self.resolve(expected);
+            let found = self.resolve(found);
+            let mut names = expected.variables();
+            names.extend(found.variables());
+
+            let message = format!(
+                "type mismatch: expected {}, found {}",
+                expected.with_names(&names),
+                found.with_names(&names)
+            );
+            // Monkey is dynamically typed, so a mismatch is only ever a hint.
+            self.diagnostics.push(Diagnostic::warning(span, message));
+        }
+    }
+
+    fn unify_inner(&mut self, left: &Type, right: &Type) -> bool {
+        let left = self.shallow(left);
+        let right = self.shallow(right);
+
+        match (left, right) {
+            (Type::Variable(a), Type::Variable(b)) if a == b => true,
+            (Type::Variable(id), other) | (other, Type::Variable(id)) => {
+                // The occurs check: `'a = fn('a) -> int` has no finite solution.
+                if self.resolve(&other).variables().contains(&id) {
+                    return false;
+                }
+
+                self.substitution[id] = Some(other);
+                true
+            }
+            (
+                Type::Function(left_parameters, left_result),
+                Type::Function(right_parameters, right_result),
+            ) => {
+                left_parameters.len() == right_parameters.len()
+                    && left_parameters
+                        .iter()
+                        .zip(&right_parameters)
+                        .all(|(left, right)| self.unify_inner(left, right))
+                    && self.unify_inner(&left_result, &right_result)
+            }
+            (Type::Array(left), Type::Array(right)) => self.unify_inner(&left, &right),
+            (left, right) => left == right,
+        }
+    }
+
+    /// Follow solved variables until reaching a constructor or an unsolved variable.
+    fn shallow(&self, ty: &Type) -> Type {
+        match *ty {
+            Type::Variable(id) => match self.substitution[id] {
+                Some(ref inner) => self.shallow(inner),
+                None => ty.clone(),
+            },
+            _ => ty.clone(),
+        }
+    }
+
+    fn instantiate(&mut self, scheme: &Scheme) -> Type {
+        let fresh = scheme
+            .variables
+            .iter()
+            .map(|&id| (id, self.fresh()))
+            .collect::<Vec<_>>();
+
+        replace(&scheme.ty, &fresh)
+    }
+
+    fn generalize(&self, ty: &Type) -> Scheme {
+        let ty = self.resolve(ty);
+        let mut bound = Vec::new();
+
+        for (_, scheme) in &self.scope {
+            for id in self.resolve(&scheme.ty).variables() {
+                if !scheme.variables.contains(&id) {
+                    bound.push(id);
+                }
+            }
+        }
+
+        let variables = ty
+            .variables()
+            .into_iter()
+            .filter(|id| !bound.contains(id))
+            .collect();
+
+        Scheme { variables, ty }
+    }
+
+    /// Bind the prelude's functions, which a program's own names can shadow.
+    fn define_prelude(&mut self) {
+        for &name in prelude::NAMES {
+            let variables = [self.fresh(), self.fresh(), self.fresh()];
+            let scheme = self.generalize(&prelude_type(name, variables));
+            self.scope.push((name.to_owned(), scheme));
+        }
+    }
+
+    fn lookup(&mut self, name: &str) -> Type {
+        match self.scope.iter().rev().find(|(key, _)| key == name) {
+            Some((_, scheme)) => {
+                let scheme = scheme.clone();
+                self.instantiate(&scheme)
+            }
+            None => self.fresh(),
+        }
+    }
+
+    /// Turn an annotation into a type. Single-letter names are type variables, shared with every
+    /// other annotation in `variables`.
+    fn annotation(&mut self, annotation: &Annotation, variables: &mut Vec<(String, Type)>) -> Type {
+        match *annotation {
+            Annotation::Named(ref token) => match token.literal() {
+                "int" => Type::Integer,
+                "float" => Type::Float,
+                "string" => Type::String,
+                "bool" => Type::Boolean,
+                "null" => Type::Null,
+                name if self.named.iter().any(|key| key == name) => Type::Named(name.to_owned()),
+                name if name.len() == 1 => {
+                    if let Some((_, ty)) = variables.iter().find(|(key, _)| key == name) {
+                        return ty.clone();
+                    }
+
+                    let ty = self.fresh();
+                    variables.push((name.to_owned(), ty.clone()));
+                    ty
+                }
+                name => {
+                    let message = format!("unknown type {name:?}");
+                    self.diagnostics
+                        .push(Diagnostic::error(token.span(), message));
+                    self.fresh()
+                }
+            },
+            Annotation::Function {
+                ref parameters,
+                ref result,
+                ..
+            } => Type::Function(
+                parameters
+                    .iter()
+                    .map(|parameter| self.annotation(parameter, variables))
+                    .collect(),
+                Box::new(self.annotation(result, variables)),
+            ),
+        }
+    }
+
+    fn infer_statement(&mut self, statement: &Statement) -> Type {
+        match *statement {
+            Statement::Let(ref inner) => {
+                self.infer_let(inner);
+                Type::Null
+            }
+            Statement::Return(ref inner) => {
+                let ty = self.infer_expression(inner.value());
+                let expected = self
+                    .returns
+                    .last()
+                    .expect("return outside of a function")
+                    .clone();
+                self.unify(&expected, &ty, inner.value().span());
+
+                // Nothing after a `return` runs, so the statement itself can be any type.
+                self.fresh()
+            }
+            Statement::Expression(ref inner) => self.infer_expression(inner.expression()),
+            Statement::Block(ref inner) => self.infer_block(inner),
+            Statement::While(ref inner) => {
+                // As with `if`, any value can be a condition.
+                _ = self.infer_expression(inner.condition());
+                self.infer_loop_body(inner.body());
+                Type::Null
+            }
+            Statement::For(ref inner) => {
+                self.infer_for(inner);
+                Type::Null
+            }
+            // Like `return`, nothing after these runs.
+            Statement::Break(_) | Statement::Continue(_) => self.fresh(),
+            Statement::Impl(ref inner) => {
+                for method in inner.methods() {
+                    _ = self.infer_function_literal(method.function());
+                }
+
+                Type::Null
+            }
+            Statement::Enum(ref inner) => {
+                let name = inner.name().token().literal();
+                let variants = inner
+                    .variants()
+                    .iter()
+                    .map(|variant| {
+                        let name = variant.name().token().literal();
+                        (name.to_owned(), variant.fields().len())
+                    })
+                    .collect();
+
+                self.named.push(name.to_owned());
+                self.enums.push((name.to_owned(), variants));
+                Type::Null
+            }
+            Statement::Struct(ref inner) => {
+                let name = inner.name().token().literal();
+                self.named.push(name.to_owned());
+                Type::Null
+            }
+            // Other files aren't read here, so what a module exports can be anything.
+            Statement::Import(ref inner) => {
+                let name = inner.name().token().literal().to_owned();
+                let ty = self.fresh();
+                self.scope.push((name, Scheme::monomorphic(ty.clone())));
+                ty
+            }
+            Statement::Export(ref inner) => self.infer_statement(inner.statement()),
+            Statement::Assign(ref inner) => {
+                let ty = self.lookup(inner.name().token().literal());
+                let value = self.infer_expression(inner.value());
+
+                // Fields can hold anything, so only plain assignments are checked.
+                if inner.fields().is_empty() {
+                    self.unify(&ty, &value, inner.value().span());
+                }
+
+                value
+            }
+        }
+    }
+
+    fn infer_let(&mut self, node: &Let) {
+        let mut variables = Vec::new();
+        let annotation = node
+            .annotation()
+            .map(|annotation| self.annotation(annotation, &mut variables));
+
+        // Bind functions before inferring their body, so they can call themselves.
+        let ty = if let Pattern::Binding(ref name) = *node.pattern()
+            && matches!(*node.value(), Expression::FunctionLiteral(_))
+        {
+            let name = name.token().literal();
+            let ty = annotation.clone().unwrap_or_else(|| self.fresh());
+            self.scope
+                .push((name.to_owned(), Scheme::monomorphic(ty.clone())));
+            let value = self.infer_expression(node.value());
+            _ = self.scope.pop();
+            self.unify(&ty, &value, node.value().span());
+            ty
+        } else {
+            let value = self.infer_expression(node.value());
+
+            match annotation {
+                Some(annotation) => {
+                    self.unify(&annotation, &value, node.value().span());
+                    annotation
+                }
+                None => value,
+            }
+        };
+
+        // Generalize each name only once the whole pattern has been taken apart.
+        let scope = self.scope.len();
+        self.infer_pattern(node.pattern(), &ty);
+        let bound = self.scope.split_off(scope);
+
+        for ((name, scheme), binding) in bound.into_iter().zip(node.pattern().bindings()) {
+            let scheme = self.generalize(&scheme.ty);
+            self.bindings
+                .push((name.clone(), binding.span(), scheme.ty.clone()));
+            self.scope.push((name, scheme));
+        }
+    }
+
+    fn infer_for(&mut self, node: &For) {
+        let iterable = self.infer_expression(node.iterable());
+        let element = self.fresh();
+        let expected = Type::Array(Box::new(element.clone()));
+        self.unify(&expected, &iterable, node.iterable().span());
+
+        let scope = self.scope.len();
+        let name = node.variable().token().literal().to_owned();
+        self.scope.push((name, Scheme::monomorphic(element)));
+        self.infer_loop_body(node.body());
+        self.scope.truncate(scope);
+    }
+
+    /// Each pass of a loop gets a scope of its own, so its `let`s end with the body.
+    fn infer_loop_body(&mut self, node: &Block) {
+        let scope = self.scope.len();
+        _ = self.infer_block(node);
+        self.scope.truncate(scope);
+    }
+
+    fn infer_block(&mut self, node: &Block) -> Type {
+        let mut ty = Type::Null;
+
+        for statement in node.statements() {
+            ty = self.infer_statement(statement);
+
+            // Whatever follows can never run, so it says nothing about the types involved.
+            if jumps(statement) {
+                break;
+            }
+        }
+
+        ty
+    }
+
+    fn infer_expression(&mut self, node: &Expression) -> Type {
+        match *node {
+            Expression::Identifier(ref inner) => self.lookup(inner.token().literal()),
+            Expression::IntegerLiteral(_) => Type::Integer,
+            Expression::FloatLiteral(_) => Type::Float,
+            Expression::StringLiteral(_) => Type::String,
+            Expression::Boolean(_) => Type::Boolean,
+            Expression::Prefix(ref inner) => self.infer_prefix(inner),
+            Expression::Infix(ref inner) => self.infer_infix(inner),
+            Expression::If(ref inner) => {
+                // Any value can be used as a condition; only `false` and `null` are falsy.
+                _ = self.infer_expression(inner.condition());
+                let consequence = self.infer_block(inner.consequence());
+
+                if let Some(alternative) = inner.alternative() {
+                    let ty = self.infer_block(alternative);
+                    self.unify(&consequence, &ty, last_span(alternative));
+                }
+
+                consequence
+            }
+            Expression::FunctionLiteral(ref inner) => self.infer_function_literal(inner),
+            Expression::Call(ref inner) => self.infer_call(inner),
+            Expression::MacroLiteral(_) => Type::Macro,
+            Expression::ArrayLiteral(ref inner) => {
+                let element = self.fresh();
+
+                for expression in inner.elements() {
+                    let ty = self.infer_expression(expression);
+                    self.unify(&element, &ty, expression.span());
+                }
+
+                Type::Array(Box::new(element))
+            }
+            Expression::Index(ref inner) => self.infer_index(inner),
+            Expression::StructLiteral(ref inner) => {
+                for (_, value) in inner.fields() {
+                    _ = self.infer_expression(value);
+                }
+
+                // Types from other modules aren't tracked, so `m.Point` is only known to be
+                // whatever `m` exports.
+                match inner.module() {
+                    Some(_) => self.fresh(),
+                    None => Type::Named(inner.name().token().literal().to_owned()),
+                }
+            }
+            Expression::Path(ref inner) => self.infer_path(inner),
+            Expression::Match(ref inner) => self.infer_match(inner),
+            Expression::FieldAccess(ref inner) => {
+                _ = self.infer_expression(inner.left());
+                self.fresh()
+            }
+        }
+    }
+
+    fn infer_match(&mut self, node: &Match) -> Type {
+        let value = self.infer_expression(node.value());
+        let result = self.fresh();
+
+        for arm in node.arms() {
+            let scope = self.scope.len();
+            self.infer_pattern(arm.pattern(), &value);
+
+            // Like conditions, a guard can be any value.
+            if let Some(guard) = arm.guard() {
+                _ = self.infer_expression(guard);
+            }
+
+            let body = self.infer_statement(arm.body());
+            self.unify(&result, &body, arm.body().span());
+            self.scope.truncate(scope);
+        }
+
+        result
+    }
+
+    /// Bind the names in `pattern`, which is matched against a value of type `ty`.
+    fn infer_pattern(&mut self, pattern: &Pattern, ty: &Type) {
+        match *pattern {
+            Pattern::Wildcard(_) => {}
+            Pattern::Binding(ref name) => {
+                let name = name.token().literal().to_owned();
+                self.scope.push((name, Scheme::monomorphic(ty.clone())));
+            }
+            Pattern::Literal(ref literal) => {
+                let literal = self.infer_expression(literal);
+                self.unify(ty, &literal, pattern.span());
+            }
+            Pattern::Array {
+                ref elements,
+                ref rest,
+                ..
+            } => {
+                let element = self.fresh();
+                let array = Type::Array(Box::new(element.clone()));
+                self.unify(ty, &array, pattern.span());
+
+                for pattern in elements {
+                    self.infer_pattern(pattern, &element);
+                }
+
+                if let Some(rest) = rest {
+                    self.infer_pattern(rest, &array);
+                }
+            }
+            // What structs and variants hold is not tracked, so their parts can be anything, and
+            // neither are the types other modules export.
+            Pattern::Struct {
+                ref module,
+                ref name,
+                ref fields,
+                ..
+            } => {
+                if let (None, Some(name)) = (module, name) {
+                    let named = Type::Named(name.token().literal().to_owned());
+                    self.unify(ty, &named, pattern.span());
+                }
+
+                for (_, pattern) in fields {
+                    let field = self.fresh();
+                    self.infer_pattern(pattern, &field);
+                }
+            }
+            Pattern::Variant {
+                ref module,
+                ref name,
+                ref arguments,
+                ..
+            } => {
+                if module.is_none() {
+                    let named = Type::Named(name.token().literal().to_owned());
+                    self.unify(ty, &named, pattern.span());
+                }
+
+                for pattern in arguments {
+                    let argument = self.fresh();
+                    self.infer_pattern(pattern, &argument);
+                }
+            }
+        }
+    }
+
+    /// A variant that carries nothing is a value of its enum, and any other is a function
+    /// building one. What the variants carry is not checked.
+    fn infer_path(&mut self, node: &Path) -> Type {
+        _ = self.infer_expression(node.left());
+
+        let Expression::Identifier(ref name) = *node.left() else {
+            return self.fresh();
+        };
+
+        let name = name.token().literal();
+        let variant = node.variant().token().literal();
+        let arity = self
+            .enums
+            .iter()
+            .rev()
+            .find(|(key, _)| key == name)
+            .and_then(|(_, variants)| variants.iter().find(|(key, _)| key == variant))
+            .map(|&(_, arity)| arity);
+
+        match arity {
+            Some(0) => Type::Named(name.to_owned()),
+            Some(arity) => {
+                let parameters = (0..arity).map(|_| self.fresh()).collect();
+                Type::Function(parameters, Box::new(Type::Named(name.to_owned())))
+            }
+            None => self.fresh(),
+        }
+    }
+
+    fn infer_index(&mut self, node: &Index) -> Type {
+        let left = self.infer_expression(node.left());
+        let index = self.infer_expression(node.index());
+        let element = self.fresh();
+
+        self.unify(
+            &Type::Array(Box::new(element.clone())),
+            &left,
+            node.left().span(),
+        );
+        self.unify(&Type::Integer, &index, node.index().span());
+        element
+    }
+
+    fn infer_prefix(&mut self, node: &Prefix) -> Type {
+        let right = self.infer_expression(node.right());
+
+        match node.token().kind() {
+            TokenKind::Minus if self.is_float(&right) => Type::Float,
+            TokenKind::Minus | TokenKind::Tilde => {
+                self.unify(&Type::Integer, &right, node.right().span());
+                Type::Integer
+            }
+            _ => Type::Boolean,
+        }
+    }
+
+    fn infer_infix(&mut self, node: &Infix) -> Type {
+        let left = self.infer_expression(node.left());
+        let right = self.infer_expression(node.right());
+
+        // Strings can be joined with `+` and compared, but only with other strings.
+        if matches!(self.resolve(&left), Type::String)
+            || matches!(self.resolve(&right), Type::String)
+        {
+            let result = match node.token().kind() {
+                TokenKind::Plus => Some(Type::String),
+                TokenKind::LessThan
+                | TokenKind::GreaterThan
+                | TokenKind::LessThanOrEqual
+                | TokenKind::GreaterThanOrEqual => Some(Type::Boolean),
+                _ => None,
+            };
+
+            if let Some(result) = result {
+                self.unify(&Type::String, &left, node.left().span());
+                self.unify(&Type::String, &right, node.right().span());
+                return result;
+            }
+        }
+
+        // Mixed arithmetic promotes to float, so an integer operand is fine next to a float one.
+        if self.is_float(&left) || self.is_float(&right) {
+            let result = match node.token().kind() {
+                TokenKind::Plus
+                | TokenKind::Minus
+                | TokenKind::Asterisk
+                | TokenKind::Slash
+                | TokenKind::Percent
+                | TokenKind::DoubleAsterisk => Some(Type::Float),
+                TokenKind::LessThan
+                | TokenKind::GreaterThan
+                | TokenKind::LessThanOrEqual
+                | TokenKind::GreaterThanOrEqual
+                | TokenKind::Equal
+                | TokenKind::NotEqual => Some(Type::Boolean),
+                _ => None,
+            };
+
+            if let Some(result) = result {
+                for (ty, operand) in [(&left, node.left()), (&right, node.right())] {
+                    if !matches!(self.resolve(ty), Type::Integer) {
+                        self.unify(&Type::Float, ty, operand.span());
+                    }
+                }
+
+                return result;
+            }
+        }
+
+        let (operand, result) = match node.token().kind() {
+            TokenKind::Plus
+            | TokenKind::Minus
+            | TokenKind::Asterisk
+            | TokenKind::Slash
+            | TokenKind::Percent
+            | TokenKind::DoubleAsterisk
+            | TokenKind::Ampersand
+            | TokenKind::Pipe
+            | TokenKind::Caret
+            | TokenKind::ShiftLeft
+            | TokenKind::ShiftRight => (Type::Integer, Type::Integer),
+            TokenKind::LessThan
+            | TokenKind::GreaterThan
+            | TokenKind::LessThanOrEqual
+            | TokenKind::GreaterThanOrEqual => (Type::Integer, Type::Boolean),
+            // Like conditions, either side can be any value.
+            TokenKind::DoubleAmpersand | TokenKind::DoublePipe => return Type::Boolean,
+            _ => {
+                // `==` and `!=` work on any type, as long as both sides agree.
+                self.unify(&left, &right, node.right().span());
+                return Type::Boolean;
+            }
+        };
+
+        self.unify(&operand, &left, node.left().span());
+        self.unify(&operand, &right, node.right().span());
+        result
+    }
+
+    fn is_float(&self, ty: &Type) -> bool {
+        matches!(self.resolve(ty), Type::Float)
+    }
+
+    fn infer_function_literal(&mut self, node: &FunctionLiteral) -> Type {
+        let mut variables = Vec::new();
+        let scope = self.scope.len();
+
+        let parameters = node
+            .parameters()
+            .iter()
+            .zip(node.annotations())
+            .map(|(parameter, annotation)| {
+                let ty = match *annotation {
+                    Some(ref annotation) => self.annotation(annotation, &mut variables),
+                    None => self.fresh(),
+                };
+                self.infer_pattern(parameter, &ty);
+                ty
+            })
+            .collect::<Vec<_>>();
+
+        let result = match node.result() {
+            Some(annotation) => self.annotation(annotation, &mut variables),
+            None => self.fresh(),
+        };
+
+        self.returns.push(result.clone());
+        let body = self.infer_block(node.body());
+        self.unify(&result, &body, last_span(node.body()));
+        _ = self.returns.pop();
+
+        // Lets inside the body go out of scope with it.
+        self.scope.truncate(scope);
+
+        Type::Function(parameters, Box::new(result))
+    }
+
+    fn infer_call(&mut self, node: &Call) -> Type {
+        if let Expression::Identifier(ref callee) = *node.function()
+            && is_builtin(callee)
+            && node.arguments().len() == 1
+        {
+            return match callee.token().literal() {
+                "quote" => Type::Quote,
+                _ => {
+                    _ = self.infer_expression(&node.arguments()[0]);
+                    self.fresh()
+                }
+            };
+        }
+
+        let function = self.infer_expression(node.function());
+
+        match self.shallow(&function) {
+            // Macro arguments are passed as syntax, not values.
+            Type::Macro => self.fresh(),
+            // Report each argument where it is written, rather than the whole call at once.
+            Type::Function(parameters, result) if parameters.len() == node.arguments().len() => {
+                for (parameter, argument) in parameters.iter().zip(node.arguments()) {
+                    let ty = self.infer_expression(argument);
+                    self.unify(parameter, &ty, argument.span());
+                }
+
+                *result
+            }
+            // The wrong number of arguments is reported by the checker, not as a type error.
+            Type::Function(..) => {
+                for argument in node.arguments() {
+                    _ = self.infer_expression(argument);
+                }
+
+                self.fresh()
+            }
+            _ => {
+                let arguments = node
+                    .arguments()
+                    .iter()
+                    .map(|argument| self.infer_expression(argument))
+                    .collect();
+                let result = self.fresh();
+                let expected = Type::Function(arguments, Box::new(result.clone()));
+                self.unify(&expected, &function, node.function().span());
+                result
+            }
+        }
+    }
+}
+
+fn is_builtin(identifier: &Identifier) -> bool {
+    matches!(identifier.token().literal(), "quote" | "unquote")
+}
+
+/// The type of the prelude function `name`, generic in the given type variables.
+fn prelude_type(name: &str, [a, b, c]: [Type; 3]) -> Type {
+    let array = |element: &Type| Type::Array(Box::new(element.clone()));
+    let function =
+        |parameters: Vec<Type>, result: Type| Type::Function(parameters, Box::new(result));
+    let predicate = |parameters: Vec<Type>| function(parameters, Type::Boolean);
+
+    match name {
+        // These take either a string or an array.
+        "len" => function(vec![a], Type::Integer),
+        "contains" => predicate(vec![a, b]),
+        "reverse" => function(vec![a.clone()], a),
+        "push" => function(vec![array(&a), a.clone()], array(&a)),
+        "split" => function(vec![Type::String, Type::String], array(&Type::String)),
+        "join" => function(vec![array(&a), Type::String], Type::String),
+        "trim" | "upper" | "lower" => function(vec![Type::String], Type::String),
+        "replace" => function(vec![Type::String, Type::String, Type::String], Type::String),
+        "starts_with" => predicate(vec![Type::String, Type::String]),
+        "range" => function(vec![Type::Integer, Type::Integer], array(&Type::Integer)),
+        "sort" => function(
+            vec![array(&a), predicate(vec![a.clone(), a.clone()])],
+            array(&a),
+        ),
+        "keys" => function(vec![a], array(&Type::String)),
+        // Fields, and the pairs built from them, can hold values of different types.
+        "values" | "entries" => function(vec![a], array(&b)),
+        "merge" => function(vec![a.clone(), b], a),
+        "map" => function(vec![array(&a), function(vec![a], b.clone())], array(&b)),
+        "filter" => function(vec![array(&a), predicate(vec![a.clone()])], array(&a)),
+        "reduce" => function(
+            vec![
+                array(&a),
+                b.clone(),
+                function(vec![b.clone(), a], b.clone()),
+            ],
+            b,
+        ),
+        "read_file" => function(vec![Type::String], Type::String),
+        "write_file" => function(vec![Type::String, Type::String], Type::Null),
+        "list_dir" => function(vec![Type::String], array(&Type::String)),
+        // A string, or null if the variable isn't set.
+        "getenv" => function(vec![Type::String], a),
+        "args" => function(Vec::new(), array(&Type::String)),
+        // It never returns, so it fits wherever it is called.
+        "exit" => function(vec![Type::Integer], a),
+        // Whatever the document holds.
+        "json_parse" => function(vec![Type::String], a),
+        "json_stringify" => function(vec![a, Type::Integer], Type::String),
+        "zip" => function(vec![array(&a), array(&b)], array(&c)),
+        "enumerate" => function(vec![array(&a)], array(&b)),
+        _ => unreachable!("no type for the prelude's {name:?}"),
+    }
+}
+
+/// Where the value of a block comes from: its last statement, or the block itself if empty.
+fn last_span(block: &Block) -> Span {
+    block
+        .statements()
+        .last()
+        .map_or_else(|| block.span(), Statement::span)
+}
+
+/// Substitute the given variables in `ty`.
+fn replace(ty: &Type, variables: &[(usize, Type)]) -> Type {
+    match *ty {
+        Type::Variable(id) => variables
+            .iter()
+            .find(|&&(key, _)| key == id)
+            .map_or_else(|| ty.clone(), |(_, ty)| ty.clone()),
+        Type::Function(ref parameters, ref result) => Type::Function(
+            parameters
+                .iter()
+                .map(|parameter| replace(parameter, variables))
+                .collect(),
+            Box::new(replace(result, variables)),
+        ),
+        Type::Array(ref element) => Type::Array(Box::new(replace(element, variables))),
+        _ => ty.clone(),
+    }
+}
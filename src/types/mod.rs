@@ -0,0 +1,116 @@
+//! Static types for Monkey programs.
+//!
+//! Monkey itself is dynamically typed; these types only exist for the checker, which infers them
+//! Hindley–Milner style (see `infer`) and reports operations that would fail at runtime with a
+//! type mismatch.
+
+mod infer;
+
+use std::fmt;
+
+pub use infer::{Inference, TypedBinding, infer};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Type {
+    Integer,
+    Float,
+    String,
+    Boolean,
+    Null,
+    Quote,
+    Macro,
+    Array(Box<Type>),
+    /// An unknown type, to be solved by unification, or a generic parameter once generalized.
+    Variable(usize),
+    Function(Vec<Type>, Box<Type>),
+    /// A value of the struct or enum with this name. What it holds is not tracked.
+    Named(String),
+}
+
+impl Type {
+    /// Type variables in order of first appearance, without duplicates.
+    pub fn variables(&self) -> Vec<usize> {
+        let mut variables = Vec::new();
+        self.collect_variables(&mut variables);
+        variables
+    }
+
+    /// Display this type naming variables by their position in `names`, so that several types
+    /// can be shown side by side with consistent names.
+    pub(crate) fn with_names<'a>(&'a self, names: &'a [usize]) -> impl fmt::Display + 'a {
+        WithNames { ty: self, names }
+    }
+
+    fn collect_variables(&self, variables: &mut Vec<usize>) {
+        match *self {
+            Self::Variable(id) if !variables.contains(&id) => variables.push(id),
+            Self::Array(ref element) => element.collect_variables(variables),
+            Self::Function(ref parameters, ref result) => {
+                for parameter in parameters {
+                    parameter.collect_variables(variables);
+                }
+
+                result.collect_variables(variables);
+            }
+            _ => {}
+        }
+    }
+
+    fn write(&self, f: &mut fmt::Formatter<'_>, names: &[usize]) -> fmt::Result {
+        match *self {
+            Self::Integer => f.write_str("int"),
+            Self::Float => f.write_str("float"),
+            Self::String => f.write_str("string"),
+            Self::Boolean => f.write_str("bool"),
+            Self::Null => f.write_str("null"),
+            Self::Quote => f.write_str("quote"),
+            Self::Macro => f.write_str("macro"),
+            Self::Named(ref name) => f.write_str(name),
+            Self::Array(ref element) => {
+                f.write_str("[")?;
+                element.write(f, names)?;
+                f.write_str("]")
+            }
+            Self::Variable(id) => {
+                let index = names.iter().position(|&name| name == id).unwrap_or(id);
+
+                match u8::try_from(index) {
+                    Ok(index) if index < 26 => write!(f, "'{}", char::from(b'a' + index)),
+                    _ => write!(f, "'t{index}"),
+                }
+            }
+            Self::Function(ref parameters, ref result) => {
+                f.write_str("fn(")?;
+
+                for (index, parameter) in parameters.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+
+                    parameter.write(f, names)?;
+                }
+
+                f.write_str(") -> ")?;
+                result.write(f, names)
+            }
+        }
+    }
+}
+
+struct WithNames<'a> {
+    ty: &'a Type,
+    names: &'a [usize],
+}
+
+impl fmt::Display for WithNames<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.ty.write(f, self.names)
+    }
+}
+
+/// Type variables are renamed `'a`, `'b`, ... in order of appearance.
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, &self.variables())
+    }
+}
@@ -0,0 +1,754 @@
+//! Shared traversals over the AST.
+//!
+//! Implement `Visitor` to inspect a tree, or `MutVisitor` to rewrite one in place. Every method
+//! defaults to walking the node's children, so an implementation only overrides the nodes it is
+//! interested in and calls the matching `walk_*` function when it still wants to descend.
+
+use crate::expression::{
+    ArrayLiteral, Boolean, Call, Expression, FieldAccess, FloatLiteral, FunctionLiteral,
+    Identifier, If, Index, Infix, IntegerLiteral, MacroLiteral, Match, Path, Prefix, StringLiteral,
+    StructLiteral,
+};
+use crate::pattern::Pattern;
+use crate::program::Program;
+use crate::statement::{
+    self, Assign, Block, Break, Continue, Enum, Export, For, Impl, Import, Let, Return, Statement,
+    Struct, While,
+};
+
+pub trait Visitor: Sized {
+    fn visit_program(&mut self, node: &Program) {
+        walk_program(self, node);
+    }
+
+    fn visit_statement(&mut self, node: &Statement) {
+        walk_statement(self, node);
+    }
+
+    fn visit_let(&mut self, node: &Let) {
+        walk_let(self, node);
+    }
+
+    fn visit_return(&mut self, node: &Return) {
+        walk_return(self, node);
+    }
+
+    fn visit_expression_statement(&mut self, node: &statement::Expression) {
+        walk_expression_statement(self, node);
+    }
+
+    fn visit_block(&mut self, node: &Block) {
+        walk_block(self, node);
+    }
+
+    fn visit_while(&mut self, node: &While) {
+        walk_while(self, node);
+    }
+
+    fn visit_for(&mut self, node: &For) {
+        walk_for(self, node);
+    }
+
+    fn visit_break(&mut self, _: &Break) {}
+
+    fn visit_continue(&mut self, _: &Continue) {}
+
+    fn visit_assign(&mut self, node: &Assign) {
+        walk_assign(self, node);
+    }
+
+    fn visit_struct(&mut self, node: &Struct) {
+        walk_struct(self, node);
+    }
+
+    fn visit_impl(&mut self, node: &Impl) {
+        walk_impl(self, node);
+    }
+
+    fn visit_enum(&mut self, node: &Enum) {
+        walk_enum(self, node);
+    }
+
+    fn visit_import(&mut self, node: &Import) {
+        walk_import(self, node);
+    }
+
+    fn visit_export(&mut self, node: &Export) {
+        walk_export(self, node);
+    }
+
+    fn visit_expression(&mut self, node: &Expression) {
+        walk_expression(self, node);
+    }
+
+    fn visit_identifier(&mut self, _: &Identifier) {}
+
+    fn visit_integer_literal(&mut self, _: &IntegerLiteral) {}
+
+    fn visit_float_literal(&mut self, _: &FloatLiteral) {}
+
+    fn visit_string_literal(&mut self, _: &StringLiteral) {}
+
+    fn visit_boolean(&mut self, _: &Boolean) {}
+
+    fn visit_prefix(&mut self, node: &Prefix) {
+        walk_prefix(self, node);
+    }
+
+    fn visit_infix(&mut self, node: &Infix) {
+        walk_infix(self, node);
+    }
+
+    fn visit_if(&mut self, node: &If) {
+        walk_if(self, node);
+    }
+
+    fn visit_function_literal(&mut self, node: &FunctionLiteral) {
+        walk_function_literal(self, node);
+    }
+
+    fn visit_call(&mut self, node: &Call) {
+        walk_call(self, node);
+    }
+
+    fn visit_macro_literal(&mut self, node: &MacroLiteral) {
+        walk_macro_literal(self, node);
+    }
+
+    fn visit_array_literal(&mut self, node: &ArrayLiteral) {
+        walk_array_literal(self, node);
+    }
+
+    fn visit_index(&mut self, node: &Index) {
+        walk_index(self, node);
+    }
+
+    fn visit_struct_literal(&mut self, node: &StructLiteral) {
+        walk_struct_literal(self, node);
+    }
+
+    fn visit_field_access(&mut self, node: &FieldAccess) {
+        walk_field_access(self, node);
+    }
+
+    fn visit_path(&mut self, node: &Path) {
+        walk_path(self, node);
+    }
+
+    fn visit_match(&mut self, node: &Match) {
+        walk_match(self, node);
+    }
+
+    fn visit_pattern(&mut self, node: &Pattern) {
+        walk_pattern(self, node);
+    }
+}
+
+pub fn walk_program<V: Visitor>(visitor: &mut V, node: &Program) {
+    for statement in node.statements() {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_statement<V: Visitor>(visitor: &mut V, node: &Statement) {
+    match *node {
+        Statement::Let(ref inner) => visitor.visit_let(inner),
+        Statement::Return(ref inner) => visitor.visit_return(inner),
+        Statement::Expression(ref inner) => visitor.visit_expression_statement(inner),
+        Statement::Block(ref inner) => visitor.visit_block(inner),
+        Statement::While(ref inner) => visitor.visit_while(inner),
+        Statement::For(ref inner) => visitor.visit_for(inner),
+        Statement::Break(ref inner) => visitor.visit_break(inner),
+        Statement::Continue(ref inner) => visitor.visit_continue(inner),
+        Statement::Assign(ref inner) => visitor.visit_assign(inner),
+        Statement::Struct(ref inner) => visitor.visit_struct(inner),
+        Statement::Impl(ref inner) => visitor.visit_impl(inner),
+        Statement::Enum(ref inner) => visitor.visit_enum(inner),
+        Statement::Import(ref inner) => visitor.visit_import(inner),
+        Statement::Export(ref inner) => visitor.visit_export(inner),
+    }
+}
+
+pub fn walk_let<V: Visitor>(visitor: &mut V, node: &Let) {
+    visitor.visit_pattern(node.pattern());
+    visitor.visit_expression(node.value());
+}
+
+pub fn walk_return<V: Visitor>(visitor: &mut V, node: &Return) {
+    visitor.visit_expression(node.value());
+}
+
+pub fn walk_expression_statement<V: Visitor>(visitor: &mut V, node: &statement::Expression) {
+    visitor.visit_expression(node.expression());
+}
+
+pub fn walk_block<V: Visitor>(visitor: &mut V, node: &Block) {
+    for statement in node.statements() {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_while<V: Visitor>(visitor: &mut V, node: &While) {
+    visitor.visit_expression(node.condition());
+    visitor.visit_block(node.body());
+}
+
+pub fn walk_for<V: Visitor>(visitor: &mut V, node: &For) {
+    visitor.visit_identifier(node.variable());
+    visitor.visit_expression(node.iterable());
+    visitor.visit_block(node.body());
+}
+
+pub fn walk_assign<V: Visitor>(visitor: &mut V, node: &Assign) {
+    visitor.visit_identifier(node.name());
+    visitor.visit_expression(node.value());
+}
+
+/// Only the name is visited: the fields are not variables.
+pub fn walk_struct<V: Visitor>(visitor: &mut V, node: &Struct) {
+    visitor.visit_identifier(node.name());
+}
+
+/// Method names are not variables, so only the struct's name and the functions are visited.
+pub fn walk_impl<V: Visitor>(visitor: &mut V, node: &Impl) {
+    visitor.visit_identifier(node.name());
+
+    for method in node.methods() {
+        visitor.visit_function_literal(method.function());
+    }
+}
+
+/// Only the name is visited: variants and their fields are not variables.
+pub fn walk_enum<V: Visitor>(visitor: &mut V, node: &Enum) {
+    visitor.visit_identifier(node.name());
+}
+
+pub fn walk_import<V: Visitor>(visitor: &mut V, node: &Import) {
+    visitor.visit_identifier(node.name());
+}
+
+pub fn walk_export<V: Visitor>(visitor: &mut V, node: &Export) {
+    visitor.visit_statement(node.statement());
+}
+
+pub fn walk_expression<V: Visitor>(visitor: &mut V, node: &Expression) {
+    match *node {
+        Expression::Identifier(ref inner) => visitor.visit_identifier(inner),
+        Expression::IntegerLiteral(ref inner) => visitor.visit_integer_literal(inner),
+        Expression::FloatLiteral(ref inner) => visitor.visit_float_literal(inner),
+        Expression::StringLiteral(ref inner) => visitor.visit_string_literal(inner),
+        Expression::Boolean(ref inner) => visitor.visit_boolean(inner),
+        Expression::Prefix(ref inner) => visitor.visit_prefix(inner),
+        Expression::Infix(ref inner) => visitor.visit_infix(inner),
+        Expression::If(ref inner) => visitor.visit_if(inner),
+        Expression::FunctionLiteral(ref inner) => visitor.visit_function_literal(inner),
+        Expression::Call(ref inner) => visitor.visit_call(inner),
+        Expression::MacroLiteral(ref inner) => visitor.visit_macro_literal(inner),
+        Expression::ArrayLiteral(ref inner) => visitor.visit_array_literal(inner),
+        Expression::Index(ref inner) => visitor.visit_index(inner),
+        Expression::StructLiteral(ref inner) => visitor.visit_struct_literal(inner),
+        Expression::FieldAccess(ref inner) => visitor.visit_field_access(inner),
+        Expression::Path(ref inner) => visitor.visit_path(inner),
+        Expression::Match(ref inner) => visitor.visit_match(inner),
+    }
+}
+
+pub fn walk_prefix<V: Visitor>(visitor: &mut V, node: &Prefix) {
+    visitor.visit_expression(node.right());
+}
+
+pub fn walk_infix<V: Visitor>(visitor: &mut V, node: &Infix) {
+    visitor.visit_expression(node.left());
+    visitor.visit_expression(node.right());
+}
+
+pub fn walk_if<V: Visitor>(visitor: &mut V, node: &If) {
+    visitor.visit_expression(node.condition());
+    visitor.visit_block(node.consequence());
+
+    if let Some(alternative) = node.alternative() {
+        visitor.visit_block(alternative);
+    }
+}
+
+pub fn walk_function_literal<V: Visitor>(visitor: &mut V, node: &FunctionLiteral) {
+    for parameter in node.parameters() {
+        visitor.visit_pattern(parameter);
+    }
+
+    visitor.visit_block(node.body());
+}
+
+pub fn walk_call<V: Visitor>(visitor: &mut V, node: &Call) {
+    visitor.visit_expression(node.function());
+
+    for argument in node.arguments() {
+        visitor.visit_expression(argument);
+    }
+}
+
+pub fn walk_macro_literal<V: Visitor>(visitor: &mut V, node: &MacroLiteral) {
+    for parameter in node.parameters() {
+        visitor.visit_identifier(parameter);
+    }
+
+    visitor.visit_block(node.body());
+}
+
+pub fn walk_array_literal<V: Visitor>(visitor: &mut V, node: &ArrayLiteral) {
+    for element in node.elements() {
+        visitor.visit_expression(element);
+    }
+}
+
+pub fn walk_index<V: Visitor>(visitor: &mut V, node: &Index) {
+    visitor.visit_expression(node.left());
+    visitor.visit_expression(node.index());
+}
+
+/// A struct a module exports, as in `m.Point { ... }`, visits the module's name instead of its own.
+pub fn walk_struct_literal<V: Visitor>(visitor: &mut V, node: &StructLiteral) {
+    visitor.visit_identifier(node.module().unwrap_or(node.name()));
+
+    for (_, value) in node.fields() {
+        visitor.visit_expression(value);
+    }
+}
+
+pub fn walk_field_access<V: Visitor>(visitor: &mut V, node: &FieldAccess) {
+    visitor.visit_expression(node.left());
+}
+
+pub fn walk_path<V: Visitor>(visitor: &mut V, node: &Path) {
+    visitor.visit_expression(node.left());
+}
+
+pub fn walk_match<V: Visitor>(visitor: &mut V, node: &Match) {
+    visitor.visit_expression(node.value());
+
+    for arm in node.arms() {
+        visitor.visit_pattern(arm.pattern());
+
+        if let Some(guard) = arm.guard() {
+            visitor.visit_expression(guard);
+        }
+
+        visitor.visit_statement(arm.body());
+    }
+}
+
+/// Names a pattern binds are visited as identifiers, like the name in a `let`. The names of
+/// structs and enums it refers to are visited too, or the module's name for ones a module exports,
+/// but not field or variant names.
+pub fn walk_pattern<V: Visitor>(visitor: &mut V, node: &Pattern) {
+    match *node {
+        Pattern::Wildcard(_) => {}
+        Pattern::Binding(ref name) => visitor.visit_identifier(name),
+        Pattern::Literal(ref literal) => visitor.visit_expression(literal),
+        Pattern::Array {
+            ref elements,
+            ref rest,
+            ..
+        } => {
+            for element in elements.iter().chain(rest.as_deref()) {
+                visitor.visit_pattern(element);
+            }
+        }
+        Pattern::Struct {
+            ref module,
+            ref name,
+            ref fields,
+            ..
+        } => {
+            if let Some(name) = module.as_ref().or(name.as_ref()) {
+                visitor.visit_identifier(name);
+            }
+
+            for (_, pattern) in fields {
+                visitor.visit_pattern(pattern);
+            }
+        }
+        Pattern::Variant {
+            ref module,
+            ref name,
+            ref arguments,
+            ..
+        } => {
+            visitor.visit_identifier(module.as_ref().unwrap_or(name));
+
+            for argument in arguments {
+                visitor.visit_pattern(argument);
+            }
+        }
+    }
+}
+
+/// Like `Visitor`, but with mutable access so a pass can rewrite the tree as it goes.
+///
+/// To replace a node with one of a different kind, override the method for its enclosing enum
+/// (`visit_expression_mut` or `visit_statement_mut`) and assign through the reference.
+pub trait MutVisitor: Sized {
+    fn visit_program_mut(&mut self, node: &mut Program) {
+        walk_program_mut(self, node);
+    }
+
+    fn visit_statement_mut(&mut self, node: &mut Statement) {
+        walk_statement_mut(self, node);
+    }
+
+    fn visit_let_mut(&mut self, node: &mut Let) {
+        walk_let_mut(self, node);
+    }
+
+    fn visit_return_mut(&mut self, node: &mut Return) {
+        walk_return_mut(self, node);
+    }
+
+    fn visit_expression_statement_mut(&mut self, node: &mut statement::Expression) {
+        walk_expression_statement_mut(self, node);
+    }
+
+    fn visit_block_mut(&mut self, node: &mut Block) {
+        walk_block_mut(self, node);
+    }
+
+    fn visit_while_mut(&mut self, node: &mut While) {
+        walk_while_mut(self, node);
+    }
+
+    fn visit_for_mut(&mut self, node: &mut For) {
+        walk_for_mut(self, node);
+    }
+
+    fn visit_break_mut(&mut self, _: &mut Break) {}
+
+    fn visit_continue_mut(&mut self, _: &mut Continue) {}
+
+    fn visit_assign_mut(&mut self, node: &mut Assign) {
+        walk_assign_mut(self, node);
+    }
+
+    fn visit_struct_mut(&mut self, node: &mut Struct) {
+        walk_struct_mut(self, node);
+    }
+
+    fn visit_impl_mut(&mut self, node: &mut Impl) {
+        walk_impl_mut(self, node);
+    }
+
+    fn visit_enum_mut(&mut self, node: &mut Enum) {
+        walk_enum_mut(self, node);
+    }
+
+    fn visit_import_mut(&mut self, node: &mut Import) {
+        walk_import_mut(self, node);
+    }
+
+    fn visit_export_mut(&mut self, node: &mut Export) {
+        walk_export_mut(self, node);
+    }
+
+    fn visit_expression_mut(&mut self, node: &mut Expression) {
+        walk_expression_mut(self, node);
+    }
+
+    fn visit_identifier_mut(&mut self, _: &mut Identifier) {}
+
+    fn visit_integer_literal_mut(&mut self, _: &mut IntegerLiteral) {}
+
+    fn visit_float_literal_mut(&mut self, _: &mut FloatLiteral) {}
+
+    fn visit_string_literal_mut(&mut self, _: &mut StringLiteral) {}
+
+    fn visit_boolean_mut(&mut self, _: &mut Boolean) {}
+
+    fn visit_prefix_mut(&mut self, node: &mut Prefix) {
+        walk_prefix_mut(self, node);
+    }
+
+    fn visit_infix_mut(&mut self, node: &mut Infix) {
+        walk_infix_mut(self, node);
+    }
+
+    fn visit_if_mut(&mut self, node: &mut If) {
+        walk_if_mut(self, node);
+    }
+
+    fn visit_function_literal_mut(&mut self, node: &mut FunctionLiteral) {
+        walk_function_literal_mut(self, node);
+    }
+
+    fn visit_call_mut(&mut self, node: &mut Call) {
+        walk_call_mut(self, node);
+    }
+
+    fn visit_macro_literal_mut(&mut self, node: &mut MacroLiteral) {
+        walk_macro_literal_mut(self, node);
+    }
+
+    fn visit_array_literal_mut(&mut self, node: &mut ArrayLiteral) {
+        walk_array_literal_mut(self, node);
+    }
+
+    fn visit_index_mut(&mut self, node: &mut Index) {
+        walk_index_mut(self, node);
+    }
+
+    fn visit_struct_literal_mut(&mut self, node: &mut StructLiteral) {
+        walk_struct_literal_mut(self, node);
+    }
+
+    fn visit_field_access_mut(&mut self, node: &mut FieldAccess) {
+        walk_field_access_mut(self, node);
+    }
+
+    fn visit_path_mut(&mut self, node: &mut Path) {
+        walk_path_mut(self, node);
+    }
+
+    fn visit_match_mut(&mut self, node: &mut Match) {
+        walk_match_mut(self, node);
+    }
+
+    fn visit_pattern_mut(&mut self, node: &mut Pattern) {
+        walk_pattern_mut(self, node);
+    }
+}
+
+pub fn walk_program_mut<V: MutVisitor>(visitor: &mut V, node: &mut Program) {
+    for statement in node.statements_mut() {
+        visitor.visit_statement_mut(statement);
+    }
+}
+
+pub fn walk_statement_mut<V: MutVisitor>(visitor: &mut V, node: &mut Statement) {
+    match *node {
+        Statement::Let(ref mut inner) => visitor.visit_let_mut(inner),
+        Statement::Return(ref mut inner) => visitor.visit_return_mut(inner),
+        Statement::Expression(ref mut inner) => visitor.visit_expression_statement_mut(inner),
+        Statement::Block(ref mut inner) => visitor.visit_block_mut(inner),
+        Statement::While(ref mut inner) => visitor.visit_while_mut(inner),
+        Statement::For(ref mut inner) => visitor.visit_for_mut(inner),
+        Statement::Break(ref mut inner) => visitor.visit_break_mut(inner),
+        Statement::Continue(ref mut inner) => visitor.visit_continue_mut(inner),
+        Statement::Assign(ref mut inner) => visitor.visit_assign_mut(inner),
+        Statement::Struct(ref mut inner) => visitor.visit_struct_mut(inner),
+        Statement::Impl(ref mut inner) => visitor.visit_impl_mut(inner),
+        Statement::Enum(ref mut inner) => visitor.visit_enum_mut(inner),
+        Statement::Import(ref mut inner) => visitor.visit_import_mut(inner),
+        Statement::Export(ref mut inner) => visitor.visit_export_mut(inner),
+    }
+}
+
+pub fn walk_let_mut<V: MutVisitor>(visitor: &mut V, node: &mut Let) {
+    visitor.visit_pattern_mut(node.pattern_mut());
+    visitor.visit_expression_mut(node.value_mut());
+}
+
+pub fn walk_return_mut<V: MutVisitor>(visitor: &mut V, node: &mut Return) {
+    visitor.visit_expression_mut(node.value_mut());
+}
+
+pub fn walk_expression_statement_mut<V: MutVisitor>(
+    visitor: &mut V,
+    node: &mut statement::Expression,
+) {
+    visitor.visit_expression_mut(node.expression_mut());
+}
+
+pub fn walk_block_mut<V: MutVisitor>(visitor: &mut V, node: &mut Block) {
+    for statement in node.statements_mut() {
+        visitor.visit_statement_mut(statement);
+    }
+}
+
+pub fn walk_while_mut<V: MutVisitor>(visitor: &mut V, node: &mut While) {
+    visitor.visit_expression_mut(node.condition_mut());
+    visitor.visit_block_mut(node.body_mut());
+}
+
+pub fn walk_for_mut<V: MutVisitor>(visitor: &mut V, node: &mut For) {
+    visitor.visit_identifier_mut(node.variable_mut());
+    visitor.visit_expression_mut(node.iterable_mut());
+    visitor.visit_block_mut(node.body_mut());
+}
+
+pub fn walk_assign_mut<V: MutVisitor>(visitor: &mut V, node: &mut Assign) {
+    visitor.visit_identifier_mut(node.name_mut());
+    visitor.visit_expression_mut(node.value_mut());
+}
+
+pub fn walk_struct_mut<V: MutVisitor>(visitor: &mut V, node: &mut Struct) {
+    visitor.visit_identifier_mut(node.name_mut());
+}
+
+pub fn walk_impl_mut<V: MutVisitor>(visitor: &mut V, node: &mut Impl) {
+    visitor.visit_identifier_mut(node.name_mut());
+
+    for method in node.methods_mut() {
+        visitor.visit_function_literal_mut(method.function_mut());
+    }
+}
+
+pub fn walk_enum_mut<V: MutVisitor>(visitor: &mut V, node: &mut Enum) {
+    visitor.visit_identifier_mut(node.name_mut());
+}
+
+pub fn walk_import_mut<V: MutVisitor>(visitor: &mut V, node: &mut Import) {
+    visitor.visit_identifier_mut(node.name_mut());
+}
+
+pub fn walk_export_mut<V: MutVisitor>(visitor: &mut V, node: &mut Export) {
+    visitor.visit_statement_mut(node.statement_mut());
+}
+
+pub fn walk_expression_mut<V: MutVisitor>(visitor: &mut V, node: &mut Expression) {
+    match *node {
+        Expression::Identifier(ref mut inner) => visitor.visit_identifier_mut(inner),
+        Expression::IntegerLiteral(ref mut inner) => visitor.visit_integer_literal_mut(inner),
+        Expression::FloatLiteral(ref mut inner) => visitor.visit_float_literal_mut(inner),
+        Expression::StringLiteral(ref mut inner) => visitor.visit_string_literal_mut(inner),
+        Expression::Boolean(ref mut inner) => visitor.visit_boolean_mut(inner),
+        Expression::Prefix(ref mut inner) => visitor.visit_prefix_mut(inner),
+        Expression::Infix(ref mut inner) => visitor.visit_infix_mut(inner),
+        Expression::If(ref mut inner) => visitor.visit_if_mut(inner),
+        Expression::FunctionLiteral(ref mut inner) => visitor.visit_function_literal_mut(inner),
+        Expression::Call(ref mut inner) => visitor.visit_call_mut(inner),
+        Expression::MacroLiteral(ref mut inner) => visitor.visit_macro_literal_mut(inner),
+        Expression::ArrayLiteral(ref mut inner) => visitor.visit_array_literal_mut(inner),
+        Expression::Index(ref mut inner) => visitor.visit_index_mut(inner),
+        Expression::StructLiteral(ref mut inner) => visitor.visit_struct_literal_mut(inner),
+        Expression::FieldAccess(ref mut inner) => visitor.visit_field_access_mut(inner),
+        Expression::Path(ref mut inner) => visitor.visit_path_mut(inner),
+        Expression::Match(ref mut inner) => visitor.visit_match_mut(inner),
+    }
+}
+
+pub fn walk_prefix_mut<V: MutVisitor>(visitor: &mut V, node: &mut Prefix) {
+    visitor.visit_expression_mut(node.right_mut());
+}
+
+pub fn walk_infix_mut<V: MutVisitor>(visitor: &mut V, node: &mut Infix) {
+    visitor.visit_expression_mut(node.left_mut());
+    visitor.visit_expression_mut(node.right_mut());
+}
+
+pub fn walk_if_mut<V: MutVisitor>(visitor: &mut V, node: &mut If) {
+    visitor.visit_expression_mut(node.condition_mut());
+    visitor.visit_block_mut(node.consequence_mut());
+
+    if let Some(alternative) = node.alternative_mut() {
+        visitor.visit_block_mut(alternative);
+    }
+}
+
+pub fn walk_function_literal_mut<V: MutVisitor>(visitor: &mut V, node: &mut FunctionLiteral) {
+    for parameter in node.parameters_mut() {
+        visitor.visit_pattern_mut(parameter);
+    }
+
+    visitor.visit_block_mut(node.body_mut());
+}
+
+pub fn walk_call_mut<V: MutVisitor>(visitor: &mut V, node: &mut Call) {
+    visitor.visit_expression_mut(node.function_mut());
+
+    for argument in node.arguments_mut() {
+        visitor.visit_expression_mut(argument);
+    }
+}
+
+pub fn walk_macro_literal_mut<V: MutVisitor>(visitor: &mut V, node: &mut MacroLiteral) {
+    for parameter in node.parameters_mut() {
+        visitor.visit_identifier_mut(parameter);
+    }
+
+    visitor.visit_block_mut(node.body_mut());
+}
+
+pub fn walk_array_literal_mut<V: MutVisitor>(visitor: &mut V, node: &mut ArrayLiteral) {
+    for element in node.elements_mut() {
+        visitor.visit_expression_mut(element);
+    }
+}
+
+pub fn walk_index_mut<V: MutVisitor>(visitor: &mut V, node: &mut Index) {
+    visitor.visit_expression_mut(node.left_mut());
+    visitor.visit_expression_mut(node.index_mut());
+}
+
+pub fn walk_struct_literal_mut<V: MutVisitor>(visitor: &mut V, node: &mut StructLiteral) {
+    match node.module_mut() {
+        Some(module) => visitor.visit_identifier_mut(module),
+        None => visitor.visit_identifier_mut(node.name_mut()),
+    }
+
+    for (_, value) in node.fields_mut() {
+        visitor.visit_expression_mut(value);
+    }
+}
+
+pub fn walk_field_access_mut<V: MutVisitor>(visitor: &mut V, node: &mut FieldAccess) {
+    visitor.visit_expression_mut(node.left_mut());
+}
+
+pub fn walk_path_mut<V: MutVisitor>(visitor: &mut V, node: &mut Path) {
+    visitor.visit_expression_mut(node.left_mut());
+}
+
+pub fn walk_match_mut<V: MutVisitor>(visitor: &mut V, node: &mut Match) {
+    visitor.visit_expression_mut(node.value_mut());
+
+    for arm in node.arms_mut() {
+        visitor.visit_pattern_mut(arm.pattern_mut());
+
+        if let Some(guard) = arm.guard_mut() {
+            visitor.visit_expression_mut(guard);
+        }
+
+        visitor.visit_statement_mut(arm.body_mut());
+    }
+}
+
+pub fn walk_pattern_mut<V: MutVisitor>(visitor: &mut V, node: &mut Pattern) {
+    match *node {
+        Pattern::Wildcard(_) => {}
+        Pattern::Binding(ref mut name) => visitor.visit_identifier_mut(name),
+        Pattern::Literal(ref mut literal) => visitor.visit_expression_mut(literal),
+        Pattern::Array {
+            ref mut elements,
+            ref mut rest,
+            ..
+        } => {
+            for element in elements.iter_mut().chain(rest.as_deref_mut()) {
+                visitor.visit_pattern_mut(element);
+            }
+        }
+        Pattern::Struct {
+            ref mut module,
+            ref mut name,
+            ref mut fields,
+            ..
+        } => {
+            if let Some(name) = module.as_mut().or(name.as_mut()) {
+                visitor.visit_identifier_mut(name);
+            }
+
+            for (_, pattern) in fields {
+                visitor.visit_pattern_mut(pattern);
+            }
+        }
+        Pattern::Variant {
+            ref mut module,
+            ref mut name,
+            ref mut arguments,
+            ..
+        } => {
+            visitor.visit_identifier_mut(module.as_mut().unwrap_or(name));
+
+            for argument in arguments {
+                visitor.visit_pattern_mut(argument);
+            }
+        }
+    }
+}